@@ -0,0 +1,57 @@
+//! Per-frame cost of each step of the audio path at every FFT size, on mock
+//! input with the default `[audio]` settings so runs compare across machines
+//! and commits. Fails when a step goes over its budget
+//!
+//! cargo bench --bench audio
+
+use std::process::ExitCode;
+use std::time::Duration;
+
+use phosphor::config::AudioConfig;
+use phosphor::modules::audio::{time_stages, AnalysisOptions, FFT_SIZES, STAGES};
+
+const FRAMES: u32 = 500;
+
+/// Untimed frames first, through the same instances, so plans and buffers
+/// are in place before timing
+const WARMUP: u32 = 50;
+
+/// Time each of `STAGES` may take per sample of the FFT window, around four
+/// times what they need on a desktop CPU, so a regression fails here long
+/// before it costs frames. Beat detection works on a fixed number of bands
+const NANOS_PER_SAMPLE: [u32; 5] = [120, 0, 4, 6, 20];
+
+/// Least budget a stage gets, below which timer noise dominates
+const MIN_BUDGET: Duration = Duration::from_micros(2);
+
+fn main() -> ExitCode {
+    let config = AudioConfig::default();
+    let options = AnalysisOptions::from_config(&config).expect("default audio config is valid");
+
+    let mut over_budget = Vec::new();
+    for fft_size in FFT_SIZES {
+        let options = AnalysisOptions { fft_size, ..options };
+        let times = time_stages(&config, options, WARMUP, FRAMES);
+        for ((stage, time), nanos) in STAGES.iter().zip(times).zip(NANOS_PER_SAMPLE) {
+            let budget = Duration::from_nanos(u64::from(nanos) * fft_size as u64).max(MIN_BUDGET);
+            println!(
+                "audio/{:<5} {:<9} {:>9.1?}/frame  {:>5.1}% of its {:.1?}",
+                fft_size,
+                stage,
+                time,
+                time.as_secs_f64() / budget.as_secs_f64() * 100.0,
+                budget
+            );
+            if time > budget {
+                over_budget.push(format!("{} at {}", stage, fft_size));
+            }
+        }
+    }
+
+    if over_budget.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("Over budget: {}", over_budget.join(", "));
+        ExitCode::FAILURE
+    }
+}
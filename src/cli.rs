@@ -21,22 +21,66 @@ pub enum Commands {
         #[command(subcommand)]
         command: GitCommands,
     },
+    /// GitHub notifications
+    Github {
+        #[command(subcommand)]
+        command: GithubCommands,
+    },
     /// Audio device management
     Audio {
         #[command(subcommand)]
         command: AudioCommands,
     },
+    /// Last.fm / ListenBrainz scrobbling
+    Scrobble {
+        #[command(subcommand)]
+        command: ScrobbleCommands,
+    },
+    /// Listening stats from the local play log
+    Stats {
+        /// Number of top artists and tracks to show
+        #[arg(short = 'n', long, default_value_t = 10)]
+        top: usize,
+        /// Number of days in the daily breakdown
+        #[arg(short, long, default_value_t = 7)]
+        days: usize,
+    },
+    /// Built-in color themes
+    Theme {
+        #[command(subcommand)]
+        command: ThemeCommands,
+    },
     /// Configuration management
     Config {
         #[command(subcommand)]
         command: ConfigCommands,
     },
+    /// Run the dashboard with synthetic data (no network, auth or audio device)
+    Demo,
 }
 
 #[derive(Subcommand)]
 pub enum SpotifyCommands {
+    /// Sign in to Spotify and store the token
+    Login {
+        /// Print the sign-in URL and paste the redirect back instead of opening a browser (for SSH sessions)
+        #[arg(long)]
+        no_browser: bool,
+    },
+    /// Delete the stored Spotify token
+    Logout,
     /// Show currently playing track
-    Now,
+    Now {
+        /// Keep running and update the progress bar live
+        #[arg(short, long)]
+        watch: bool,
+    },
+    /// Print the open.spotify.com link of the current track
+    Url {
+        /// Also open it in the browser
+        #[arg(long)]
+        open: bool,
+    },
     /// Show synced lyrics for current track
     Lyrics,
     /// Resume playback
@@ -52,6 +96,46 @@ pub enum SpotifyCommands {
         #[arg(value_parser = clap::value_parser!(u8).range(0..=100))]
         level: u8,
     },
+    /// Jump to a position in the current track
+    Seek {
+        /// Position as SS, MM:SS or H:MM:SS (e.g. 1:23)
+        #[arg(value_parser = parse_position)]
+        position: u64,
+    },
+    /// Start a radio of recommendations seeded from the current track
+    Radio,
+    /// Pause playback after a while
+    Sleep {
+        /// How long to wait, e.g. 30m, 1h30m or 90s (a bare number is minutes)
+        #[arg(value_parser = parse_sleep)]
+        duration: std::time::Duration,
+    },
+    /// List Spotify Connect devices, or move playback to one
+    Devices {
+        /// Device name (case-insensitive, partial match) or id to transfer playback to
+        device: Option<String>,
+    },
+    /// Show recently played tracks
+    History {
+        /// Number of tracks to show (1-50)
+        #[arg(short = 'n', long, default_value_t = 20, value_parser = clap::value_parser!(u32).range(1..=50))]
+        limit: u32,
+    },
+    /// Show the upcoming queue, or add a track to it
+    Queue {
+        /// Track or episode URI (e.g. spotify:track:...) to add
+        uri: Option<String>,
+    },
+}
+
+fn parse_position(input: &str) -> Result<u64, String> {
+    crate::modules::spotify::parse_time(input)
+        .ok_or_else(|| format!("invalid position '{}' (expected SS, MM:SS or H:MM:SS)", input))
+}
+
+fn parse_sleep(input: &str) -> Result<std::time::Duration, String> {
+    crate::modules::spotify::parse_duration(input)
+        .ok_or_else(|| format!("invalid duration '{}' (expected e.g. 30m, 1h30m or 90s)", input))
 }
 
 #[derive(Subcommand)]
@@ -60,6 +144,45 @@ pub enum GitCommands {
     Status,
     /// Show recent commits across all repositories
     Log,
+    /// Start tracking a repository
+    Add {
+        /// Path inside the repository (defaults to the current directory)
+        path: Option<String>,
+    },
+    /// Stop tracking a repository
+    Remove {
+        /// Path or name of the repository, as shown by `phosphor git list`
+        repo: String,
+    },
+    /// List tracked repositories
+    List,
+}
+
+#[derive(Subcommand)]
+pub enum GithubCommands {
+    /// List unread notifications
+    Notifications,
+}
+
+#[derive(Subcommand)]
+pub enum ScrobbleCommands {
+    /// Authorize phosphor with Last.fm and print the session key for the config
+    LastfmLogin,
+}
+
+#[derive(Subcommand)]
+pub enum ThemeCommands {
+    /// List the built-in presets with a swatch of each
+    List,
+    /// Switch the dashboard to a preset
+    Set {
+        /// Preset name, e.g. green
+        name: String,
+    },
+    /// Show a preset's colors, or the configured theme's without a name
+    Preview {
+        name: Option<String>,
+    },
 }
 
 #[derive(Subcommand)]
@@ -68,6 +191,8 @@ pub enum ConfigCommands {
     Edit,
     /// Print config file path
     Path,
+    /// Print the full default config with comments
+    Default,
 }
 
 #[derive(Subcommand)]
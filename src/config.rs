@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct Config {
+    /// Playback source, in place of `spotify.backend` when set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub player: Option<String>,
     #[serde(default)]
     pub theme: ThemeConfig,
     #[serde(default)]
@@ -14,40 +18,77 @@ pub struct Config {
     pub audio: AudioConfig,
     #[serde(default)]
     pub git: GitConfig,
+    #[serde(default)]
+    pub github: GitHubConfig,
+    #[serde(default)]
+    pub lyrics: LyricsConfig,
+    #[serde(default)]
+    pub albumart: AlbumArtConfig,
+    #[serde(default)]
+    pub mpd: MpdConfig,
+    #[serde(default)]
+    pub scrobble: ScrobbleConfig,
+    #[serde(default)]
+    pub stats: StatsConfig,
+    #[serde(default)]
+    pub network: NetworkConfig,
+    #[serde(default)]
+    pub ui: UiConfig,
+    /// Keys for actions by name, replacing their defaults
+    #[serde(default)]
+    pub keys: BTreeMap<String, KeyBinding>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThemeConfig {
-    #[serde(default = "default_background")]
-    pub background: String,
-    #[serde(default = "default_foreground")]
-    pub foreground: String,
-    #[serde(default = "default_accent")]
-    pub accent: String,
-    #[serde(default = "default_dim")]
-    pub dim: String,
+    /// Built-in color scheme: "amber", "green", "paper", "dos", "gruvbox" or
+    /// "nord"; the colors below override its fields
+    #[serde(default = "default_preset")]
+    pub preset: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub foreground: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dim: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub warning: Option<String>,
+    /// Leave the terminal's own (possibly transparent) background untouched
+    #[serde(default)]
+    pub transparent_background: bool,
+    /// What the spectrum colors follow: "height" or "frequency"
+    #[serde(default = "default_spectrum_gradient")]
+    pub spectrum_gradient: String,
+    /// Panel borders: "plain", "rounded", "thick", "double", "ascii", or
+    /// "compact" for none at all
+    #[serde(default = "default_borders")]
+    pub borders: String,
 }
 
-fn default_background() -> String {
-    "#1a1000".to_string()
-}
-fn default_foreground() -> String {
-    "#ffb000".to_string()
+fn default_preset() -> String {
+    "amber".to_string()
 }
-fn default_accent() -> String {
-    "#ffcc00".to_string()
+fn default_spectrum_gradient() -> String {
+    "height".to_string()
 }
-fn default_dim() -> String {
-    "#664400".to_string()
+fn default_borders() -> String {
+    "plain".to_string()
 }
 
 impl Default for ThemeConfig {
     fn default() -> Self {
         Self {
-            background: default_background(),
-            foreground: default_foreground(),
-            accent: default_accent(),
-            dim: default_dim(),
+            preset: default_preset(),
+            background: None,
+            foreground: None,
+            accent: None,
+            dim: None,
+            warning: None,
+            transparent_background: false,
+            spectrum_gradient: default_spectrum_gradient(),
+            borders: default_borders(),
         }
     }
 }
@@ -56,12 +97,25 @@ impl Default for ThemeConfig {
 pub struct LayoutConfig {
     #[serde(default = "default_rows")]
     pub rows: Vec<Vec<String>>,
+    /// Panels in `rows` left off the screen until shown with their number key
+    #[serde(default)]
+    pub hidden: Vec<String>,
+    #[serde(default)]
+    pub spectrum: PanelRows,
+    #[serde(default)]
+    pub spectrogram: PanelRows,
+    #[serde(default)]
+    pub waveform: PanelRows,
+    #[serde(default)]
+    pub scope: PanelRows,
 }
 
 fn default_rows() -> Vec<Vec<String>> {
     vec![
-        vec!["spotify".to_string(), "spectrum".to_string()],
-        vec!["git".to_string(), "waveform".to_string()],
+        vec!["spotify".to_string(), "git".to_string()],
+        vec!["lyrics".to_string()],
+        vec!["spectrum".to_string()],
+        vec!["waveform".to_string()],
     ]
 }
 
@@ -69,6 +123,36 @@ impl Default for LayoutConfig {
     fn default() -> Self {
         Self {
             rows: default_rows(),
+            hidden: Vec::new(),
+            spectrum: PanelRows::default(),
+            spectrogram: PanelRows::default(),
+            waveform: PanelRows::default(),
+            scope: PanelRows::default(),
+        }
+    }
+}
+
+/// Height limits for a proportionally sized panel (0 = no limit)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PanelRows {
+    #[serde(default = "default_min_rows")]
+    pub min_rows: u16,
+    #[serde(default = "default_max_rows")]
+    pub max_rows: u16,
+}
+
+fn default_min_rows() -> u16 {
+    5
+}
+fn default_max_rows() -> u16 {
+    12
+}
+
+impl Default for PanelRows {
+    fn default() -> Self {
+        Self {
+            min_rows: default_min_rows(),
+            max_rows: default_max_rows(),
         }
     }
 }
@@ -77,12 +161,53 @@ impl Default for LayoutConfig {
 pub struct SpotifyConfig {
     #[serde(default)]
     pub client_id: String,
+    /// "auto" (Web API, falling back to MPRIS), "web", "mpris" or "mpd"
+    #[serde(default = "default_spotify_backend")]
+    pub backend: String,
+    /// Keep the OAuth token in the OS keyring (builds with the `keyring` feature)
+    #[serde(default = "default_spotify_keyring")]
+    pub keyring: bool,
+    /// Milliseconds between playback polls while a track is playing
+    #[serde(default = "default_spotify_poll_ms")]
+    pub poll_ms: u64,
+    /// Seconds between polls while paused or nothing is playing
+    #[serde(default = "default_spotify_idle_poll_secs")]
+    pub idle_poll_secs: u64,
+    /// Where volume keys go: "auto" (the player, falling back to the local sink
+    /// when it refuses), "player" or "pulse"
+    #[serde(default = "default_spotify_volume")]
+    pub volume: String,
+}
+
+fn default_spotify_backend() -> String {
+    "auto".to_string()
+}
+
+fn default_spotify_keyring() -> bool {
+    true
+}
+
+fn default_spotify_poll_ms() -> u64 {
+    1000
+}
+
+fn default_spotify_idle_poll_secs() -> u64 {
+    15
+}
+
+fn default_spotify_volume() -> String {
+    "auto".to_string()
 }
 
 impl Default for SpotifyConfig {
     fn default() -> Self {
         Self {
             client_id: String::new(),
+            backend: default_spotify_backend(),
+            keyring: default_spotify_keyring(),
+            poll_ms: default_spotify_poll_ms(),
+            idle_poll_secs: default_spotify_idle_poll_secs(),
+            volume: default_spotify_volume(),
         }
     }
 }
@@ -93,23 +218,235 @@ pub struct AudioConfig {
     pub device: String,
     #[serde(default = "default_fft_size")]
     pub fft_size: usize,
+    /// FFT window: "hann", "hamming", "blackman-harris" or "rectangular"
+    #[serde(default = "default_window")]
+    pub window: String,
+    /// Extra FFT four times longer for finer detail below 200 Hz
+    #[serde(default)]
+    pub bass_fft: bool,
     #[serde(default = "default_fps")]
     pub fps: u32,
+    /// "mono", "split" (left | right) or "stacked" (left over right)
+    #[serde(default = "default_stereo")]
+    pub stereo: String,
+    /// Align the waveform on a rising zero crossing so it holds still
+    #[serde(default = "default_trigger")]
+    pub trigger: bool,
+    /// Waveform trace: "bars" or "braille"
+    #[serde(default = "default_waveform")]
+    pub waveform: String,
+    /// Spectrum frequency axis: "linear", "log" or "mel"
+    #[serde(default = "default_scale")]
+    pub scale: String,
+    #[serde(default)]
+    pub spectrum: SpectrumConfig,
+    #[serde(default)]
+    pub agc: AgcConfig,
+    #[serde(default)]
+    pub smoothing: SmoothingConfig,
+    #[serde(default)]
+    pub output: OutputConfig,
 }
 
 fn default_fft_size() -> usize {
     2048
 }
+fn default_window() -> String {
+    "hann".to_string()
+}
 fn default_fps() -> u32 {
     30
 }
+fn default_stereo() -> String {
+    "mono".to_string()
+}
+fn default_trigger() -> bool {
+    true
+}
+fn default_waveform() -> String {
+    "bars".to_string()
+}
+fn default_scale() -> String {
+    "log".to_string()
+}
 
 impl Default for AudioConfig {
     fn default() -> Self {
         Self {
             device: String::new(),
             fft_size: default_fft_size(),
+            window: default_window(),
+            bass_fft: false,
             fps: default_fps(),
+            stereo: default_stereo(),
+            trigger: default_trigger(),
+            waveform: default_waveform(),
+            scale: default_scale(),
+            spectrum: SpectrumConfig::default(),
+            agc: AgcConfig::default(),
+            smoothing: SmoothingConfig::default(),
+            output: OutputConfig::default(),
+        }
+    }
+}
+
+/// Automatic gain control for the spectrum, `[audio.agc]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgcConfig {
+    /// Scale to the recent peak; off scales every frame to its own loudest bar
+    #[serde(default = "default_agc_enabled")]
+    pub enabled: bool,
+    /// Seconds to catch up with louder and quieter audio
+    #[serde(default = "default_agc_attack")]
+    pub attack: f32,
+    #[serde(default = "default_agc_release")]
+    pub release: f32,
+}
+
+fn default_agc_enabled() -> bool {
+    true
+}
+fn default_agc_attack() -> f32 {
+    0.1
+}
+fn default_agc_release() -> f32 {
+    3.0
+}
+
+impl Default for AgcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_agc_enabled(),
+            attack: default_agc_attack(),
+            release: default_agc_release(),
+        }
+    }
+}
+
+/// Frame-to-frame smoothing of the visualizers, `[audio.smoothing]`.
+/// Each is the share of the gap to the new frame closed per frame (0-1)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SmoothingConfig {
+    /// Spectrum bars rising and falling
+    #[serde(default = "default_smoothing_attack")]
+    pub attack: f32,
+    #[serde(default = "default_smoothing_decay")]
+    pub decay: f32,
+    /// The waveform, 1 for none
+    #[serde(default = "default_smoothing_waveform")]
+    pub waveform: f32,
+}
+
+fn default_smoothing_attack() -> f32 {
+    0.6
+}
+fn default_smoothing_decay() -> f32 {
+    0.15
+}
+fn default_smoothing_waveform() -> f32 {
+    0.7
+}
+
+impl Default for SmoothingConfig {
+    fn default() -> Self {
+        Self {
+            attack: default_smoothing_attack(),
+            decay: default_smoothing_decay(),
+            waveform: default_smoothing_waveform(),
+        }
+    }
+}
+
+/// Bar levels for other programs in cava's raw format, `[audio.output]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// FIFO path, or "udp://host:port"; empty = off
+    #[serde(default)]
+    pub target: String,
+    /// "ascii", "8bit" or "16bit"
+    #[serde(default = "default_output_format")]
+    pub format: String,
+    #[serde(default = "default_output_bars")]
+    pub bars: usize,
+    /// Value of a full bar in the ascii format
+    #[serde(default = "default_ascii_max")]
+    pub ascii_max: u32,
+}
+
+fn default_output_format() -> String {
+    "ascii".to_string()
+}
+fn default_output_bars() -> usize {
+    32
+}
+fn default_ascii_max() -> u32 {
+    1000
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            target: String::new(),
+            format: default_output_format(),
+            bars: default_output_bars(),
+            ascii_max: default_ascii_max(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectrumConfig {
+    /// Lowest and highest frequency shown, in Hz
+    #[serde(default = "default_min_freq")]
+    pub min_freq: f32,
+    #[serde(default = "default_max_freq")]
+    pub max_freq: f32,
+    /// Number of bars (0 = as many as fit the panel)
+    #[serde(default)]
+    pub bars: usize,
+    /// Columns per bar and blank columns between bars
+    #[serde(default = "default_bar_width")]
+    pub bar_width: u16,
+    #[serde(default)]
+    pub bar_gap: u16,
+    /// How fast the peak markers fall (0 = no markers)
+    #[serde(default = "default_gravity")]
+    pub gravity: f32,
+    /// Frequency labels in Hz along the bottom
+    #[serde(default)]
+    pub labels: bool,
+    /// "bars", "mirror" (up and down from a center line) or "wings" (bass in the middle)
+    #[serde(default = "default_spectrum_mode")]
+    pub mode: String,
+}
+
+fn default_min_freq() -> f32 {
+    30.0
+}
+fn default_max_freq() -> f32 {
+    16_000.0
+}
+fn default_bar_width() -> u16 {
+    1
+}
+fn default_gravity() -> f32 {
+    1.0
+}
+fn default_spectrum_mode() -> String {
+    "bars".to_string()
+}
+
+impl Default for SpectrumConfig {
+    fn default() -> Self {
+        Self {
+            min_freq: default_min_freq(),
+            max_freq: default_max_freq(),
+            bars: 0,
+            bar_width: default_bar_width(),
+            bar_gap: 0,
+            gravity: default_gravity(),
+            labels: false,
+            mode: default_spectrum_mode(),
         }
     }
 }
@@ -120,10 +457,61 @@ pub struct GitConfig {
     pub repos: Vec<String>,
     #[serde(default = "default_max_commits")]
     pub max_commits: usize,
+    /// Only list commits by these author emails or names (empty = everyone)
+    #[serde(default)]
+    pub only_authors: Vec<String>,
+    /// Highlight a repo once modified + untracked files exceed this (0 = off)
+    #[serde(default)]
+    pub dirty_warn_threshold: usize,
+    /// Minutes between background `git fetch`es of every repo (0 = never)
+    #[serde(default)]
+    pub fetch_interval_mins: u64,
+    /// Weeks covered by the heatmap panel
+    #[serde(default = "default_heatmap_weeks")]
+    pub heatmap_weeks: usize,
+    /// Only count commits by this author email in the heatmap (empty = everyone)
+    #[serde(default)]
+    pub heatmap_author: String,
+    #[serde(default)]
+    pub icons: GitIcons,
+    /// `[[git.repo]]` sections: repos with their own settings, tracked
+    /// alongside `repos`
+    #[serde(default, rename = "repo", skip_serializing_if = "Vec::is_empty")]
+    pub repo_settings: Vec<RepoConfig>,
+}
+
+/// Settings for one repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepoConfig {
+    pub path: String,
+    /// Shown instead of the directory name (empty = directory name)
+    #[serde(default)]
+    pub name: String,
+    /// Seconds between reads of this repo (0 = the usual 30)
+    #[serde(default)]
+    pub refresh_secs: u64,
+    /// Include it in the background `git fetch`es
+    #[serde(default = "default_repo_fetch")]
+    pub fetch: bool,
+    /// Ref to count ahead/behind against, e.g. "origin/main" (empty = the
+    /// current branch on origin)
+    #[serde(default)]
+    pub compare: String,
+    /// Leave its commits out of the commit list and heatmap
+    #[serde(default)]
+    pub ignore_commits: bool,
+}
+
+fn default_repo_fetch() -> bool {
+    true
 }
 
 fn default_max_commits() -> usize {
-    10
+    50
+}
+
+fn default_heatmap_weeks() -> usize {
+    26
 }
 
 impl Default for GitConfig {
@@ -131,10 +519,324 @@ impl Default for GitConfig {
         Self {
             repos: Vec::new(),
             max_commits: default_max_commits(),
+            only_authors: Vec::new(),
+            dirty_warn_threshold: 0,
+            fetch_interval_mins: 0,
+            heatmap_weeks: default_heatmap_weeks(),
+            heatmap_author: String::new(),
+            icons: GitIcons::default(),
+            repo_settings: Vec::new(),
         }
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GitIcons {
+    #[serde(default)]
+    pub branch: String,
+    #[serde(default = "default_icon_clean")]
+    pub clean: String,
+    #[serde(default = "default_icon_dirty")]
+    pub dirty: String,
+    #[serde(default = "default_icon_warn")]
+    pub warn: String,
+    #[serde(default = "default_icon_ahead")]
+    pub ahead: String,
+    #[serde(default = "default_icon_behind")]
+    pub behind: String,
+    #[serde(default = "default_icon_stash")]
+    pub stash: String,
+    #[serde(default = "default_icon_conflict")]
+    pub conflict: String,
+    #[serde(default = "default_icon_submodule")]
+    pub submodule: String,
+}
+
+fn default_icon_clean() -> String {
+    "✓".to_string()
+}
+fn default_icon_dirty() -> String {
+    "●".to_string()
+}
+fn default_icon_warn() -> String {
+    "▲".to_string()
+}
+fn default_icon_ahead() -> String {
+    "↑".to_string()
+}
+fn default_icon_behind() -> String {
+    "↓".to_string()
+}
+fn default_icon_stash() -> String {
+    "⚑".to_string()
+}
+fn default_icon_conflict() -> String {
+    "‼".to_string()
+}
+fn default_icon_submodule() -> String {
+    "◇".to_string()
+}
+
+impl Default for GitIcons {
+    fn default() -> Self {
+        Self {
+            branch: String::new(),
+            clean: default_icon_clean(),
+            dirty: default_icon_dirty(),
+            warn: default_icon_warn(),
+            ahead: default_icon_ahead(),
+            behind: default_icon_behind(),
+            stash: default_icon_stash(),
+            conflict: default_icon_conflict(),
+            submodule: default_icon_submodule(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LyricsConfig {
+    /// Lines before/after the current line that stay highlighted
+    #[serde(default = "default_context_lines")]
+    pub context_lines: usize,
+    /// Fade context lines progressively with distance from the current line
+    #[serde(default = "default_fade")]
+    pub fade: bool,
+    /// Reorder right-to-left lines into visual order (disable if the terminal does bidi)
+    #[serde(default = "default_reorder_rtl")]
+    pub reorder_rtl: bool,
+}
+
+fn default_context_lines() -> usize {
+    2
+}
+fn default_fade() -> bool {
+    true
+}
+fn default_reorder_rtl() -> bool {
+    true
+}
+
+impl Default for LyricsConfig {
+    fn default() -> Self {
+        Self {
+            context_lines: default_context_lines(),
+            fade: default_fade(),
+            reorder_rtl: default_reorder_rtl(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlbumArtConfig {
+    /// Height:width ratio of a terminal cell, used to keep covers square
+    #[serde(default = "default_cell_aspect")]
+    pub cell_aspect: f32,
+}
+
+fn default_cell_aspect() -> f32 {
+    2.0
+}
+
+impl Default for AlbumArtConfig {
+    fn default() -> Self {
+        Self {
+            cell_aspect: default_cell_aspect(),
+        }
+    }
+}
+
+/// Music Player Daemon connection, used with `player = "mpd"`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MpdConfig {
+    /// Hostname, or the path of MPD's Unix socket
+    #[serde(default = "default_mpd_host")]
+    pub host: String,
+    #[serde(default = "default_mpd_port")]
+    pub port: u16,
+    #[serde(default)]
+    pub password: String,
+}
+
+fn default_mpd_host() -> String {
+    "localhost".to_string()
+}
+
+fn default_mpd_port() -> u16 {
+    6600
+}
+
+impl Default for MpdConfig {
+    fn default() -> Self {
+        Self {
+            host: default_mpd_host(),
+            port: default_mpd_port(),
+            password: String::new(),
+        }
+    }
+}
+
+/// Scrobbling targets; each one is enabled once its credentials are set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScrobbleConfig {
+    #[serde(default)]
+    pub lastfm_api_key: String,
+    #[serde(default)]
+    pub lastfm_api_secret: String,
+    /// Obtained with `phosphor scrobble lastfm-login`
+    #[serde(default)]
+    pub lastfm_session_key: String,
+    #[serde(default)]
+    pub listenbrainz_token: String,
+}
+
+/// Notifications for the github panel and `phosphor github notifications`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GitHubConfig {
+    /// Personal access token with the `notifications` scope (empty = use `GITHUB_TOKEN`)
+    #[serde(default)]
+    pub token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsConfig {
+    /// Log played tracks to the data directory for `phosphor stats`
+    #[serde(default = "default_stats_record")]
+    pub record: bool,
+}
+
+fn default_stats_record() -> bool {
+    true
+}
+
+impl Default for StatsConfig {
+    fn default() -> Self {
+        Self {
+            record: default_stats_record(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Proxy URL for all outbound requests (empty = use HTTP(S)_PROXY env vars)
+    #[serde(default)]
+    pub proxy: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// Show the PHOSPHOR logo while subsystems start up
+    #[serde(default = "default_splash")]
+    pub splash: bool,
+    /// Run a memory check and device checks before the logo
+    #[serde(default = "default_boot_sequence")]
+    pub boot_sequence: bool,
+    /// Pulse the now-playing title brightness on detected beats
+    #[serde(default)]
+    pub beat_pulse: bool,
+    /// Flash the theme accent (focused border, highlights) on detected beats
+    #[serde(default)]
+    pub beat_accent: bool,
+    /// Dim the dashboard when the sleep timer pauses playback
+    #[serde(default = "default_sleep_dim")]
+    pub sleep_dim: bool,
+    /// One line along the bottom with the time, device, audio backend,
+    /// volume and key hints
+    #[serde(default)]
+    pub status_bar: bool,
+    /// Minutes without input before the screensaver takes over (0 = never)
+    #[serde(default)]
+    pub screensaver_mins: u64,
+    /// Drop to a couple of frames a second and stop capturing audio while
+    /// the terminal is in the background or nothing is playing
+    #[serde(default)]
+    pub power_save: bool,
+    #[serde(default)]
+    pub crt: CrtConfig,
+}
+
+fn default_splash() -> bool {
+    true
+}
+
+fn default_boot_sequence() -> bool {
+    true
+}
+
+fn default_sleep_dim() -> bool {
+    true
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            splash: default_splash(),
+            boot_sequence: default_boot_sequence(),
+            beat_pulse: false,
+            beat_accent: false,
+            sleep_dim: default_sleep_dim(),
+            status_bar: false,
+            screensaver_mins: 0,
+            power_save: false,
+            crt: CrtConfig::default(),
+        }
+    }
+}
+
+/// CRT effects applied to every frame, `[ui.crt]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CrtConfig {
+    /// Darken every other row
+    #[serde(default)]
+    pub scanlines: bool,
+    /// Tint the blank cells around bright text, like phosphor bleeding
+    #[serde(default)]
+    pub glow: bool,
+    /// Dip the brightness a little at random
+    #[serde(default)]
+    pub flicker: bool,
+    /// Strength of all three (0-1)
+    #[serde(default = "default_crt_intensity")]
+    pub intensity: f32,
+}
+
+fn default_crt_intensity() -> f32 {
+    0.5
+}
+
+impl Default for CrtConfig {
+    fn default() -> Self {
+        Self {
+            scanlines: false,
+            glow: false,
+            flicker: false,
+            intensity: default_crt_intensity(),
+        }
+    }
+}
+
+/// One key sequence (keys separated by spaces, e.g. "g r"), or a list of
+/// alternatives
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum KeyBinding {
+    One(String),
+    Many(Vec<String>),
+}
+
+impl KeyBinding {
+    pub fn sequences(&self) -> Vec<&str> {
+        match self {
+            KeyBinding::One(sequence) => vec![sequence.as_str()],
+            KeyBinding::Many(sequences) => sequences.iter().map(String::as_str).collect(),
+        }
+    }
+}
+
+/// Fully annotated default config, kept in sync with the structs above (the
+/// tests check it parses to `Config::default()` without unknown keys)
+pub const DEFAULT_TEMPLATE: &str = include_str!("../config.example.toml");
+
 impl Config {
     pub fn path() -> PathBuf {
         dirs::config_dir()
@@ -143,13 +845,32 @@ impl Config {
             .join("config.toml")
     }
 
+    /// Playback source to connect to: top-level `player` when set, otherwise
+    /// `spotify.backend`
+    pub fn player_backend(&self) -> &str {
+        self.player.as_deref().unwrap_or(&self.spotify.backend)
+    }
+
+    /// Directory for persisted state (tokens, caches), overridable via `PHOSPHOR_DATA_DIR`
+    pub fn data_dir() -> PathBuf {
+        if let Ok(dir) = std::env::var("PHOSPHOR_DATA_DIR")
+            && !dir.is_empty()
+        {
+            return PathBuf::from(shellexpand::tilde(&dir).as_ref());
+        }
+
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("phosphor")
+    }
+
     pub fn load() -> Result<Self> {
         let path = Self::path();
 
         if !path.exists() {
-            let config = Config::default();
-            config.save()?;
-            return Ok(config);
+            // Start users off with the annotated template rather than a bare dump
+            Self::write_default()?;
+            return Ok(Config::default());
         }
 
         let contents = std::fs::read_to_string(&path)
@@ -161,28 +882,113 @@ impl Config {
         Ok(config)
     }
 
+    /// Write settings that differ from the config file back to it, keeping
+    /// its comments and layout and leaving everything else untouched
     pub fn save(&self) -> Result<()> {
         let path = Self::path();
+        let contents = std::fs::read_to_string(&path).unwrap_or_default();
+
+        let mut document: toml_edit::DocumentMut = contents
+            .parse()
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+        let on_disk: Config = toml::from_str(&contents)
+            .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
+
+        let old = toml::Table::try_from(&on_disk)?;
+        let new = toml::Table::try_from(self)?;
+        apply_changes(document.as_table_mut(), &old, &new)?;
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, document.to_string())
+            .with_context(|| format!("Failed to write config file: {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Write the annotated default template to the config path
+    pub fn write_default() -> Result<()> {
+        let path = Self::path();
 
         if let Some(parent) = path.parent() {
             std::fs::create_dir_all(parent)?;
         }
 
-        let contents = toml::to_string_pretty(self)?;
-        std::fs::write(&path, contents)?;
+        std::fs::write(&path, DEFAULT_TEMPLATE)?;
 
         Ok(())
     }
 }
 
-impl Default for Config {
-    fn default() -> Self {
-        Self {
-            theme: ThemeConfig::default(),
-            layout: LayoutConfig::default(),
-            spotify: SpotifyConfig::default(),
-            audio: AudioConfig::default(),
-            git: GitConfig::default(),
+/// Copy the keys that differ between `old` and `new` into `table`, recursing
+/// into sections so untouched ones keep their formatting
+fn apply_changes(table: &mut dyn toml_edit::TableLike, old: &toml::Table, new: &toml::Table) -> Result<()> {
+    for (key, value) in new {
+        match (old.get(key), value) {
+            (Some(old_value), _) if old_value == value => {}
+            (Some(toml::Value::Table(old_section)), toml::Value::Table(new_section)) => {
+                let section = table.entry(key).or_insert_with(toml_edit::table);
+                if let Some(section) = section.as_table_like_mut() {
+                    apply_changes(section, old_section, new_section)?;
+                }
+            }
+            _ => {
+                let mut value: toml_edit::Value = value.to_string().parse()?;
+                match table.get_mut(key).and_then(toml_edit::Item::as_value_mut) {
+                    // Keep the comments around it
+                    Some(existing) => {
+                        *value.decor_mut() = existing.decor().clone();
+                        *existing = value;
+                    }
+                    None => {
+                        table.insert(key, toml_edit::value(value));
+                    }
+                }
+            }
         }
     }
+    for key in old.keys().filter(|key| !new.contains_key(*key)) {
+        table.remove(key);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dotted paths of keys in `table` that `known` doesn't have; serde would
+    /// ignore them, so a typo in the template would go unnoticed
+    fn unknown_keys(table: &toml::Table, known: &toml::Table, prefix: &str) -> Vec<String> {
+        let mut unknown = Vec::new();
+        for (key, value) in table {
+            let path = format!("{}{}", prefix, key);
+            match (value, known.get(key)) {
+                (_, None) => unknown.push(path),
+                (toml::Value::Table(section), Some(toml::Value::Table(known_section))) => {
+                    unknown.extend(unknown_keys(section, known_section, &format!("{}.", path)));
+                }
+                _ => {}
+            }
+        }
+        unknown
+    }
+
+    #[test]
+    fn template_parses_to_the_defaults() {
+        let parsed: Config = toml::from_str(DEFAULT_TEMPLATE).expect("template parses");
+        assert_eq!(
+            toml::to_string(&parsed).unwrap(),
+            toml::to_string(&Config::default()).unwrap(),
+            "config.example.toml differs from Config::default()"
+        );
+    }
+
+    #[test]
+    fn template_has_no_unknown_keys() {
+        let template: toml::Table = toml::from_str(DEFAULT_TEMPLATE).unwrap();
+        let known = toml::Table::try_from(Config::default()).unwrap();
+        assert_eq!(unknown_keys(&template, &known, ""), Vec::<String>::new());
+    }
 }
@@ -0,0 +1,5 @@
+//! The dashboard and its data sources, shared by the binary and the benches
+
+pub mod config;
+pub mod modules;
+pub mod tui;
@@ -1,21 +1,32 @@
 mod cli;
-mod config;
-mod modules;
-mod tui;
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use clap::Parser;
-use cli::{Cli, Commands, GitCommands, SpotifyCommands, ConfigCommands, AudioCommands};
+use modules::player::Player;
+use phosphor::{config, modules, tui};
+use std::path::{Path, PathBuf};
+use cli::{Cli, Commands, GitCommands, GithubCommands, SpotifyCommands, ScrobbleCommands, ThemeCommands, ConfigCommands, AudioCommands};
 
-#[tokio::main]
-async fn main() -> Result<()> {
+fn main() -> Result<()> {
+    // Has to happen while we're still single-threaded
+    modules::net::export_proxy_env();
+
+    tokio::runtime::Runtime::new()?.block_on(run())
+}
+
+async fn run() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
         Some(Commands::Spotify { command }) => handle_spotify(command).await?,
         Some(Commands::Git { command }) => handle_git(command).await?,
+        Some(Commands::Github { command }) => handle_github(command)?,
         Some(Commands::Audio { command }) => handle_audio(command)?,
+        Some(Commands::Scrobble { command }) => handle_scrobble(command)?,
+        Some(Commands::Stats { top, days }) => handle_stats(top, days)?,
+        Some(Commands::Theme { command }) => handle_theme(command)?,
         Some(Commands::Config { command }) => handle_config(command)?,
+        Some(Commands::Demo) => tui::run_demo().await?,
         None => tui::run().await?,
     }
 
@@ -24,33 +35,75 @@ async fn main() -> Result<()> {
 
 async fn handle_spotify(command: SpotifyCommands) -> Result<()> {
     let config = config::Config::load()?;
+
+    // Signing in and out must not go through the cached token that `new` would reuse
+    match command {
+        SpotifyCommands::Login { no_browser } => {
+            let flow = if no_browser {
+                modules::spotify::AuthFlow::Manual
+            } else {
+                modules::spotify::AuthFlow::Browser
+            };
+            let token = modules::spotify::SpotifyClient::login(&config, flow).await?;
+            println!("✓ Signed in to Spotify");
+            println!("  Token:  {}", token.storage);
+            println!("  Scopes: {}", token.scopes.join(", "));
+            if let Some(remaining) = token
+                .expires_at
+                .and_then(|at| at.duration_since(std::time::SystemTime::now()).ok())
+            {
+                println!(
+                    "  Access token expires in {}m (renewed automatically)",
+                    remaining.as_secs() / 60
+                );
+            }
+            return Ok(());
+        }
+        SpotifyCommands::Logout => {
+            if modules::spotify::SpotifyClient::logout()? {
+                println!("✓ Signed out of Spotify");
+            } else {
+                println!("Not signed in");
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
     let spotify = modules::spotify::SpotifyClient::new(&config).await?;
 
     match command {
-        SpotifyCommands::Now => {
+        SpotifyCommands::Login { .. } | SpotifyCommands::Logout => unreachable!("handled above"),
+        SpotifyCommands::Now { watch: true } => {
+            watch_now_playing(&spotify).await?;
+        }
+        SpotifyCommands::Now { watch: false } => {
             if let Some(track) = spotify.get_current_track().await? {
                 println!("♫ {} - {}", track.name, track.artist);
                 println!("  Album: {}", track.album);
+                if let Some(context) = &track.context {
+                    println!("  Playing from: {}", context.label());
+                }
                 if let Some(progress) = track.progress {
-                    let duration = track.duration;
-                    let pct = (progress as f64 / duration as f64 * 100.0) as u32;
-                    let bar_width = 30;
-                    let filled = (pct as usize * bar_width / 100).min(bar_width);
-                    let empty = bar_width - filled;
-                    println!(
-                        "  [{}{}] {:02}:{:02} / {:02}:{:02}",
-                        "█".repeat(filled),
-                        "░".repeat(empty),
-                        progress / 60000,
-                        (progress / 1000) % 60,
-                        duration / 60000,
-                        (duration / 1000) % 60
-                    );
+                    println!("  {}", progress_bar(progress, track.duration));
                 }
             } else {
                 println!("Nothing playing");
             }
         }
+        SpotifyCommands::Url { open } => {
+            let track = spotify
+                .get_current_track()
+                .await?
+                .context("Nothing playing")?;
+            let url = track
+                .web_url()
+                .with_context(|| format!("No Spotify link for {} - {}", track.name, track.artist))?;
+            println!("{}", url);
+            if open {
+                open::that(&url).context("Failed to open the browser")?;
+            }
+        }
         SpotifyCommands::Play => {
             spotify.play().await?;
             println!("▶ Playing");
@@ -68,8 +121,75 @@ async fn handle_spotify(command: SpotifyCommands) -> Result<()> {
             println!("⏮ Previous track");
         }
         SpotifyCommands::Vol { level } => {
-            spotify.set_volume(level).await?;
-            println!("🔊 Volume: {}%", level);
+            let mut volume = modules::volume::VolumeControl::new(&config.spotify)?;
+            volume.set(&spotify, level).await?;
+            println!("🔊 Volume: {}% ({})", level, volume.label(&spotify));
+        }
+        SpotifyCommands::Seek { position } => {
+            spotify.seek(position).await?;
+            let with_hours = modules::spotify::needs_hours(position);
+            println!("⏩ Seeked to {}", modules::spotify::format_time(position, with_hours));
+        }
+        SpotifyCommands::Radio => {
+            let radio = spotify.start_radio().await?;
+            println!("📻 Radio from {}", radio.seed);
+            println!("  {} tracks", radio.tracks);
+        }
+        SpotifyCommands::Sleep { duration } => {
+            let ms = duration.as_millis() as u64;
+            let with_hours = modules::spotify::needs_hours(ms);
+            println!(
+                "☾ Pausing in {} (Ctrl+C to cancel)",
+                modules::spotify::format_time(ms, with_hours)
+            );
+            tokio::time::sleep(duration).await;
+            spotify.pause().await?;
+            println!("⏸ Paused");
+        }
+        SpotifyCommands::Devices { device: None } => {
+            let devices = spotify.list_devices().await?;
+            if devices.is_empty() {
+                println!("No devices found - open Spotify on a device first");
+            }
+            for device in &devices {
+                let marker = if device.is_active { "▶" } else { " " };
+                let volume = device.volume.map(|v| format!("  {}%", v)).unwrap_or_default();
+                println!("{} {} ({}){}", marker, device.name, device.kind, volume);
+            }
+        }
+        SpotifyCommands::Devices { device: Some(target) } => {
+            let devices = spotify.list_devices().await?;
+            let needle = target.to_lowercase();
+            let device = devices
+                .iter()
+                .find(|d| d.id == target)
+                .or_else(|| devices.iter().find(|d| d.name.to_lowercase().contains(&needle)))
+                .with_context(|| format!("No device matching '{}'", target))?;
+            spotify.transfer_playback(&device.id).await?;
+            println!("🔈 Playing on {}", device.name);
+        }
+        SpotifyCommands::History { limit } => {
+            let history = spotify.recently_played(limit).await?;
+            if history.is_empty() {
+                println!("Nothing played recently");
+            }
+            for item in &history {
+                let ago = modules::spotify::format_ago(item.played_at);
+                println!("{:>9}  {} - {}", ago, item.name, item.artist);
+            }
+        }
+        SpotifyCommands::Queue { uri: Some(uri) } => {
+            spotify.add_to_queue(&uri).await?;
+            println!("➕ Added to queue");
+        }
+        SpotifyCommands::Queue { uri: None } => {
+            let queue = spotify.get_queue().await?;
+            if queue.is_empty() {
+                println!("Queue is empty");
+            }
+            for (i, item) in queue.iter().enumerate() {
+                println!("{:>2}. {} - {}", i + 1, item.name, item.artist);
+            }
         }
         SpotifyCommands::Lyrics => {
             if let Some(track) = spotify.get_current_track().await? {
@@ -116,41 +236,320 @@ async fn handle_spotify(command: SpotifyCommands) -> Result<()> {
 }
 
 async fn handle_git(command: GitCommands) -> Result<()> {
-    let config = config::Config::load()?;
-    let git = modules::git::GitTracker::new(&config.git.repos);
+    let mut config = config::Config::load()?;
+    let git = modules::git::GitTracker::new(&config.git);
 
     match command {
         GitCommands::Status => {
             let repos = git.get_status()?;
+            let icons = &config.git.icons;
             for repo in repos {
-                let branch_icon = if repo.is_clean { "" } else { "" };
                 let sync_status = match (repo.ahead, repo.behind) {
                     (0, 0) => String::new(),
-                    (a, 0) => format!(" ↑{}", a),
-                    (0, b) => format!(" ↓{}", b),
-                    (a, b) => format!(" ↑{} ↓{}", a, b),
+                    (a, 0) => format!(" {}{}", icons.ahead, a),
+                    (0, b) => format!(" {}{}", icons.behind, b),
+                    (a, b) => format!(" {}{} {}{}", icons.ahead, a, icons.behind, b),
+                };
+                let status_icon = if repo.is_clean {
+                    &icons.clean
+                } else if repo.exceeds_dirty_threshold(config.git.dirty_warn_threshold) {
+                    &icons.warn
+                } else {
+                    &icons.dirty
+                };
+                let conflicts = match repo.conflicts {
+                    0 => String::new(),
+                    n => format!(" {}{}", icons.conflict, n),
+                };
+                let diffstat = if repo.insertions + repo.deletions > 0 {
+                    format!(" +{} −{}", repo.insertions, repo.deletions)
+                } else {
+                    String::new()
+                };
+                let stashes = match repo.stashes {
+                    0 => String::new(),
+                    n => format!(" {}{}", icons.stash, n),
+                };
+                let submodules = match repo.stale_submodules {
+                    0 => String::new(),
+                    n => format!(" {}{}", icons.submodule, n),
                 };
                 println!(
-                    "{} {} {} {}{}",
-                    branch_icon,
+                    "{} {} {} {}{}{}{}{}{}",
+                    icons.branch,
                     repo.name,
-                    repo.branch,
-                    if repo.is_clean { "✓" } else { "●" },
-                    sync_status
+                    repo.branch_label(),
+                    status_icon,
+                    diffstat,
+                    conflicts,
+                    sync_status,
+                    stashes,
+                    submodules
                 );
             }
         }
         GitCommands::Log => {
-            let commits = git.get_recent_commits(config.git.max_commits)?;
+            let commits = git.get_recent_commits(config.git.max_commits, &config.git.only_authors)?;
             for commit in commits {
                 println!(
-                    " {} {} - {} ({})",
+                    " {} {} - {} ({}, {})",
                     &commit.hash[..7],
                     commit.message,
                     commit.author,
-                    commit.repo_name
+                    commit.repo_name,
+                    modules::spotify::format_ago(commit.timestamp())
+                );
+            }
+        }
+        GitCommands::Add { path } => {
+            let root = modules::git::repo_root(Path::new(path.as_deref().unwrap_or(".")))?;
+            if tracked_paths(&config.git).any(|repo| is_same_repo(repo, &root)) {
+                println!("Already tracking {}", root.display());
+                return Ok(());
+            }
+            config.git.repos.push(home_relative(&root));
+            config.save()?;
+            println!("✓ Tracking {}", root.display());
+        }
+        GitCommands::Remove { repo } => {
+            let target = expand_path(&repo);
+            let target = target.canonicalize().unwrap_or(target);
+            let matches = |entry: &str| {
+                entry == repo
+                    || is_same_repo(entry, &target)
+                    || expand_path(entry).file_name().is_some_and(|name| *name == *repo)
+            };
+            if let Some(settings) = config.git.repo_settings.iter().find(|settings| matches(&settings.path)) {
+                bail!(
+                    "{} has a [[git.repo]] section; remove it from {} instead",
+                    settings.path,
+                    config::Config::path().display()
                 );
             }
+            let index = config
+                .git
+                .repos
+                .iter()
+                .position(|entry| matches(entry))
+                .with_context(|| format!("{} isn't tracked (see `phosphor git list`)", repo))?;
+            let removed = config.git.repos.remove(index);
+            config.save()?;
+            println!("✓ Stopped tracking {}", removed);
+        }
+        GitCommands::List => {
+            let mut listed: Vec<&str> = Vec::new();
+            for repo in tracked_paths(&config.git) {
+                if listed.contains(&repo) {
+                    continue;
+                }
+                listed.push(repo);
+                match modules::git::repo_root(&expand_path(repo)) {
+                    Ok(_) => println!("  {}", repo),
+                    Err(e) => println!("✗ {} ({})", repo, e),
+                }
+            }
+            if listed.is_empty() {
+                println!("No repositories tracked (add one with `phosphor git add`)");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// `git.repos` and the paths of `[[git.repo]]` sections
+fn tracked_paths(config: &config::GitConfig) -> impl Iterator<Item = &str> {
+    config
+        .repos
+        .iter()
+        .map(String::as_str)
+        .chain(config.repo_settings.iter().map(|settings| settings.path.as_str()))
+}
+
+fn expand_path(path: &str) -> PathBuf {
+    PathBuf::from(shellexpand::tilde(path).as_ref())
+}
+
+/// Whether a `git.repos` entry points at `root` (canonical)
+fn is_same_repo(entry: &str, root: &Path) -> bool {
+    let path = expand_path(entry);
+    path.canonicalize().unwrap_or(path) == root
+}
+
+/// `~/...` for paths under the home directory, like the config examples
+fn home_relative(path: &Path) -> String {
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(relative) => format!("~/{}", relative.display()),
+        None => path.display().to_string(),
+    }
+}
+
+fn handle_github(command: GithubCommands) -> Result<()> {
+    let config = config::Config::load()?;
+    let github = modules::github::GitHubClient::new(&config.github)
+        .context("No GitHub token: set github.token in the config or GITHUB_TOKEN")?;
+
+    match command {
+        GithubCommands::Notifications => {
+            let notifications = github.notifications()?;
+            if notifications.is_empty() {
+                println!("No unread notifications");
+            }
+            for notification in notifications {
+                let ago = modules::spotify::format_ago(notification.updated_at);
+                println!(
+                    "[{}] {} - {} ({})",
+                    notification.label(),
+                    notification.title,
+                    notification.repo,
+                    ago
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_scrobble(command: ScrobbleCommands) -> Result<()> {
+    let config = config::Config::load()?;
+
+    match command {
+        ScrobbleCommands::LastfmLogin => {
+            let (user, session_key) = modules::scrobble::lastfm_login(&config.scrobble)?;
+            println!("✓ Authorized as {}", user);
+            println!("Add this under [scrobble] in {}:", config::Config::path().display());
+            println!("  lastfm_session_key = \"{}\"", session_key);
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_stats(top: usize, days: usize) -> Result<()> {
+    let records = modules::stats::load()?;
+    if records.is_empty() {
+        println!("No plays recorded yet (log: {})", modules::stats::log_path().display());
+        return Ok(());
+    }
+
+    let stats = modules::stats::compute(&records, top, days);
+    match stats.first_day {
+        Some(day) => println!("{} plays since {}", stats.plays, day),
+        None => println!("{} plays", stats.plays),
+    }
+
+    println!("\nTop artists");
+    for (i, (artist, plays)) in stats.top_artists.iter().enumerate() {
+        println!("  {:>2}. {} ({})", i + 1, artist, plays);
+    }
+
+    println!("\nTop tracks");
+    for (i, (track, plays)) in stats.top_tracks.iter().enumerate() {
+        println!("  {:>2}. {} ({})", i + 1, track, plays);
+    }
+
+    println!("\nHours per day");
+    let max = stats.daily_hours.iter().map(|(_, h)| *h).fold(0.0, f64::max);
+    for (day, hours) in &stats.daily_hours {
+        let width = if max > 0.0 { (hours / max * 30.0).round() as usize } else { 0 };
+        println!("  {}  {:<30} {:.1}h", day, "█".repeat(width), hours);
+    }
+
+    println!(
+        "\nStreak: {} day{} (longest {})",
+        stats.current_streak,
+        if stats.current_streak == 1 { "" } else { "s" },
+        stats.longest_streak
+    );
+
+    Ok(())
+}
+
+fn handle_theme(command: ThemeCommands) -> Result<()> {
+    use ratatui::style::Color;
+    use tui::theme::{Preset, Theme, PRESETS};
+
+    let mut config = config::Config::load()?;
+
+    let fg = |color: Color| match color {
+        Color::Rgb(r, g, b) => format!("\x1b[38;2;{};{};{}m", r, g, b),
+        _ => String::new(),
+    };
+    let bg = |color: Color| match color {
+        Color::Rgb(r, g, b) => format!("\x1b[48;2;{};{};{}m", r, g, b),
+        _ => String::new(),
+    };
+    let hex = |color: Color| match color {
+        Color::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+        other => format!("{:?}", other),
+    };
+    // A line of sample text in each color with the spectrum gradient, on the
+    // theme's background
+    let swatch = |theme: &Theme| {
+        let gradient: String = (0..8)
+            .map(|i| format!("{}{}", fg(theme.gradient(i as f32 / 7.0)), ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"][i]))
+            .collect();
+        format!(
+            "{} {}text {}accent {}dim {}warning {} \x1b[0m",
+            bg(theme.background),
+            fg(theme.foreground),
+            fg(theme.accent),
+            fg(theme.dim),
+            fg(theme.warning),
+            gradient
+        )
+    };
+
+    match command {
+        ThemeCommands::List => {
+            for preset in &PRESETS {
+                let current = if preset.name == config.theme.preset.trim().to_lowercase() { "*" } else { " " };
+                println!("{} {:<8} {:<22} {}", current, preset.name, preset.description, swatch(&preset.theme()));
+            }
+        }
+        ThemeCommands::Set { name } => {
+            let preset = Preset::named(&name)?;
+            config.theme.preset = preset.name.to_string();
+            config.save()?;
+            println!("✓ Theme set to {} ({})", preset.name, preset.description);
+
+            let theme = &config.theme;
+            let overrides: Vec<&str> = [
+                ("background", &theme.background),
+                ("foreground", &theme.foreground),
+                ("accent", &theme.accent),
+                ("dim", &theme.dim),
+                ("warning", &theme.warning),
+            ]
+            .into_iter()
+            .filter(|(_, value)| value.is_some())
+            .map(|(key, _)| key)
+            .collect();
+            if !overrides.is_empty() {
+                println!(
+                    "  {} in {} still override it",
+                    overrides.join(", "),
+                    config::Config::path().display()
+                );
+            }
+        }
+        ThemeCommands::Preview { name } => {
+            let theme = match &name {
+                Some(name) => Preset::named(name)?.theme(),
+                None => Theme::from_config(&config.theme),
+            };
+            println!("{}", name.as_deref().unwrap_or(&config.theme.preset));
+            for (key, color) in [
+                ("background", theme.background),
+                ("foreground", theme.foreground),
+                ("accent", theme.accent),
+                ("dim", theme.dim),
+                ("warning", theme.warning),
+            ] {
+                println!("  {:<10} {}  {}      \x1b[0m", key, hex(color), bg(color));
+            }
+            println!("  {}", swatch(&theme));
         }
     }
 
@@ -169,53 +568,151 @@ fn handle_config(command: ConfigCommands) -> Result<()> {
         ConfigCommands::Path => {
             println!("{}", config::Config::path().display());
         }
+        ConfigCommands::Default => {
+            print!("{}", config::DEFAULT_TEMPLATE);
+        }
     }
 
     Ok(())
 }
 
-#[cfg(feature = "audio")]
+/// `[████░░░░] 01:23 / 03:45` style progress line
+fn progress_bar(progress: u64, duration: u64) -> String {
+    let pct = (progress as f64 / duration as f64 * 100.0) as u32;
+    let bar_width = 30;
+    let filled = (pct as usize * bar_width / 100).min(bar_width);
+    let empty = bar_width - filled;
+    let with_hours = modules::spotify::needs_hours(duration);
+    format!(
+        "[{}{}] {} / {}",
+        "█".repeat(filled),
+        "░".repeat(empty),
+        modules::spotify::format_time(progress, with_hours),
+        modules::spotify::format_time(duration, with_hours)
+    )
+}
+
+/// Redraw the now-playing line in place until interrupted, polling the player
+/// occasionally and interpolating progress in between
+async fn watch_now_playing(player: &impl Player) -> Result<()> {
+    use std::io::Write;
+    use std::time::{Duration, Instant};
+
+    const POLL_INTERVAL: Duration = Duration::from_secs(5);
+    const TICK: Duration = Duration::from_millis(250);
+
+    let mut clock = modules::spotify::PlaybackClock::new();
+    let mut track = None;
+    let mut last_poll: Option<Instant> = None;
+
+    loop {
+        if last_poll.is_none_or(|t| t.elapsed() >= POLL_INTERVAL) {
+            // Keep showing the last known track through transient API errors
+            if let Ok(current) = player.get_current_track().await {
+                if let Some(ref t) = current {
+                    clock.sync(t);
+                }
+                track = current;
+            }
+            last_poll = Some(Instant::now());
+        }
+
+        let line = match &track {
+            Some(t) => format!(
+                "♫ {} - {}  {}",
+                t.name,
+                t.artist,
+                progress_bar(clock.now_ms(), t.duration)
+            ),
+            None => "Nothing playing".to_string(),
+        };
+        print!("\r\x1b[2K{}", line);
+        std::io::stdout().flush()?;
+
+        tokio::time::sleep(TICK).await;
+    }
+}
+
 fn handle_audio(command: AudioCommands) -> Result<()> {
+    match command {
+        AudioCommands::Devices => list_audio_devices(),
+    }
+}
+
+#[cfg(feature = "audio")]
+fn list_audio_devices() -> Result<()> {
     use cpal::traits::{DeviceTrait, HostTrait};
 
-    match command {
-        AudioCommands::Devices => {
-            let host = cpal::default_host();
-
-            // Get default monitor source name
-            let default_monitor = std::process::Command::new("pactl")
-                .args(["get-default-sink"])
-                .output()
-                .ok()
-                .filter(|o| o.status.success())
-                .map(|o| format!("{}.monitor", String::from_utf8_lossy(&o.stdout).trim()));
-
-            println!("Audio input devices (cpal):");
-            println!("─────────────────────────────");
-
-            if let Ok(devices) = host.input_devices() {
-                for device in devices {
-                    let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
-                    let is_default = default_monitor.as_ref().map_or(false, |m| name.contains(m));
-                    let marker = if is_default { " ← default monitor" } else { "" };
-                    println!("  {}{}", name, marker);
-                }
+    let host = cpal::default_host();
+
+    // Get default monitor source name (PulseAudio/PipeWire only)
+    let default_monitor = std::process::Command::new("pactl")
+        .args(["get-default-sink"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| format!("{}.monitor", String::from_utf8_lossy(&o.stdout).trim()));
+
+    println!("Audio input devices (cpal):");
+    println!("─────────────────────────────");
+
+    if let Ok(devices) = host.input_devices() {
+        for device in devices {
+            let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+            let is_default = default_monitor.as_ref().is_some_and(|m| name.contains(m));
+            let marker = if is_default { " ← default monitor" } else { "" };
+            println!("  {}{}", name, marker);
+        }
+    }
+
+    // WASAPI captures output devices in loopback mode
+    #[cfg(target_os = "windows")]
+    {
+        println!();
+        println!("Audio output devices (WASAPI loopback):");
+        println!("───────────────────────────────────────");
+        // With no audio.device set, the default output is what gets captured
+        let default_output = host.default_output_device().and_then(|d| d.name().ok());
+        if let Ok(devices) = host.output_devices() {
+            for device in devices {
+                let name = device.name().unwrap_or_else(|_| "Unknown".to_string());
+                let is_default = default_output.as_deref() == Some(name.as_str());
+                let marker = if is_default { " ← default, captured" } else { "" };
+                println!("  {}{}", name, marker);
             }
+        }
+    }
 
-            println!();
-            println!("PulseAudio/PipeWire sources (pactl):");
-            println!("─────────────────────────────────────");
-            let _ = std::process::Command::new("pactl")
-                .args(["list", "short", "sources"])
-                .status();
+    // CoreAudio only sees system output through a loopback driver
+    #[cfg(target_os = "macos")]
+    {
+        println!();
+        let loopback = modules::audio::loopback_device(&host).and_then(|d| d.name().ok());
+        match loopback {
+            Some(name) => println!("Captured by default (loopback): {}", name),
+            None => {
+                println!("No loopback device found, so the default input (usually the");
+                println!("microphone) is captured. Install one of these and route output");
+                println!("through it: {}", modules::audio::LOOPBACK_DEVICES.join(", "));
+            }
         }
     }
 
+    #[cfg(target_os = "linux")]
+    {
+        println!();
+        println!("PulseAudio/PipeWire sources (pactl):");
+        println!("─────────────────────────────────────");
+        let _ = std::process::Command::new("pactl")
+            .args(["list", "short", "sources"])
+            .status();
+    }
+
     Ok(())
 }
 
 #[cfg(not(feature = "audio"))]
-fn handle_audio(_command: AudioCommands) -> Result<()> {
+fn list_audio_devices() -> Result<()> {
     println!("Audio feature not enabled. Rebuild with: cargo build --features audio");
     Ok(())
 }
@@ -3,78 +3,709 @@ use anyhow::{Context, Result};
 #[cfg(feature = "audio")]
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
 use rustfft::{num_complex::Complex, FftPlanner};
-#[cfg(feature = "audio")]
-use std::sync::{Arc, Mutex};
-#[cfg(feature = "audio")]
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::config::AudioConfig;
+use std::sync::Arc;
+#[cfg(all(feature = "audio", target_os = "linux"))]
 use std::io::Read;
-#[cfg(feature = "audio")]
+#[cfg(all(feature = "audio", target_os = "linux"))]
 use std::process::{Command, Stdio};
 
+/// FFT sizes the `F` key steps through
+pub const FFT_SIZES: [usize; 5] = [512, 1024, 2048, 4096, 8192];
+
+/// Taper applied to each block of samples before the FFT; trades frequency
+/// resolution (narrow peaks) against leakage (how far a loud tone smears)
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WindowFunction {
+    #[default]
+    Hann,
+    Hamming,
+    /// Lowest leakage, widest peaks
+    BlackmanHarris,
+    /// No taper: sharpest peaks, most leakage
+    Rectangular,
+}
+
+impl WindowFunction {
+    /// Config names
+    pub const NAMES: [&'static str; 4] = ["hann", "hamming", "blackman-harris", "rectangular"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "hann" => Some(WindowFunction::Hann),
+            "hamming" => Some(WindowFunction::Hamming),
+            "blackman-harris" => Some(WindowFunction::BlackmanHarris),
+            "rectangular" => Some(WindowFunction::Rectangular),
+            _ => None,
+        }
+    }
+
+    /// Periodic window of `size` samples
+    fn coefficients(self, size: usize) -> Vec<f32> {
+        (0..size)
+            .map(|i| {
+                let x = 2.0 * std::f32::consts::PI * i as f32 / size as f32;
+                match self {
+                    WindowFunction::Hann => 0.5 - 0.5 * x.cos(),
+                    WindowFunction::Hamming => 0.54 - 0.46 * x.cos(),
+                    WindowFunction::BlackmanHarris => {
+                        0.35875 - 0.48829 * x.cos() + 0.14128 * (2.0 * x).cos() - 0.01168 * (3.0 * x).cos()
+                    }
+                    WindowFunction::Rectangular => 1.0,
+                }
+            })
+            .collect()
+    }
+}
+
+/// How many times longer the bass FFT is than `fft_size`
+const BASS_FFT_FACTOR: usize = 4;
+
+/// Below this the bass FFT's bins are used, blending into the regular FFT
+/// over the next half octave
+const BASS_CROSSOVER_HZ: f32 = 200.0;
+
+/// FFT size and window from `[audio]`, shared by every capture backend
+#[derive(Clone, Copy, Debug)]
+pub struct AnalysisOptions {
+    pub fft_size: usize,
+    pub window: WindowFunction,
+    /// A second, `BASS_FFT_FACTOR` times longer FFT for the bass
+    pub bass: bool,
+}
+
+impl AnalysisOptions {
+    pub fn from_config(config: &AudioConfig) -> anyhow::Result<Self> {
+        let window = WindowFunction::from_name(&config.window).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown audio.window '{}' (expected one of: {})",
+                config.window,
+                WindowFunction::NAMES.join(", ")
+            )
+        })?;
+        if config.fft_size < 64 {
+            anyhow::bail!("audio.fft_size must be at least 64 (got {})", config.fft_size);
+        }
+        Ok(Self {
+            fft_size: config.fft_size,
+            window,
+            bass: config.bass_fft,
+        })
+    }
+
+    /// Frames each analysis looks at: the bass FFT's when it's on
+    fn frames(self) -> usize {
+        if self.bass { self.fft_size * BASS_FFT_FACTOR } else { self.fft_size }
+    }
+
+    /// The next size in `FFT_SIZES`, wrapping around to the smallest
+    pub fn next_fft_size(self) -> Self {
+        let fft_size = FFT_SIZES
+            .iter()
+            .copied()
+            .find(|&size| size > self.fft_size)
+            .unwrap_or(FFT_SIZES[0]);
+        Self { fft_size, ..self }
+    }
+}
+
+/// Window coefficients and the factor that turns FFT magnitudes into levels.
+/// The factor evens out the windows' gain so switching keeps bars at the same height
+fn window_with_scale(function: WindowFunction, size: usize) -> (Vec<f32>, f32) {
+    let window = function.coefficients(size);
+    let gain: f32 = window.iter().sum();
+    (window, 0.5 / gain.max(f32::EPSILON))
+}
+
+/// Spectrum and samples of one channel
+#[derive(Clone)]
+pub struct Channel {
+    pub spectrum: Vec<f32>,
+    pub waveform: Vec<f32>,
+}
+
+impl Channel {
+    fn silent(fft_size: usize) -> Self {
+        Self {
+            spectrum: vec![0.0; fft_size / 2],
+            waveform: vec![0.0; fft_size],
+        }
+    }
+}
+
+/// Rate assumed until a device reports its own; parec is asked for this one
+pub const DEFAULT_SAMPLE_RATE: u32 = 48_000;
+
 #[derive(Clone)]
 pub struct AudioData {
+    /// Mono mix
     pub spectrum: Vec<f32>,
     pub waveform: Vec<f32>,
+    /// Left and right channels; mono sources repeat the mix in both
+    pub stereo: [Channel; 2],
+    /// Capture rate in Hz; the spectrum covers 0 to half of it
+    pub sample_rate: u32,
+}
+
+impl AudioData {
+    pub fn silent(fft_size: usize) -> Self {
+        let channel = Channel::silent(fft_size);
+        Self {
+            spectrum: channel.spectrum.clone(),
+            waveform: channel.waveform.clone(),
+            stereo: [channel.clone(), channel],
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        }
+    }
+
+    /// Start the waveforms at a rising zero crossing of the mix, like an
+    /// oscilloscope trigger, so periodic signals hold still from frame to frame.
+    /// Half the samples are kept; the other half is room to search
+    pub fn trigger(&mut self) {
+        let len = self.waveform.len() / 2;
+        let offset = trigger_offset(&self.waveform[..len]);
+        // Channels share the offset so they stay in phase with each other
+        let [left, right] = &mut self.stereo;
+        for waveform in [&mut self.waveform, &mut left.waveform, &mut right.waveform] {
+            if waveform.len() >= offset + len {
+                waveform.copy_within(offset..offset + len, 0);
+                waveform.truncate(len);
+            }
+        }
+    }
+}
+
+/// First rise through zero after the signal dipped clearly below it; the
+/// hysteresis keeps noise around zero from triggering. 0 when there is none
+fn trigger_offset(samples: &[f32]) -> usize {
+    let peak = samples.iter().fold(0.0f32, |acc, &s| acc.max(s.abs()));
+    let hysteresis = peak * 0.1;
+
+    let mut armed = false;
+    for (i, &sample) in samples.iter().enumerate() {
+        if sample < -hysteresis {
+            armed = true;
+        } else if armed && sample >= 0.0 {
+            return i;
+        }
+    }
+    0
 }
 
 /// Smoothed audio data with exponential decay for fluid animations
 pub struct SmoothedAudio {
-    spectrum: Vec<f32>,
-    waveform: Vec<f32>,
+    data: AudioData,
     attack: f32,  // How fast values rise (0-1, higher = faster)
     decay: f32,   // How fast values fall (0-1, higher = faster)
+    waveform: f32, // Share of each new waveform frame (0-1, 1 = no smoothing)
 }
 
 impl SmoothedAudio {
-    pub fn new(fft_size: usize, attack: f32, decay: f32) -> Self {
+    pub fn new(fft_size: usize, attack: f32, decay: f32, waveform: f32) -> Self {
         Self {
-            spectrum: vec![0.0; fft_size / 2],
-            waveform: vec![0.0; fft_size],
+            data: AudioData::silent(fft_size),
             attack,
             decay,
+            waveform,
         }
     }
 
     pub fn update(&mut self, data: &AudioData) -> AudioData {
-        // Smooth spectrum with asymmetric attack/decay
-        for (i, &target) in data.spectrum.iter().enumerate() {
-            if i < self.spectrum.len() {
-                let current = self.spectrum[i];
-                if target > current {
-                    self.spectrum[i] = current + (target - current) * self.attack;
-                } else {
-                    self.spectrum[i] = current + (target - current) * self.decay;
-                }
+        let (attack, decay) = (self.attack, self.decay);
+        self.data.sample_rate = data.sample_rate;
+        smooth_spectrum(&mut self.data.spectrum, &data.spectrum, attack, decay);
+        smooth_waveform(&mut self.data.waveform, &data.waveform, self.waveform);
+        for (current, target) in self.data.stereo.iter_mut().zip(&data.stereo) {
+            smooth_spectrum(&mut current.spectrum, &target.spectrum, attack, decay);
+            smooth_waveform(&mut current.waveform, &target.waveform, self.waveform);
+        }
+        self.data.clone()
+    }
+}
+
+/// Asymmetric attack/decay so bars jump up and settle slowly
+fn smooth_spectrum(current: &mut Vec<f32>, target: &[f32], attack: f32, decay: f32) {
+    // A new FFT size changes the bin count; start over from the new frame
+    if current.len() != target.len() {
+        current.clear();
+        current.extend_from_slice(target);
+        return;
+    }
+    for (value, &target) in current.iter_mut().zip(target) {
+        let rate = if target > *value { attack } else { decay };
+        *value += (target - *value) * rate;
+    }
+}
+
+/// Waveform blends in `weight` of each new frame, usually more than the
+/// spectrum (it needs to track audio closely)
+fn smooth_waveform(current: &mut Vec<f32>, target: &[f32], weight: f32) {
+    // Triggering shortens the frames; start over rather than blend mismatched lengths
+    if current.len() != target.len() {
+        current.clear();
+        current.extend_from_slice(target);
+        return;
+    }
+    for (value, &target) in current.iter_mut().zip(target) {
+        *value += (target - *value) * weight;
+    }
+}
+
+/// Recent spectra, oldest first, for the spectrogram
+pub struct SpectrumHistory {
+    frames: VecDeque<Vec<f32>>,
+    capacity: usize,
+}
+
+impl SpectrumHistory {
+    /// Keep the last `capacity` frames (one spectrogram column each)
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            frames: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn push(&mut self, spectrum: &[f32]) {
+        // Reuse the oldest frame's buffer once full
+        let mut frame = if self.frames.len() == self.capacity {
+            self.frames.pop_front().unwrap_or_default()
+        } else {
+            Vec::with_capacity(spectrum.len())
+        };
+        frame.clear();
+        frame.extend_from_slice(spectrum);
+        self.frames.push_back(frame);
+    }
+
+    /// The newest `count` frames (or all there are), oldest first
+    pub fn latest(&self, count: usize) -> impl Iterator<Item = &[f32]> {
+        let skip = self.frames.len().saturating_sub(count);
+        self.frames.iter().skip(skip).map(Vec::as_slice)
+    }
+}
+
+/// Auto gain never goes above 1 / this, so silence stays dark instead of
+/// amplifying the noise floor to full height
+const AGC_FLOOR: f32 = 1e-3;
+
+/// Automatic gain control: a peak follower over the spectrum bins that rises
+/// within `attack` seconds and falls back within `release`, scaling the
+/// spectra so the loudest recent bin sits at 1.0 for quiet and loud tracks alike.
+/// Bins are compared as they will be drawn, through the caller's weighting
+pub struct AutoGain {
+    attack: f32,
+    release: f32,
+    peak: f32,
+}
+
+impl AutoGain {
+    pub fn new(attack: f32, release: f32) -> Self {
+        Self {
+            attack,
+            release,
+            peak: AGC_FLOOR,
+        }
+    }
+
+    /// Follow this frame's loudest bin, each weighted by `weight(hz)`, and
+    /// scale every spectrum by the result; `dt` is the time since the last frame
+    pub fn apply(&mut self, data: &mut AudioData, dt: Duration, weight: impl Fn(f32) -> f32) {
+        let bins = data.spectrum.len().max(1);
+        let bin_hz = data.sample_rate as f32 / 2.0 / bins as f32;
+        let weights: Vec<f32> = (0..bins).map(|i| weight(i as f32 * bin_hz)).collect();
+
+        let [left, right] = &mut data.stereo;
+        let spectra = [&mut data.spectrum, &mut left.spectrum, &mut right.spectrum];
+        let loudest = spectra
+            .iter()
+            .flat_map(|spectrum| spectrum.iter().zip(&weights))
+            .fold(0.0f32, |acc, (&value, &w)| acc.max(value * w));
+        let time = if loudest > self.peak { self.attack } else { self.release };
+        let rate = if time > 0.0 {
+            1.0 - (-dt.as_secs_f32() / time).exp()
+        } else {
+            1.0
+        };
+        self.peak += (loudest - self.peak) * rate;
+
+        let gain = 1.0 / self.peak.max(AGC_FLOOR);
+        for spectrum in spectra {
+            spectrum.iter_mut().for_each(|value| *value *= gain);
+        }
+    }
+}
+
+/// How fast falling peaks pick up speed at gravity 1.0; a peak is down to
+/// about 2% of its height a second after it starts to fall
+const PEAK_ACCELERATION: f32 = 8.0;
+
+/// Peak-hold markers per FFT bin, like cava: each peak holds the highest recent
+/// value and falls with speed that grows under `gravity` until a bar catches it
+pub struct SpectrumPeaks {
+    gravity: f32,
+    /// Mono mix, then left and right
+    lines: [PeakLine; 3],
+}
+
+#[derive(Default)]
+struct PeakLine {
+    peaks: Vec<f32>,
+    /// Fraction of the peak lost per second
+    speed: Vec<f32>,
+}
+
+impl PeakLine {
+    fn update(&mut self, spectrum: &[f32], gravity: f32, dt: f32) {
+        if self.peaks.len() != spectrum.len() {
+            self.peaks = spectrum.to_vec();
+            self.speed = vec![0.0; spectrum.len()];
+            return;
+        }
+
+        for ((peak, speed), &value) in self.peaks.iter_mut().zip(&mut self.speed).zip(spectrum) {
+            if value >= *peak {
+                *peak = value;
+                *speed = 0.0;
+            } else {
+                *speed += gravity * PEAK_ACCELERATION * dt;
+                *peak = (*peak * (1.0 - *speed * dt).max(0.0)).max(value);
             }
         }
+    }
+}
+
+impl SpectrumPeaks {
+    pub fn new(gravity: f32) -> Self {
+        Self {
+            gravity,
+            lines: Default::default(),
+        }
+    }
+
+    /// Feed the displayed (smoothed) spectra; `dt` is the time since the last frame
+    pub fn update(&mut self, data: &AudioData, dt: Duration) {
+        let dt = dt.as_secs_f32();
+        let [mono, left, right] = &mut self.lines;
+        mono.update(&data.spectrum, self.gravity, dt);
+        left.update(&data.stereo[0].spectrum, self.gravity, dt);
+        right.update(&data.stereo[1].spectrum, self.gravity, dt);
+    }
+
+    pub fn mono(&self) -> &[f32] {
+        &self.lines[0].peaks
+    }
+
+    /// Peaks of the left (0) or right (1) channel
+    pub fn channel(&self, index: usize) -> &[f32] {
+        &self.lines[1 + index].peaks
+    }
+}
+
+/// Frames of spectral flux the onset threshold averages over (about a second)
+const FLUX_HISTORY: usize = 32;
+/// Shortest gap between beats, capping detection at 200 BPM
+const MIN_BEAT_GAP: Duration = Duration::from_millis(300);
+/// Beat intervals kept for the tempo estimate
+const BEAT_INTERVALS: usize = 16;
+/// Forget the tempo after this long without a beat
+const BEAT_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// Onset detector over the low end of the spectrum: a beat is a jump in
+/// spectral flux (summed rises in bin energy) well above its recent average.
+/// Exposes the beat as a decaying 0-1 pulse plus a tempo estimated from the
+/// gaps between beats
+pub struct BeatDetector {
+    previous: Vec<f32>,
+    flux_history: VecDeque<f32>,
+    level: f32,
+    last_beat: Option<Instant>,
+    /// Seconds between recent beats
+    intervals: VecDeque<f32>,
+}
+
+impl BeatDetector {
+    pub fn new() -> Self {
+        Self {
+            previous: Vec::new(),
+            flux_history: VecDeque::with_capacity(FLUX_HISTORY),
+            level: 0.0,
+            last_beat: None,
+            intervals: VecDeque::with_capacity(BEAT_INTERVALS),
+        }
+    }
+
+    /// Feed the latest unsmoothed spectrum; returns whether a beat starts now
+    pub fn update(&mut self, spectrum: &[f32]) -> bool {
+        // Kick and bass live in the lowest bins (roughly below 1kHz)
+        let bins = (spectrum.len() / 20).max(1).min(spectrum.len());
+        let band = &spectrum[..bins];
 
-        // Waveform uses faster response (it needs to track audio closely)
-        for (i, &target) in data.waveform.iter().enumerate() {
-            if i < self.waveform.len() {
-                self.waveform[i] = self.waveform[i] * 0.3 + target * 0.7;
+        let flux: f32 = band
+            .iter()
+            .zip(&self.previous)
+            .map(|(&now, &before)| (now - before).max(0.0))
+            .sum();
+        self.previous.clear();
+        self.previous.extend_from_slice(band);
+
+        let average = if self.flux_history.is_empty() {
+            f32::MAX
+        } else {
+            self.flux_history.iter().sum::<f32>() / self.flux_history.len() as f32
+        };
+        if self.flux_history.len() == FLUX_HISTORY {
+            self.flux_history.pop_front();
+        }
+        self.flux_history.push_back(flux);
+
+        let now = Instant::now();
+        let since_last = self.last_beat.map(|at| now - at);
+        if since_last.is_some_and(|gap| gap > BEAT_TIMEOUT) {
+            self.last_beat = None;
+            self.intervals.clear();
+        }
+
+        let onset = flux > average * 1.5
+            && flux > 1e-4
+            && since_last.is_none_or(|gap| gap >= MIN_BEAT_GAP);
+
+        if onset {
+            if let Some(gap) = since_last.filter(|gap| *gap <= BEAT_TIMEOUT) {
+                if self.intervals.len() == BEAT_INTERVALS {
+                    self.intervals.pop_front();
+                }
+                self.intervals.push_back(gap.as_secs_f32());
             }
+            self.last_beat = Some(now);
         }
 
+        self.level = if onset { 1.0 } else { self.level * 0.85 };
+        onset
+    }
+
+    /// Decaying pulse, 1.0 on a beat
+    pub fn level(&self) -> f32 {
+        self.level
+    }
+
+    /// Estimated tempo once a few beats in a row have been heard
+    pub fn bpm(&self) -> Option<f32> {
+        if self.intervals.len() < 4 {
+            return None;
+        }
+
+        // The median interval shrugs off missed and doubled beats
+        let mut intervals: Vec<f32> = self.intervals.iter().copied().collect();
+        intervals.sort_by(f32::total_cmp);
+        let mut bpm = 60.0 / intervals[intervals.len() / 2];
+
+        // Fold into the range most music is counted in
+        while bpm < 70.0 {
+            bpm *= 2.0;
+        }
+        while bpm > 180.0 {
+            bpm /= 2.0;
+        }
+        Some(bpm)
+    }
+}
+
+impl Default for BeatDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// One planned FFT size with its window and buffer
+struct Transform {
+    fft: Arc<dyn rustfft::Fft<f32>>,
+    window: Vec<f32>,
+    /// Magnitude to level, see `window_with_scale`
+    scale: f32,
+    buffer: Vec<Complex<f32>>,
+}
+
+impl Transform {
+    fn new(function: WindowFunction, size: usize) -> Self {
+        let (window, scale) = window_with_scale(function, size);
+        Self {
+            fft: FftPlanner::new().plan_fft_forward(size),
+            window,
+            scale,
+            buffer: vec![Complex::new(0.0f32, 0.0f32); size],
+        }
+    }
+
+    /// Levels of the lower half of the bins
+    fn levels(&mut self, samples: impl Iterator<Item = f32>) -> Vec<f32> {
+        // Apply window and prepare FFT input (no allocation)
+        for ((slot, sample), &w) in self.buffer.iter_mut().zip(samples).zip(&self.window) {
+            *slot = Complex::new(sample * w, 0.0);
+        }
+
+        self.fft.process(&mut self.buffer);
+
+        let scale = self.scale;
+        self.buffer[..self.buffer.len() / 2]
+            .iter()
+            .map(|c| (c.re * c.re + c.im * c.im).sqrt() * scale)
+            .collect()
+    }
+}
+
+/// Windowed FFT over the mix and both channels, planned once per capture
+struct Analyzer {
+    options: AnalysisOptions,
+    sample_rate: u32,
+    fft: Transform,
+    /// The long FFT when `options.bass` is on
+    bass: Option<Transform>,
+    /// The last `options.frames()` frames
+    frames: Vec<[f32; 2]>,
+}
+
+impl Analyzer {
+    fn new(options: AnalysisOptions, sample_rate: u32) -> Self {
+        Self {
+            options,
+            sample_rate,
+            fft: Transform::new(options.window, options.fft_size),
+            bass: options
+                .bass
+                .then(|| Transform::new(options.window, options.frames())),
+            frames: vec![[0.0; 2]; options.frames()],
+        }
+    }
+
+    fn spectrum(&mut self, channel: impl Fn(&[f32; 2]) -> f32) -> Vec<f32> {
+        let recent = &self.frames[self.frames.len() - self.options.fft_size..];
+        let spectrum = self.fft.levels(recent.iter().map(&channel));
+        let Some(bass) = self.bass.as_mut() else {
+            return spectrum;
+        };
+
+        // The merged spectrum has the long FFT's bins; above the crossover
+        // each regular bin fills the BASS_FFT_FACTOR bins it spans
+        let mut merged = bass.levels(self.frames.iter().map(&channel));
+        let bin_hz = self.sample_rate as f32 / 2.0 / merged.len() as f32;
+        for (i, level) in merged.iter_mut().enumerate() {
+            let blend = ((i as f32 * bin_hz / BASS_CROSSOVER_HZ).log2() * 2.0).clamp(0.0, 1.0);
+            let regular = spectrum[(i / BASS_FFT_FACTOR).min(spectrum.len() - 1)];
+            *level += (regular - *level) * blend;
+        }
+        merged
+    }
+
+    /// Analyze the frames last copied into `self.frames`
+    fn analyze(&mut self) -> AudioData {
+        // The waveform shows the same stretch of audio with or without the bass FFT
+        let recent = &self.frames[self.frames.len() - self.options.fft_size..];
+        let left: Vec<f32> = recent.iter().map(|f| f[0]).collect();
+        let right: Vec<f32> = recent.iter().map(|f| f[1]).collect();
+        let mix: Vec<f32> = recent.iter().map(|f| (f[0] + f[1]) * 0.5).collect();
+
         AudioData {
-            spectrum: self.spectrum.clone(),
-            waveform: self.waveform.clone(),
+            spectrum: self.spectrum(|f| (f[0] + f[1]) * 0.5),
+            waveform: mix,
+            stereo: [
+                Channel { spectrum: self.spectrum(|f| f[0]), waveform: left },
+                Channel { spectrum: self.spectrum(|f| f[1]), waveform: right },
+            ],
+            sample_rate: self.sample_rate,
         }
     }
 }
 
 #[cfg(feature = "audio")]
 pub struct AudioCapture {
-    _stream: cpal::Stream,
-    samples: Arc<Mutex<Vec<f32>>>,
-    fft_size: usize,
-    fft: std::sync::Arc<dyn rustfft::Fft<f32>>,
-    window: Vec<f32>,
-    // Pre-allocated buffers
-    waveform_buf: Vec<f32>,
-    fft_buffer: Vec<Complex<f32>>,
-    spectrum_buf: Vec<f32>,
+    stream: cpal::Stream,
+    /// Name of the captured device
+    device: String,
+    /// Frames as (left, right) from the stream callback; mono devices repeat the sample
+    frames: FrameReceiver,
+    analyzer: Analyzer,
+}
+
+/// Frames the capture thread hasn't handed over yet, at least this many
+/// (~340ms at 48kHz) so a slow render never makes the callback drop audio
+#[cfg(feature = "audio")]
+const MIN_RING_FRAMES: usize = 16384;
+
+/// Fixed-size history of (left, right) frames, overwriting the oldest
+#[cfg(feature = "audio")]
+struct RingBuffer {
+    data: Vec<[f32; 2]>,
+    write_pos: usize,
+}
+
+#[cfg(feature = "audio")]
+impl RingBuffer {
+    fn new(size: usize) -> Self {
+        Self {
+            data: vec![[0.0; 2]; size],
+            write_pos: 0,
+        }
+    }
+
+    fn push(&mut self, frame: [f32; 2]) {
+        self.data[self.write_pos] = frame;
+        self.write_pos = (self.write_pos + 1) % self.data.len();
+    }
+
+    fn copy_ordered_into(&self, dest: &mut [[f32; 2]]) {
+        let first_part = &self.data[self.write_pos..];
+        let second_part = &self.data[..self.write_pos];
+        dest[..first_part.len()].copy_from_slice(first_part);
+        dest[first_part.len()..].copy_from_slice(second_part);
+    }
+}
+
+/// Render side of the lock-free queue filled by the capture thread, keeping
+/// the latest `len` frames for analysis
+#[cfg(feature = "audio")]
+struct FrameReceiver {
+    consumer: rtrb::Consumer<[f32; 2]>,
+    history: RingBuffer,
 }
 
 #[cfg(feature = "audio")]
+impl FrameReceiver {
+    fn new(len: usize) -> (rtrb::Producer<[f32; 2]>, Self) {
+        let (producer, consumer) = rtrb::RingBuffer::new((len * 4).max(MIN_RING_FRAMES));
+        let receiver = Self {
+            consumer,
+            history: RingBuffer::new(len),
+        };
+        (producer, receiver)
+    }
+
+    /// Keep `len` frames from now on, holding on to the most recent ones
+    fn resize(&mut self, len: usize) {
+        let mut frames = vec![[0.0; 2]; self.history.data.len()];
+        self.history.copy_ordered_into(&mut frames);
+        self.history = RingBuffer::new(len);
+        let skip = frames.len().saturating_sub(len);
+        for &frame in &frames[skip..] {
+            self.history.push(frame);
+        }
+    }
+
+    /// Take everything queued so far and copy the latest frames, oldest first
+    fn read_into(&mut self, dest: &mut [[f32; 2]]) {
+        if let Ok(chunk) = self.consumer.read_chunk(self.consumer.slots()) {
+            for frame in chunk {
+                self.history.push(frame);
+            }
+        }
+        self.history.copy_ordered_into(dest);
+    }
+}
+
+#[cfg(all(feature = "audio", target_os = "linux"))]
 fn get_default_monitor_source() -> Option<String> {
     // Try to get the default sink's monitor source using pactl
     let output = std::process::Command::new("pactl")
@@ -91,361 +722,404 @@ fn get_default_monitor_source() -> Option<String> {
     None
 }
 
+/// Find a configured device by (partial) name
+#[cfg(feature = "audio")]
+fn find_named_device(
+    host: &cpal::Host,
+    device_name: &str,
+) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    let matches = |d: &cpal::Device| d.name().map(|n| n.contains(device_name)).unwrap_or(false);
+
+    if let Some(device) = host.input_devices()?.find(matches) {
+        let config = device.default_input_config()?;
+        return Ok((device, config));
+    }
+
+    // WASAPI can capture output devices in loopback mode, so accept those by name too
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(device) = host.output_devices()?.find(matches) {
+            let config = device.default_output_config()?;
+            return Ok((device, config));
+        }
+    }
+
+    anyhow::bail!("Device '{}' not found", device_name)
+}
+
+/// Auto-detect: try monitor source first, then any monitor, then default
+#[cfg(all(feature = "audio", target_os = "linux"))]
+fn default_capture_device(host: &cpal::Host) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    let monitor_name = get_default_monitor_source();
+
+    let device = if let Some(ref monitor) = monitor_name {
+        host.input_devices()?
+            .find(|d| d.name().map(|n| n.contains(monitor)).unwrap_or(false))
+    } else {
+        None
+    };
+
+    // If no default monitor found, try any device with "monitor" in the name
+    let device = device.or_else(|| {
+        host.input_devices().ok()?.find(|d| {
+            d.name()
+                .map(|n| n.to_lowercase().contains("monitor"))
+                .unwrap_or(false)
+        })
+    });
+
+    // Fall back to default input device
+    let device = device
+        .or_else(|| host.default_input_device())
+        .context("No audio input device available")?;
+    let config = device.default_input_config()?;
+    Ok((device, config))
+}
+
+/// Capture whatever the default output device is playing via WASAPI loopback
+#[cfg(all(feature = "audio", target_os = "windows"))]
+fn default_capture_device(host: &cpal::Host) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    // Opening an input stream on an output device puts WASAPI into loopback mode
+    let device = host
+        .default_output_device()
+        .context("No audio output device available")?;
+    let config = device.default_output_config()?;
+    Ok((device, config))
+}
+
+/// Virtual loopback drivers that expose system output as a CoreAudio input,
+/// in the order they're tried:
+/// 1. BlackHole - free and the usual recommendation ("BlackHole 2ch", "BlackHole 16ch")
+/// 2. Loopback - Rogue Amoeba's app; its devices are named by the user, but
+///    default to "Loopback Audio"
+/// 3. Soundflower - the older, unmaintained driver still found on some machines
+///
+/// Aggregate or multi-output devices built in Audio MIDI Setup don't show up as
+/// loopback inputs by themselves; set `audio.device` to the BlackHole input instead
+#[cfg(all(feature = "audio", target_os = "macos"))]
+pub const LOOPBACK_DEVICES: [&str; 3] = ["BlackHole", "Loopback", "Soundflower"];
+
+/// The input captured by default on macOS: the first loopback driver found
+/// in `LOOPBACK_DEVICES` order (not device enumeration order)
+#[cfg(all(feature = "audio", target_os = "macos"))]
+pub fn loopback_device(host: &cpal::Host) -> Option<cpal::Device> {
+    let inputs: Vec<cpal::Device> = host.input_devices().ok()?.collect();
+    LOOPBACK_DEVICES.iter().find_map(|driver| {
+        inputs
+            .iter()
+            .find(|d| d.name().map(|n| n.contains(driver)).unwrap_or(false))
+            .cloned()
+    })
+}
+
+/// CoreAudio has no built-in loopback, so prefer a virtual loopback driver
+#[cfg(all(feature = "audio", target_os = "macos"))]
+fn default_capture_device(host: &cpal::Host) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    // Fall back to the default input (usually the microphone)
+    let device = loopback_device(host)
+        .or_else(|| host.default_input_device())
+        .context("No audio input device available")?;
+    let config = device.default_input_config()?;
+    Ok((device, config))
+}
+
+#[cfg(all(
+    feature = "audio",
+    not(any(target_os = "linux", target_os = "windows", target_os = "macos"))
+))]
+fn default_capture_device(host: &cpal::Host) -> Result<(cpal::Device, cpal::SupportedStreamConfig)> {
+    let device = host
+        .default_input_device()
+        .context("No audio input device available")?;
+    let config = device.default_input_config()?;
+    Ok((device, config))
+}
+
 #[cfg(feature = "audio")]
 impl AudioCapture {
-    pub fn new(device_name: &str, fft_size: usize) -> Result<Self> {
+    pub fn new(device_name: &str, options: AnalysisOptions) -> Result<Self> {
         let host = cpal::default_host();
 
-        let device = if !device_name.is_empty() {
+        let (device, config) = if !device_name.is_empty() {
             // User specified a device
-            host.input_devices()?
-                .find(|d| d.name().map(|n| n.contains(device_name)).unwrap_or(false))
-                .context(format!("Device '{}' not found", device_name))?
+            find_named_device(&host, device_name)?
         } else {
-            // Auto-detect: try monitor source first, then any monitor, then default
-            let monitor_name = get_default_monitor_source();
-
-            let device = if let Some(ref monitor) = monitor_name {
-                host.input_devices()?
-                    .find(|d| d.name().map(|n| n.contains(monitor)).unwrap_or(false))
-            } else {
-                None
-            };
-
-            // If no default monitor found, try any device with "monitor" in the name
-            let device = device.or_else(|| {
-                host.input_devices().ok()?.find(|d| {
-                    d.name()
-                        .map(|n| n.to_lowercase().contains("monitor"))
-                        .unwrap_or(false)
-                })
-            });
-
-            // Fall back to default input device
-            device
-                .or_else(|| host.default_input_device())
-                .context("No audio input device available")?
+            default_capture_device(&host)?
         };
 
-        let config = device.default_input_config()?;
         let sample_format = config.sample_format();
         let config: cpal::StreamConfig = config.into();
+        let sample_rate = config.sample_rate.0;
 
-        let samples: Arc<Mutex<Vec<f32>>> = Arc::new(Mutex::new(vec![0.0; fft_size]));
-        let samples_clone = samples.clone();
-
-        let err_fn = |err| eprintln!("Audio stream error: {}", err);
+        let (producer, frames) = FrameReceiver::new(options.frames());
 
         let stream = match sample_format {
-            cpal::SampleFormat::F32 => device.build_input_stream(
-                &config,
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    let mut buffer = samples_clone.lock().unwrap();
-                    for &sample in data {
-                        buffer.push(sample);
-                        if buffer.len() > fft_size {
-                            buffer.remove(0);
-                        }
-                    }
-                },
-                err_fn,
-                None,
-            )?,
-            cpal::SampleFormat::I16 => device.build_input_stream(
-                &config,
-                move |data: &[i16], _: &cpal::InputCallbackInfo| {
-                    let mut buffer = samples_clone.lock().unwrap();
-                    for &sample in data {
-                        let f = sample as f32 / i16::MAX as f32;
-                        buffer.push(f);
-                        if buffer.len() > fft_size {
-                            buffer.remove(0);
-                        }
-                    }
-                },
-                err_fn,
-                None,
-            )?,
-            cpal::SampleFormat::U16 => device.build_input_stream(
-                &config,
-                move |data: &[u16], _: &cpal::InputCallbackInfo| {
-                    let mut buffer = samples_clone.lock().unwrap();
-                    for &sample in data {
-                        let f = (sample as f32 / u16::MAX as f32) * 2.0 - 1.0;
-                        buffer.push(f);
-                        if buffer.len() > fft_size {
-                            buffer.remove(0);
-                        }
-                    }
-                },
-                err_fn,
-                None,
-            )?,
+            cpal::SampleFormat::F32 => build_stream::<f32>(&device, &config, producer)?,
+            cpal::SampleFormat::I16 => build_stream::<i16>(&device, &config, producer)?,
+            cpal::SampleFormat::U16 => build_stream::<u16>(&device, &config, producer)?,
             _ => anyhow::bail!("Unsupported sample format"),
         };
 
         stream.play()?;
 
-        // Pre-compute FFT and window
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(fft_size);
-        let window: Vec<f32> = (0..fft_size)
-            .map(|i| 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / fft_size as f32).cos()))
-            .collect();
-
-        // Pre-allocate buffers
-        let waveform_buf = vec![0.0f32; fft_size];
-        let fft_buffer = vec![Complex::new(0.0f32, 0.0f32); fft_size];
-        let spectrum_buf = vec![0.0f32; fft_size / 2];
-
         Ok(Self {
-            _stream: stream,
-            samples,
-            fft_size,
-            fft,
-            window,
-            waveform_buf,
-            fft_buffer,
-            spectrum_buf,
+            stream,
+            device: device.name().unwrap_or_default(),
+            frames,
+            analyzer: Analyzer::new(options, sample_rate),
         })
     }
 
-    pub fn get_data(&mut self) -> AudioData {
-        // Copy samples with minimal lock time
-        {
-            let samples = self.samples.lock().unwrap();
-            self.waveform_buf.copy_from_slice(&samples);
-        }
-
-        // Apply window and prepare FFT input (no allocation)
-        for i in 0..self.fft_size {
-            self.fft_buffer[i] = Complex::new(self.waveform_buf[i] * self.window[i], 0.0);
-        }
+    /// Replan the FFT without restarting the stream
+    fn set_analysis(&mut self, options: AnalysisOptions) {
+        self.frames.resize(options.frames());
+        self.analyzer = Analyzer::new(options, self.analyzer.sample_rate);
+    }
 
-        self.fft.process(&mut self.fft_buffer);
+    pub fn get_data(&mut self) -> AudioData {
+        self.frames.read_into(&mut self.analyzer.frames);
+        self.analyzer.analyze()
+    }
+}
 
-        // Compute spectrum magnitudes (no allocation)
-        let scale = 1.0 / self.fft_size as f32;
-        for i in 0..self.fft_size / 2 {
-            let c = &self.fft_buffer[i];
-            self.spectrum_buf[i] = (c.re * c.re + c.im * c.im).sqrt() * scale;
-        }
+/// Input stream that queues frames for the render thread, converting any sample
+/// format to f32 and keeping the first two channels. The callback never locks
+/// or allocates; frames are dropped only if the queue is full
+#[cfg(feature = "audio")]
+fn build_stream<T>(
+    device: &cpal::Device,
+    config: &cpal::StreamConfig,
+    mut producer: rtrb::Producer<[f32; 2]>,
+) -> Result<cpal::Stream>
+where
+    T: cpal::SizedSample,
+    f32: cpal::FromSample<T>,
+{
+    let channels = (config.channels as usize).max(1);
+    let err_fn = |err| eprintln!("Audio stream error: {}", err);
 
-        AudioData {
-            spectrum: self.spectrum_buf.clone(),
-            waveform: self.waveform_buf.clone(),
-        }
-    }
+    let stream = device.build_input_stream(
+        config,
+        move |data: &[T], _: &cpal::InputCallbackInfo| {
+            for frame in data.chunks_exact(channels) {
+                let left = frame[0].to_sample::<f32>();
+                let right = frame.get(1).map_or(left, |s| s.to_sample::<f32>());
+                let _ = producer.push([left, right]);
+            }
+        },
+        err_fn,
+        None,
+    )?;
+    Ok(stream)
 }
 
 // Mock audio for when cpal is not available or no device found
 pub struct MockAudioCapture {
     phase: f32,
-    fft_size: usize,
+    analyzer: Analyzer,
 }
 
 impl MockAudioCapture {
-    pub fn new(fft_size: usize) -> Self {
-        Self { phase: 0.0, fft_size }
+    pub fn new(options: AnalysisOptions) -> Self {
+        Self {
+            phase: 0.0,
+            analyzer: Analyzer::new(options, DEFAULT_SAMPLE_RATE),
+        }
     }
 
     pub fn get_data(&mut self) -> AudioData {
         self.phase += 0.1;
+        let phase = self.phase;
 
-        // Generate mock waveform (sine wave with harmonics)
-        let waveform: Vec<f32> = (0..self.fft_size)
-            .map(|i| {
-                let t = i as f32 / self.fft_size as f32;
-                (self.phase + t * 10.0).sin() * 0.5
-                    + (self.phase * 2.3 + t * 25.0).sin() * 0.25
-                    + (self.phase * 0.7 + t * 5.0).sin() * 0.15
-            })
-            .collect();
+        // A decaying low thump every 15 frames (120 BPM at the default 30 fps)
+        let kick = (-(phase % 1.5) * 4.0).exp() * 0.4;
 
-        // Compute real FFT on the mock waveform
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(self.fft_size);
+        // Generate mock waveforms (sine waves with harmonics), the right channel
+        // slightly out of phase and with a louder high harmonic
+        let sample = |t: f32, offset: f32, high: f32| {
+            (phase + offset + t * 10.0).sin() * 0.5
+                + (phase * 2.3 + t * 25.0).sin() * high
+                + (phase * 0.7 + t * 5.0).sin() * 0.15
+                + (t * 40.0).sin() * kick
+        };
+        // Time runs over the last fft_size frames, so the bass FFT's extra
+        // history doesn't change the tones
+        let size = self.analyzer.options.fft_size;
+        let start = self.analyzer.frames.len() - size;
+        for (i, frame) in self.analyzer.frames.iter_mut().enumerate() {
+            let t = (i as f32 - start as f32) / size as f32;
+            *frame = [sample(t, 0.0, 0.2), sample(t, 0.6, 0.3)];
+        }
 
-        let mut buffer: Vec<Complex<f32>> = waveform
-            .iter()
-            .map(|&s| Complex::new(s, 0.0))
-            .collect();
+        self.analyzer.analyze()
+    }
+}
 
-        // Apply Hann window
-        for (i, sample) in buffer.iter_mut().enumerate() {
-            let window = 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / self.fft_size as f32).cos());
-            sample.re *= window;
-        }
+/// Steps of the dashboard's per-frame audio path, as timed by [`time_stages`]
+pub const STAGES: [&str; 5] = ["analysis", "beat", "gain", "smoothing", "peaks"];
 
-        fft.process(&mut buffer);
+/// Average time each of [`STAGES`] takes per frame on mock input at
+/// `options`, after `warmup` untimed frames through the same instances so
+/// plans and buffers are in place. For the cargo bench
+pub fn time_stages(config: &AudioConfig, options: AnalysisOptions, warmup: u32, frames: u32) -> [Duration; 5] {
+    let smoothing = &config.smoothing;
+    let dt = Duration::from_secs(1) / config.fps.max(1);
+    let frames = frames.max(1);
 
-        let spectrum: Vec<f32> = buffer[..self.fft_size / 2]
-            .iter()
-            .map(|c| (c.re * c.re + c.im * c.im).sqrt() / self.fft_size as f32)
-            .collect();
+    let mut capture = MockAudioCapture::new(options);
+    let mut beat = BeatDetector::new();
+    let mut gain = AutoGain::new(config.agc.attack, config.agc.release);
+    let mut smoother = SmoothedAudio::new(options.fft_size, smoothing.attack, smoothing.decay, smoothing.waveform);
+    let mut peaks = SpectrumPeaks::new(config.spectrum.gravity);
 
-        AudioData { spectrum, waveform }
+    let mut totals = [Duration::ZERO; 5];
+    for frame in 0..warmup + frames {
+        let mut lap = Instant::now();
+        let mut times = [Duration::ZERO; 5];
+        let mut split = |stage: usize| {
+            times[stage] = lap.elapsed();
+            lap = Instant::now();
+        };
+
+        let mut data = capture.get_data();
+        split(0);
+        beat.update(&data.spectrum);
+        split(1);
+        gain.apply(&mut data, dt, |_| 1.0);
+        split(2);
+        let data = smoother.update(&data);
+        split(3);
+        peaks.update(&data, dt);
+        split(4);
+        std::hint::black_box(data);
+
+        if frame >= warmup {
+            for (total, time) in totals.iter_mut().zip(times) {
+                *total += time;
+            }
+        }
     }
+    totals.map(|total| total / frames)
 }
 
 // PulseAudio capture using parec - works with monitor sources
-#[cfg(feature = "audio")]
+#[cfg(all(feature = "audio", target_os = "linux"))]
 pub struct PulseCapture {
-    buffer: Arc<Mutex<RingBuffer>>,
-    fft_size: usize,
-    fft: std::sync::Arc<dyn rustfft::Fft<f32>>,
-    window: Vec<f32>,
-    // Pre-allocated buffers to avoid per-frame allocations
-    waveform_buf: Vec<f32>,
-    fft_buffer: Vec<Complex<f32>>,
-    spectrum_buf: Vec<f32>,
+    frames: FrameReceiver,
+    analyzer: Analyzer,
+    /// Source being recorded, e.g. "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+    source: String,
+    /// parec itself; killed on drop, which ends the reader thread
+    child: std::process::Child,
     _handle: std::thread::JoinHandle<()>,
 }
 
-// Lock-free-ish ring buffer for audio samples
-#[cfg(feature = "audio")]
-struct RingBuffer {
-    data: Vec<f32>,
-    write_pos: usize,
-}
-
-#[cfg(feature = "audio")]
-impl RingBuffer {
-    fn new(size: usize) -> Self {
-        Self {
-            data: vec![0.0; size],
-            write_pos: 0,
-        }
-    }
-
-    fn push(&mut self, sample: f32) {
-        self.data[self.write_pos] = sample;
-        self.write_pos = (self.write_pos + 1) % self.data.len();
-    }
-
-    fn copy_ordered_into(&self, dest: &mut [f32]) {
-        let first_part = &self.data[self.write_pos..];
-        let second_part = &self.data[..self.write_pos];
-        dest[..first_part.len()].copy_from_slice(first_part);
-        dest[first_part.len()..].copy_from_slice(second_part);
-    }
-}
-
-#[cfg(feature = "audio")]
+#[cfg(all(feature = "audio", target_os = "linux"))]
 impl PulseCapture {
-    pub fn new(fft_size: usize) -> Result<Self> {
-        // Get default monitor source
-        let output = Command::new("pactl")
-            .args(["get-default-sink"])
-            .output()
-            .context("pactl not found")?;
-
-        if !output.status.success() {
-            anyhow::bail!("Failed to get default sink");
-        }
+    /// Record `source`, or the default sink's monitor when None
+    pub fn new(source: Option<&str>, options: AnalysisOptions) -> Result<Self> {
+        let source = match source {
+            Some(source) => source.to_string(),
+            None => get_default_monitor_source().context("Failed to get default sink")?,
+        };
 
-        let sink = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        let monitor = format!("{}.monitor", sink);
+        let (mut producer, frames) = FrameReceiver::new(options.frames());
 
-        let buffer = Arc::new(Mutex::new(RingBuffer::new(fft_size)));
-        let buffer_clone = buffer.clone();
+        let mut child = Command::new("parec")
+            .args([
+                "--device", &source,
+                "--format=float32le",
+                "--channels=2",
+                &format!("--rate={}", DEFAULT_SAMPLE_RATE),
+                "--latency-msec=10",
+            ])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .context("parec not found")?;
+        let mut stdout = child.stdout.take().context("parec has no stdout")?;
 
-        // Spawn parec in a thread
+        // Read parec's output in a thread
         let handle = std::thread::spawn(move || {
-            let mut child = match Command::new("parec")
-                .args([
-                    "--device", &monitor,
-                    "--format=float32le",
-                    "--channels=1",
-                    "--rate=48000",
-                    "--latency-msec=10",
-                ])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::null())
-                .spawn()
-            {
-                Ok(c) => c,
-                Err(_) => return,
-            };
-
-            let mut stdout = match child.stdout.take() {
-                Some(s) => s,
-                None => return,
-            };
-
-            // Small buffer for low latency (64 samples = ~1.3ms at 48kHz)
+            // Small buffer for low latency (32 frames = ~0.7ms at 48kHz); read_exact
+            // keeps reads aligned to whole 8-byte frames
             let mut buf = [0u8; 256];
-            loop {
-                match stdout.read(&mut buf) {
-                    Ok(0) => break,
-                    Ok(n) => {
-                        // Use try_lock to avoid blocking if main thread is reading
-                        if let Ok(mut ring) = buffer_clone.try_lock() {
-                            for chunk in buf[..n].chunks_exact(4) {
-                                let sample = f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
-                                ring.push(sample);
-                            }
-                        }
-                        // If lock failed, just drop this batch - smoother than blocking
-                    }
-                    Err(_) => break,
+            while stdout.read_exact(&mut buf).is_ok() {
+                for frame in buf.chunks_exact(8) {
+                    let sample = |i: usize| {
+                        f32::from_le_bytes([frame[i], frame[i + 1], frame[i + 2], frame[i + 3]])
+                    };
+                    let _ = producer.push([sample(0), sample(4)]);
                 }
             }
         });
 
-        // Pre-compute FFT and window function
-        let mut planner = FftPlanner::new();
-        let fft = planner.plan_fft_forward(fft_size);
-        let window: Vec<f32> = (0..fft_size)
-            .map(|i| 0.5 * (1.0 - (2.0 * std::f32::consts::PI * i as f32 / fft_size as f32).cos()))
-            .collect();
-
-        // Pre-allocate buffers
-        let waveform_buf = vec![0.0f32; fft_size];
-        let fft_buffer = vec![Complex::new(0.0f32, 0.0f32); fft_size];
-        let spectrum_buf = vec![0.0f32; fft_size / 2];
-
         Ok(Self {
-            buffer,
-            fft_size,
-            fft,
-            window,
-            waveform_buf,
-            fft_buffer,
-            spectrum_buf,
+            frames,
+            analyzer: Analyzer::new(options, DEFAULT_SAMPLE_RATE),
+            source,
+            child,
             _handle: handle,
         })
     }
 
     pub fn get_data(&mut self) -> AudioData {
-        // Try to copy from ring buffer - skip if locked (don't block render)
-        if let Ok(ring) = self.buffer.try_lock() {
-            ring.copy_ordered_into(&mut self.waveform_buf);
-        }
+        self.frames.read_into(&mut self.analyzer.frames);
+        self.analyzer.analyze()
+    }
 
-        // Apply window and prepare FFT input (no allocation)
-        for i in 0..self.fft_size {
-            self.fft_buffer[i] = Complex::new(self.waveform_buf[i] * self.window[i], 0.0);
-        }
+    /// Replan the FFT; parec keeps running
+    fn set_analysis(&mut self, options: AnalysisOptions) {
+        self.frames.resize(options.frames());
+        self.analyzer = Analyzer::new(options, DEFAULT_SAMPLE_RATE);
+    }
 
-        self.fft.process(&mut self.fft_buffer);
+    /// Kill parec, which ends the reader thread
+    fn stop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
 
-        // Compute spectrum magnitudes (no allocation)
-        let scale = 1.0 / self.fft_size as f32;
-        for i in 0..self.fft_size / 2 {
-            let c = &self.fft_buffer[i];
-            self.spectrum_buf[i] = (c.re * c.re + c.im * c.im).sqrt() * scale;
-        }
+#[cfg(all(feature = "audio", target_os = "linux"))]
+impl Drop for PulseCapture {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
 
-        AudioData {
-            spectrum: self.spectrum_buf.clone(),
-            waveform: self.waveform_buf.clone(),
-        }
+/// Names of the PulseAudio/PipeWire sources (inputs and sink monitors)
+#[cfg(all(feature = "audio", target_os = "linux"))]
+fn pulse_sources() -> Result<Vec<String>> {
+    let output = Command::new("pactl")
+        .args(["list", "short", "sources"])
+        .output()
+        .context("pactl not found")?;
+    if !output.status.success() {
+        anyhow::bail!("pactl couldn't list sources");
     }
+    // "index<TAB>name<TAB>driver<TAB>format<TAB>state"
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .map(str::to_string)
+        .collect())
+}
+
+/// Devices cpal can capture from; on Windows that includes outputs (loopback)
+#[cfg(feature = "audio")]
+fn cpal_devices() -> Result<Vec<String>> {
+    use cpal::traits::{DeviceTrait, HostTrait};
+
+    let host = cpal::default_host();
+    let devices = host.input_devices()?;
+    #[cfg(target_os = "windows")]
+    let devices = devices.chain(host.output_devices()?);
+    Ok(devices.filter_map(|d| d.name().ok()).collect())
 }
 
 pub enum AudioSource {
-    #[cfg(feature = "audio")]
+    #[cfg(all(feature = "audio", target_os = "linux"))]
     Pulse(PulseCapture),
     #[cfg(feature = "audio")]
     Cpal(AudioCapture),
@@ -454,32 +1128,138 @@ pub enum AudioSource {
 
 impl AudioSource {
     #[cfg(feature = "audio")]
-    pub fn new(device_name: &str, fft_size: usize) -> Self {
-        // Try PulseAudio first (works with monitor sources)
-        if device_name.is_empty() {
-            if let Ok(capture) = PulseCapture::new(fft_size) {
-                return AudioSource::Pulse(capture);
-            }
+    pub fn new(device_name: &str, options: AnalysisOptions) -> Self {
+        // Try PulseAudio first on Linux (works with monitor sources)
+        #[cfg(target_os = "linux")]
+        if device_name.is_empty()
+            && let Ok(capture) = PulseCapture::new(None, options)
+        {
+            return AudioSource::Pulse(capture);
         }
 
-        // Fall back to cpal for explicit device names
-        match AudioCapture::new(device_name, fft_size) {
+        // Fall back to cpal for explicit device names, and on other platforms
+        // where it handles WASAPI loopback and CoreAudio devices
+        match AudioCapture::new(device_name, options) {
             Ok(capture) => AudioSource::Cpal(capture),
             Err(e) => {
                 eprintln!("Audio capture failed: {}. Using mock audio.", e);
-                AudioSource::Mock(MockAudioCapture::new(fft_size))
+                AudioSource::Mock(MockAudioCapture::new(options))
             }
         }
     }
 
     #[cfg(not(feature = "audio"))]
-    pub fn new(_device_name: &str, fft_size: usize) -> Self {
-        AudioSource::Mock(MockAudioCapture::new(fft_size))
+    pub fn new(_device_name: &str, options: AnalysisOptions) -> Self {
+        AudioSource::Mock(MockAudioCapture::new(options))
     }
 
-    pub fn get_data(&mut self) -> AudioData {
+    /// Backend name shown in the device picker
+    pub fn backend(&self) -> &'static str {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(_) => "pulse",
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(_) => "cpal",
+            AudioSource::Mock(_) => "mock",
+        }
+    }
+
+    /// The device being captured, None for the mock visualizer
+    pub fn device_name(&self) -> Option<&str> {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => Some(&capture.source),
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => Some(&capture.device),
+            AudioSource::Mock(_) => None,
+        }
+    }
+
+    /// Devices the active backend could capture from instead
+    pub fn devices(&self) -> anyhow::Result<Vec<String>> {
         match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(_) => pulse_sources(),
             #[cfg(feature = "audio")]
+            AudioSource::Cpal(_) => cpal_devices(),
+            AudioSource::Mock(_) => {
+                anyhow::bail!("No audio capture running")
+            }
+        }
+    }
+
+    /// Tear down the current stream and capture `name` with the same backend.
+    /// On failure the current capture keeps running
+    pub fn switch_device(&mut self, name: &str) -> anyhow::Result<()> {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => {
+                *capture = PulseCapture::new(Some(name), capture.analyzer.options)?;
+                Ok(())
+            }
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => {
+                *capture = AudioCapture::new(name, capture.analyzer.options)?;
+                Ok(())
+            }
+            AudioSource::Mock(_) => {
+                anyhow::bail!("Can't capture {}: no audio capture running", name)
+            }
+        }
+    }
+
+    pub fn analysis(&self) -> AnalysisOptions {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => capture.analyzer.options,
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => capture.analyzer.options,
+            AudioSource::Mock(mock) => mock.analyzer.options,
+        }
+    }
+
+    /// Change the FFT size or window, rebuilding the plan and buffers while
+    /// the capture keeps running
+    pub fn set_analysis(&mut self, options: AnalysisOptions) {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => capture.set_analysis(options),
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => capture.set_analysis(options),
+            AudioSource::Mock(mock) => mock.analyzer = Analyzer::new(options, DEFAULT_SAMPLE_RATE),
+        }
+    }
+
+    /// Stop capturing until `resume`; `get_data` has nothing new meanwhile
+    pub fn pause(&mut self) {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => capture.stop(),
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => {
+                let _ = capture.stream.pause();
+            }
+            AudioSource::Mock(_) => {}
+        }
+    }
+
+    /// Capture the same device again after `pause`
+    pub fn resume(&mut self) -> anyhow::Result<()> {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
+            AudioSource::Pulse(capture) => {
+                *capture = PulseCapture::new(Some(&capture.source), capture.analyzer.options)?;
+                Ok(())
+            }
+            #[cfg(feature = "audio")]
+            AudioSource::Cpal(capture) => capture.stream.play().context("Couldn't restart the capture stream"),
+            AudioSource::Mock(_) => Ok(()),
+        }
+    }
+
+    pub fn get_data(&mut self) -> AudioData {
+        match self {
+            #[cfg(all(feature = "audio", target_os = "linux"))]
             AudioSource::Pulse(capture) => capture.get_data(),
             #[cfg(feature = "audio")]
             AudioSource::Cpal(capture) => capture.get_data(),
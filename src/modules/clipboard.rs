@@ -0,0 +1,55 @@
+//! Copy text to the clipboard: the system clipboard (feature `clipboard`) on a
+//! local desktop, otherwise the OSC 52 escape so the terminal emulator does it,
+//! which also reaches the local machine over SSH.
+
+use anyhow::Result;
+use base64::Engine;
+use std::io::Write;
+
+/// Copy `text`, returning where it went
+pub fn copy(text: &str) -> Result<&'static str> {
+    #[cfg(feature = "clipboard")]
+    if !over_ssh() && system::copy(text) {
+        return Ok("clipboard");
+    }
+
+    osc52(text)?;
+    Ok("terminal")
+}
+
+/// Ask the terminal to set the clipboard (tmux needs `set-clipboard on`)
+fn osc52(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let sequence = format!("\x1b]52;c;{}\x07", encoded);
+
+    let mut stdout = std::io::stdout();
+    stdout.write_all(sequence.as_bytes())?;
+    stdout.flush()?;
+    Ok(())
+}
+
+#[cfg(feature = "clipboard")]
+fn over_ssh() -> bool {
+    std::env::var_os("SSH_CONNECTION").is_some() || std::env::var_os("SSH_TTY").is_some()
+}
+
+#[cfg(feature = "clipboard")]
+mod system {
+    use std::sync::Mutex;
+
+    /// On X11 and Wayland the contents live only as long as the owning
+    /// `Clipboard`, so one is kept for the life of the process
+    static CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
+
+    pub fn copy(text: &str) -> bool {
+        let Ok(mut guard) = CLIPBOARD.lock() else {
+            return false;
+        };
+        if guard.is_none() {
+            *guard = arboard::Clipboard::new().ok();
+        }
+        guard
+            .as_mut()
+            .is_some_and(|clipboard| clipboard.set_text(text).is_ok())
+    }
+}
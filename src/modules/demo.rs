@@ -0,0 +1,511 @@
+//! Canned data for `phosphor demo`: no network, auth or audio device required.
+
+use anyhow::Result;
+use image::{DynamicImage, Rgb, RgbImage};
+use std::path::PathBuf;
+use std::sync::{Mutex, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::modules::git::{Activity, ChangedFile, CommitInfo, RepoDetails, RepoStatus};
+use crate::modules::github::Notification;
+use crate::modules::lyrics::SyncedLyrics;
+use crate::modules::player::Player;
+use crate::modules::spotify::{
+    DeviceInfo, HistoryItem, PlaybackContext, QueueItem, Radio, SearchItem, SearchKind, TrackInfo,
+};
+
+const DEMO_LYRICS: &str = "\
+[00:04.00] Warm up the tubes, let the amber glow
+[00:09.50] Scanlines rolling soft and slow
+[00:15.00] Every pixel burning bright
+[00:20.50] Phosphor trails across the night
+[00:26.00] Hold the beam, don't let it fade
+[00:31.50] Echoes of the light we made
+[00:37.00] Oh, the glow, the glow
+[00:42.50] Won't you stay until the screen goes low
+[00:48.00] Oh, the glow, the glow
+[00:53.50] Amber ghosts on a midnight show
+[01:00.00] Tune the dial to a distant hum
+[01:05.50] Static sings till the morning comes
+[01:11.00] Burned-in memories, flickering slow
+[01:16.50] Nothing fades like the afterglow
+[01:22.00] Oh, the glow, the glow
+[01:27.50] Won't you stay until the screen goes low
+[01:33.00] Oh, the glow, the glow
+[01:38.50] Amber ghosts on a midnight show
+[01:46.00] (instrumental)
+[02:20.00] Power down, the picture shrinks
+[02:25.50] To a single dot that slowly sinks
+[02:31.00] But the warmth is left behind
+[02:36.50] Phosphor lingers in the mind
+";
+
+/// Length of the demo track in milliseconds
+const TRACK_DURATION_MS: u64 = 165_000;
+
+pub fn track() -> TrackInfo {
+    TrackInfo {
+        name: "Afterglow".to_string(),
+        artist: "The Scanlines".to_string(),
+        album: "Warm Tubes".to_string(),
+        duration: TRACK_DURATION_MS,
+        progress: Some(0),
+        is_playing: true,
+        album_art_url: None,
+        is_episode: false,
+        // Not a Spotify URI, so there's no link to copy or open
+        uri: Some("demo:track:afterglow".to_string()),
+        saved: Some(false),
+        context: Some(PlaybackContext {
+            kind: "playlist",
+            name: "Late Night Static".to_string(),
+            uri: "demo:playlist:late-night-static".to_string(),
+        }),
+        device: Some("Living Room".to_string()),
+    }
+}
+
+/// Up next in the demo queue
+pub fn queue() -> Vec<QueueItem> {
+    [
+        ("Scanline Serenade", "The Scanlines", 201_000),
+        ("Degauss", "Cathode Kids", 187_000),
+        ("Vertical Hold", "The Scanlines", 242_000),
+        ("Green Screen Dreams", "Monochrome", 174_000),
+        ("Flyback", "Cathode Kids", 215_000),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (name, artist, duration))| QueueItem {
+        name: name.to_string(),
+        artist: artist.to_string(),
+        duration,
+        uri: format!("demo:track:{}", i),
+    })
+    .collect()
+}
+
+/// Tracks "played" earlier, spaced back from now
+pub fn history() -> Vec<HistoryItem> {
+    let now = SystemTime::now();
+    [
+        ("Degauss", "Cathode Kids", 4),
+        ("Warm Tubes", "The Scanlines", 9),
+        ("Interlace", "Monochrome", 95),
+        ("Test Pattern", "The Scanlines", 26 * 60),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (name, artist, minutes_ago))| HistoryItem {
+        name: name.to_string(),
+        artist: artist.to_string(),
+        uri: format!("demo:history:{}", i),
+        played_at: now - Duration::from_secs(minutes_ago * 60),
+    })
+    .collect()
+}
+
+/// Canned catalog matched against the query, for the search overlay
+pub fn search(query: &str) -> Vec<SearchItem> {
+    let query = query.to_lowercase();
+    let mut catalog: Vec<SearchItem> = queue()
+        .into_iter()
+        .map(|item| SearchItem {
+            kind: SearchKind::Track,
+            name: item.name,
+            detail: item.artist,
+            uri: item.uri,
+        })
+        .collect();
+    catalog.push(SearchItem {
+        kind: SearchKind::Album,
+        name: "Warm Tubes".to_string(),
+        detail: "The Scanlines".to_string(),
+        uri: "demo:album:0".to_string(),
+    });
+    catalog.push(SearchItem {
+        kind: SearchKind::Playlist,
+        name: "Amber Nights".to_string(),
+        detail: "phosphor".to_string(),
+        uri: "demo:playlist:0".to_string(),
+    });
+
+    catalog
+        .into_iter()
+        .filter(|item| {
+            item.name.to_lowercase().contains(&query) || item.detail.to_lowercase().contains(&query)
+        })
+        .collect()
+}
+
+/// Speakers to hand playback between in the device popup
+pub fn devices() -> Vec<DeviceInfo> {
+    [
+        ("Workstation", "Computer", true, 62),
+        ("Pocket", "Smartphone", false, 100),
+        ("Living Room", "Speaker", false, 35),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(i, (name, kind, is_active, volume))| DeviceInfo {
+        id: format!("demo-device-{}", i),
+        name: name.to_string(),
+        kind: kind.to_string(),
+        is_active,
+        volume: Some(volume),
+    })
+    .collect()
+}
+
+/// Plays the demo track on a loop, with a queue, library and devices that
+/// answer like Spotify's would
+pub struct DemoPlayer {
+    state: Mutex<DemoState>,
+}
+
+struct DemoState {
+    track: TrackInfo,
+    queue: Vec<QueueItem>,
+    devices: Vec<DeviceInfo>,
+    /// When the playhead was last moved on
+    last_tick: Instant,
+}
+
+impl DemoPlayer {
+    pub fn new() -> Self {
+        Self {
+            state: Mutex::new(DemoState {
+                track: track(),
+                queue: queue(),
+                devices: devices(),
+                last_tick: Instant::now(),
+            }),
+        }
+    }
+
+    /// Run `f` on the state with the playhead brought up to now, looping at
+    /// the end of the track
+    fn with_state<T>(&self, f: impl FnOnce(&mut DemoState) -> T) -> T {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = state.last_tick.elapsed();
+        state.last_tick = Instant::now();
+        let track = &mut state.track;
+        if track.is_playing {
+            let progress = track.progress.unwrap_or(0) + elapsed.as_millis() as u64;
+            track.progress = Some(progress % track.duration);
+        }
+        f(&mut state)
+    }
+
+    /// Start the track over, playing
+    fn restart(&self) {
+        self.with_state(|state| {
+            state.track.progress = Some(0);
+            state.track.is_playing = true;
+        });
+    }
+}
+
+impl Default for DemoPlayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Player for DemoPlayer {
+    fn name(&self) -> &'static str {
+        "demo"
+    }
+
+    async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
+        Ok(Some(self.with_state(|state| state.track.clone())))
+    }
+
+    async fn play(&self) -> Result<()> {
+        self.with_state(|state| state.track.is_playing = true);
+        Ok(())
+    }
+
+    async fn pause(&self) -> Result<()> {
+        self.with_state(|state| state.track.is_playing = false);
+        Ok(())
+    }
+
+    async fn next(&self) -> Result<()> {
+        self.with_state(|state| state.track.progress = Some(0));
+        Ok(())
+    }
+
+    async fn prev(&self) -> Result<()> {
+        self.with_state(|state| state.track.progress = Some(0));
+        Ok(())
+    }
+
+    async fn seek(&self, position_ms: u64) -> Result<()> {
+        self.with_state(|state| state.track.progress = Some(position_ms));
+        Ok(())
+    }
+
+    async fn set_volume(&self, _volume: u8) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_queue(&self) -> Result<Vec<QueueItem>> {
+        Ok(self.with_state(|state| state.queue.clone()))
+    }
+
+    async fn recently_played(&self, _limit: u32) -> Result<Vec<HistoryItem>> {
+        Ok(history())
+    }
+
+    async fn is_saved(&self, _uri: &str) -> Result<bool> {
+        Ok(self.with_state(|state| state.track.saved == Some(true)))
+    }
+
+    async fn set_saved(&self, _uri: &str, saved: bool) -> Result<()> {
+        self.with_state(|state| state.track.saved = Some(saved));
+        Ok(())
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchItem>> {
+        Ok(search(query))
+    }
+
+    async fn play_uri(&self, _uri: &str) -> Result<()> {
+        self.restart();
+        Ok(())
+    }
+
+    /// The demo track stands in for the first; the rest stay queued
+    async fn play_uris(&self, uris: &[&str]) -> Result<()> {
+        let rest = uris.get(1..).unwrap_or_default();
+        self.with_state(|state| state.queue.retain(|item| rest.contains(&item.uri.as_str())));
+        self.restart();
+        Ok(())
+    }
+
+    /// The demo track stands in for `uri`; what came before it is gone
+    async fn play_in_context(&self, _context_uri: &str, uri: &str) -> Result<()> {
+        self.with_state(|state| {
+            if let Some(pos) = state.queue.iter().position(|item| item.uri == uri) {
+                state.queue.drain(..=pos);
+            }
+        });
+        self.restart();
+        Ok(())
+    }
+
+    async fn start_radio(&self) -> Result<Radio> {
+        self.restart();
+        self.with_state(|state| {
+            Ok(Radio {
+                seed: format!("{} - {}", state.track.name, state.track.artist),
+                tracks: state.queue.len(),
+            })
+        })
+    }
+
+    async fn list_devices(&self) -> Result<Vec<DeviceInfo>> {
+        Ok(self.with_state(|state| state.devices.clone()))
+    }
+
+    async fn transfer_playback(&self, device_id: &str) -> Result<()> {
+        self.with_state(|state| {
+            for device in &mut state.devices {
+                device.is_active = device.id == device_id;
+            }
+        });
+        Ok(())
+    }
+}
+
+pub fn lyrics() -> SyncedLyrics {
+    SyncedLyrics::parse(DEMO_LYRICS).expect("demo lyrics are valid LRC")
+}
+
+/// Procedural cover: a striped setting sun over a horizon grid
+pub fn album_art() -> DynamicImage {
+    let size = 128u32;
+    let center = size as f32 / 2.0;
+    let horizon = size * 5 / 8;
+
+    let img = RgbImage::from_fn(size, size, |x, y| {
+        let dx = x as f32 - center;
+        let dy = y as f32 - horizon as f32;
+        let dist = (dx * dx + dy * dy).sqrt();
+
+        if y < horizon {
+            // Sun with cut-out stripes that thicken towards the horizon
+            let stripe = (horizon - y) % 12 < (y * 6 / horizon).max(1);
+            if dist < size as f32 * 0.35 && !stripe {
+                Rgb([255, 200, 40])
+            } else {
+                let glow = (1.0 - dist / size as f32).clamp(0.0, 1.0);
+                Rgb([(60.0 * glow) as u8, (30.0 * glow) as u8, 0])
+            }
+        } else {
+            // Perspective grid below the horizon
+            let depth = y - horizon;
+            let horizontal = depth > 0 && (depth * depth / 4).is_multiple_of(8);
+            let vertical = depth > 0 && (dx * 8.0 / depth as f32).round() as i32 % 3 == 0;
+            if horizontal || vertical {
+                Rgb([200, 120, 0])
+            } else {
+                Rgb([10, 5, 0])
+            }
+        }
+    });
+
+    DynamicImage::ImageRgb8(img)
+}
+
+pub fn repos() -> Vec<RepoStatus> {
+    vec![
+        RepoStatus {
+            name: "phosphor".to_string(),
+            path: PathBuf::from("~/Projects/phosphor"),
+            branch: "main".to_string(),
+            is_clean: false,
+            ahead: 2,
+            behind: 0,
+            modified: 3,
+            staged: 1,
+            untracked: 1,
+            insertions: 128,
+            deletions: 41,
+            conflicts: 0,
+            stashes: 2,
+            stale_submodules: 0,
+            detached: false,
+        },
+        RepoStatus {
+            name: "dotfiles".to_string(),
+            path: PathBuf::from("~/dotfiles"),
+            branch: "master".to_string(),
+            is_clean: true,
+            ahead: 0,
+            behind: 1,
+            modified: 0,
+            staged: 0,
+            untracked: 0,
+            insertions: 0,
+            deletions: 0,
+            conflicts: 0,
+            stashes: 0,
+            stale_submodules: 1,
+            detached: false,
+        },
+        RepoStatus {
+            name: "crt-shader".to_string(),
+            path: PathBuf::from("~/Projects/crt-shader"),
+            branch: "feature/bloom".to_string(),
+            is_clean: true,
+            ahead: 0,
+            behind: 0,
+            modified: 0,
+            staged: 0,
+            untracked: 0,
+            insertions: 0,
+            deletions: 0,
+            conflicts: 0,
+            stashes: 0,
+            stale_submodules: 0,
+            detached: false,
+        },
+    ]
+}
+
+/// What Enter shows for each of `repos()`, matching their change counts
+pub fn repo_details() -> Vec<RepoDetails> {
+    let commits = commits();
+    let file = |code, staged, path: &str| ChangedFile { code, staged, path: path.to_string() };
+    let details = [
+        (
+            "phosphor",
+            vec!["main", "crt-curvature", "lyrics-offset"],
+            vec![
+                file('A', true, "src/tui/widgets/scanlines.rs"),
+                file('M', false, "src/tui/app.rs"),
+                file('M', false, "src/config.rs"),
+                file('M', false, "README.md"),
+                file('?', false, "notes.md"),
+            ],
+        ),
+        ("dotfiles", vec!["master"], Vec::new()),
+        ("crt-shader", vec!["feature/bloom", "main"], Vec::new()),
+    ];
+
+    repos()
+        .into_iter()
+        .zip(details)
+        .map(|(repo, (name, branches, files))| RepoDetails {
+            path: repo.path,
+            branches: branches.into_iter().map(str::to_string).collect(),
+            files,
+            commits: commits.iter().filter(|c| c.repo_name == name).cloned().collect(),
+        })
+        .collect()
+}
+
+/// Half a year of commits: busy weekdays, quiet weekends and the odd day off
+pub fn activity() -> Activity {
+    let mut activity = Activity::empty(26);
+    for (i, count) in activity.days.iter_mut().enumerate() {
+        let weekend = i % 7 == 0 || i % 7 == 6;
+        let noise = (i * 7919 + 13) % 11;
+        *count = match (weekend, noise) {
+            (_, 0..=2) => 0,
+            (true, _) => (noise % 3) as u32,
+            (false, _) => noise as u32 - 2,
+        };
+    }
+    activity
+}
+
+pub fn commits() -> Vec<CommitInfo> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or(0);
+
+    let entries = [
+        ("3f9a2c1d", "Add scanline intensity option", "ada", "phosphor", 12 * 60),
+        ("b71e04aa", "Tune bloom falloff", "ada", "crt-shader", 2 * 3600),
+        ("9c0d5e77", "Fix lyrics drift after seeking", "grace", "phosphor", 5 * 3600),
+        ("e4412b90", "Switch prompt to amber palette", "ada", "dotfiles", 26 * 3600),
+        ("1a2b3c4d", "Initial bloom pass", "grace", "crt-shader", 3 * 86400),
+    ];
+
+    entries
+        .iter()
+        .map(|(hash, message, author, repo, age)| CommitInfo {
+            hash: format!("{}{}", hash, "0".repeat(32)),
+            message: message.to_string(),
+            author: author.to_string(),
+            time: now - age,
+            repo_name: repo.to_string(),
+        })
+        .collect()
+}
+
+/// Unread GitHub notifications, one of each kind the panel highlights
+pub fn notifications() -> Vec<Notification> {
+    let entries = [
+        ("review_requested", "Add CRT curvature shader", "ada/phosphor", 8 * 60),
+        ("ci_activity", "CI workflow run failed for main", "ada/phosphor", 40 * 60),
+        ("mention", "Lyrics drift on long tracks", "grace/crt-shader", 3 * 3600),
+        ("subscribed", "v0.4.0", "ratatui/ratatui", 2 * 86400),
+    ];
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, (reason, title, repo, age))| Notification {
+            id: i.to_string(),
+            repo: repo.to_string(),
+            title: title.to_string(),
+            reason: reason.to_string(),
+            updated_at: SystemTime::now() - Duration::from_secs(*age),
+        })
+        .collect()
+}
@@ -1,6 +1,13 @@
-use anyhow::{Context, Result};
-use git2::{Repository, StatusOptions};
-use std::path::PathBuf;
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Days, Local, NaiveDate, TimeZone};
+use git2::{BranchType, Repository, Sort, Status, StatusOptions, SubmoduleIgnore, SubmoduleStatus};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::config::GitConfig;
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -14,6 +21,28 @@ pub struct RepoStatus {
     pub modified: usize,
     pub staged: usize,
     pub untracked: usize,
+    /// Lines added and removed by uncommitted changes, staged or not
+    pub insertions: usize,
+    pub deletions: usize,
+    /// Files with unresolved merge conflicts
+    pub conflicts: usize,
+    pub stashes: usize,
+    /// Submodules not checked out, or at a different commit than recorded
+    pub stale_submodules: usize,
+    /// HEAD points at a commit rather than a branch
+    pub detached: bool,
+}
+
+impl RepoStatus {
+    /// Branch name, or "(detached)" when HEAD isn't on one
+    pub fn branch_label(&self) -> &str {
+        if self.detached { "(detached)" } else { &self.branch }
+    }
+
+    /// Whether uncommitted work exceeds the warning threshold (0 disables)
+    pub fn exceeds_dirty_threshold(&self, threshold: usize) -> bool {
+        threshold > 0 && self.modified + self.untracked > threshold
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -25,50 +54,173 @@ pub struct CommitInfo {
     pub repo_name: String,
 }
 
+impl CommitInfo {
+    pub fn timestamp(&self) -> SystemTime {
+        UNIX_EPOCH + Duration::from_secs(self.time.max(0) as u64)
+    }
+}
+
+/// An uncommitted change, with a `git status --short` style code
+#[derive(Debug, Clone)]
+pub struct ChangedFile {
+    /// 'M' modified, 'A' added, 'D' deleted, 'R' renamed, '?' untracked
+    pub code: char,
+    /// Staged rather than only in the worktree
+    pub staged: bool,
+    pub path: String,
+}
+
+/// One repo in depth, for the expanded git panel
+#[derive(Debug, Clone)]
+pub struct RepoDetails {
+    pub path: PathBuf,
+    /// Local branches, the checked out one first
+    pub branches: Vec<String>,
+    pub files: Vec<ChangedFile>,
+    pub commits: Vec<CommitInfo>,
+}
+
+/// Commits per day over the last few weeks, for the contribution heatmap
+#[derive(Debug, Clone)]
+pub struct Activity {
+    /// Sunday of the first (leftmost) week
+    pub start: NaiveDate,
+    /// Commits per day from `start` through today
+    pub days: Vec<u32>,
+}
+
+impl Activity {
+    /// `weeks` of days ending today, with nothing counted yet
+    pub fn empty(weeks: usize) -> Self {
+        let today = Local::now().date_naive();
+        let back = today.weekday().num_days_from_sunday() as u64 + 7 * (weeks.max(1) as u64 - 1);
+        let start = today.checked_sub_days(Days::new(back)).unwrap_or(today);
+        let len = (today - start).num_days() as usize + 1;
+        Self { start, days: vec![0; len] }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.days.iter().sum()
+    }
+
+    fn add(&mut self, date: NaiveDate) {
+        let index = (date - self.start).num_days();
+        if let Some(count) = usize::try_from(index).ok().and_then(|i| self.days.get_mut(i)) {
+            *count += 1;
+        }
+    }
+}
+
+/// Files in the git dir whose changes show up in the panel
+#[cfg(target_os = "linux")]
+const WATCHED_FILES: [&str; 4] = ["HEAD", "index", "packed-refs", "MERGE_HEAD"];
+
+/// A repo with its `[[git.repo]]` settings, or the defaults
+struct TrackedRepo {
+    path: PathBuf,
+    name: String,
+    /// None for the usual interval
+    refresh: Option<Duration>,
+    fetch: bool,
+    /// Ref for ahead/behind, None for the current branch on origin
+    compare: Option<String>,
+    ignore_commits: bool,
+}
+
+impl TrackedRepo {
+    fn new(path: PathBuf) -> Self {
+        Self {
+            name: repo_name(&path),
+            path,
+            refresh: None,
+            fetch: true,
+            compare: None,
+            ignore_commits: false,
+        }
+    }
+}
+
 pub struct GitTracker {
-    repos: Vec<PathBuf>,
+    repos: Vec<TrackedRepo>,
+    /// Each repo's last status and when it was read
+    cache: Mutex<HashMap<PathBuf, (Instant, RepoStatus)>>,
 }
 
 impl GitTracker {
-    pub fn new(repo_paths: &[String]) -> Self {
-        let repos = repo_paths
+    /// `git.repos` followed by `[[git.repo]]` paths that aren't among them
+    pub fn new(config: &GitConfig) -> Self {
+        let expand = |path: &str| PathBuf::from(shellexpand::tilde(path).as_ref());
+        let mut repos: Vec<TrackedRepo> = config
+            .repos
             .iter()
-            .map(|p| {
-                let expanded = shellexpand::tilde(p);
-                PathBuf::from(expanded.as_ref())
-            })
+            .map(|path| TrackedRepo::new(expand(path)))
             .collect();
 
-        Self { repos }
+        for settings in &config.repo_settings {
+            let path = expand(&settings.path);
+            let index = match repos.iter().position(|repo| repo.path == path) {
+                Some(index) => index,
+                None => {
+                    repos.push(TrackedRepo::new(path));
+                    repos.len() - 1
+                }
+            };
+            let repo = &mut repos[index];
+            if !settings.name.is_empty() {
+                repo.name = settings.name.clone();
+            }
+            repo.refresh = (settings.refresh_secs > 0).then(|| Duration::from_secs(settings.refresh_secs));
+            repo.fetch = settings.fetch;
+            repo.compare = (!settings.compare.is_empty()).then(|| settings.compare.clone());
+            repo.ignore_commits = settings.ignore_commits;
+        }
+
+        Self { repos, cache: Mutex::new(HashMap::new()) }
     }
 
     pub fn get_status(&self) -> Result<Vec<RepoStatus>> {
-        let mut statuses = Vec::new();
+        Ok(self.get_status_cached(Duration::ZERO, true))
+    }
 
-        for path in &self.repos {
-            if let Ok(status) = self.get_repo_status(path) {
-                statuses.push(status);
-            }
-        }
+    /// Status of every repo, reusing the last read of those read within their
+    /// refresh interval (`default` unless overridden) unless `force`
+    pub fn get_status_cached(&self, default: Duration, force: bool) -> Vec<RepoStatus> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        self.repos
+            .iter()
+            .filter_map(|repo| {
+                let interval = repo.refresh.unwrap_or(default);
+                let fresh = cache
+                    .get(&repo.path)
+                    .filter(|(read_at, _)| !force && read_at.elapsed() < interval);
+                if let Some((_, status)) = fresh {
+                    return Some(status.clone());
+                }
+                let status = self.get_repo_status(repo).ok()?;
+                cache.insert(repo.path.clone(), (Instant::now(), status.clone()));
+                Some(status)
+            })
+            .collect()
+    }
 
-        Ok(statuses)
+    /// How often to check for repos due a read: the shortest of their
+    /// refresh intervals, `default` unless overridden
+    pub fn refresh_interval(&self, default: Duration) -> Duration {
+        self.repos
+            .iter()
+            .map(|repo| repo.refresh.unwrap_or(default))
+            .min()
+            .unwrap_or(default)
     }
 
-    fn get_repo_status(&self, path: &PathBuf) -> Result<RepoStatus> {
-        let repo = Repository::open(path)
+    fn get_repo_status(&self, tracked: &TrackedRepo) -> Result<RepoStatus> {
+        let path = &tracked.path;
+        let mut repo = Repository::open(path)
             .with_context(|| format!("Failed to open repository: {}", path.display()))?;
 
-        let name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
+        let name = tracked.name.clone();
 
-        let head = repo.head()?;
-        let branch = head
-            .shorthand()
-            .unwrap_or("HEAD")
-            .to_string();
+        let branch = repo.head()?.shorthand().unwrap_or("HEAD").to_string();
 
         let mut opts = StatusOptions::new();
         opts.include_untracked(true);
@@ -77,9 +229,14 @@ impl GitTracker {
         let mut modified = 0;
         let mut staged = 0;
         let mut untracked = 0;
+        let mut conflicts = 0;
 
         for entry in statuses_list.iter() {
             let status = entry.status();
+            if status.is_conflicted() {
+                conflicts += 1;
+                continue;
+            }
             if status.is_wt_modified() || status.is_wt_deleted() || status.is_wt_renamed() {
                 modified += 1;
             }
@@ -91,9 +248,20 @@ impl GitTracker {
             }
         }
 
-        let is_clean = modified == 0 && staged == 0 && untracked == 0;
+        let is_clean = modified == 0 && staged == 0 && untracked == 0 && conflicts == 0;
+        // Counting stashes needs the repo mutably
+        drop(statuses_list);
+
+        let (ahead, behind) = self.get_ahead_behind(&repo, tracked.compare.as_deref())?;
+        let (insertions, deletions) = if is_clean { (0, 0) } else { diffstat(&repo)? };
+        let detached = repo.head_detached().unwrap_or(false);
+        let stale_submodules = stale_submodules(&repo);
 
-        let (ahead, behind) = self.get_ahead_behind(&repo)?;
+        let mut stashes = 0;
+        let _ = repo.stash_foreach(|_, _, _| {
+            stashes += 1;
+            true
+        });
 
         Ok(RepoStatus {
             name,
@@ -105,10 +273,17 @@ impl GitTracker {
             modified,
             staged,
             untracked,
+            insertions,
+            deletions,
+            conflicts,
+            stashes,
+            stale_submodules,
+            detached,
         })
     }
 
-    fn get_ahead_behind(&self, repo: &Repository) -> Result<(usize, usize)> {
+    /// Commits ahead of and behind `compare`, or the current branch on origin
+    fn get_ahead_behind(&self, repo: &Repository, compare: Option<&str>) -> Result<(usize, usize)> {
         let head = match repo.head() {
             Ok(h) => h,
             Err(_) => return Ok((0, 0)),
@@ -119,6 +294,13 @@ impl GitTracker {
             None => return Ok((0, 0)),
         };
 
+        if let Some(compare) = compare {
+            let Ok(upstream) = repo.revparse_single(compare).and_then(|object| object.peel_to_commit()) else {
+                return Ok((0, 0));
+            };
+            return Ok(repo.graph_ahead_behind(local_oid, upstream.id())?);
+        }
+
         let branch_name = match head.shorthand() {
             Some(name) => name,
             None => return Ok((0, 0)),
@@ -139,37 +321,85 @@ impl GitTracker {
         Ok((ahead, behind))
     }
 
-    pub fn get_recent_commits(&self, max_commits: usize) -> Result<Vec<CommitInfo>> {
+    /// Branches, changed files and recent commits of the repo at `path`
+    pub fn get_details(&self, path: &PathBuf, max_commits: usize) -> Result<RepoDetails> {
+        let repo = Repository::open(path)
+            .with_context(|| format!("Failed to open repository: {}", path.display()))?;
+
+        let current = repo.head().ok().and_then(|head| head.shorthand().map(str::to_string));
+        let mut branches: Vec<String> = repo
+            .branches(Some(BranchType::Local))?
+            .filter_map(|branch| branch.ok())
+            .filter_map(|(branch, _)| branch.name().ok().flatten().map(str::to_string))
+            .filter(|name| Some(name) != current.as_ref())
+            .collect();
+        branches.sort();
+        if let Some(current) = current {
+            branches.insert(0, current);
+        }
+
+        let mut opts = StatusOptions::new();
+        opts.include_untracked(true);
+        let files = repo
+            .statuses(Some(&mut opts))?
+            .iter()
+            .filter_map(|entry| {
+                let (code, staged) = change_code(entry.status())?;
+                Some(ChangedFile {
+                    code,
+                    staged,
+                    path: entry.path().unwrap_or("?").to_string(),
+                })
+            })
+            .collect();
+
+        let name = self
+            .repos
+            .iter()
+            .find(|repo| repo.path == *path)
+            .map_or_else(|| repo_name(path), |repo| repo.name.clone());
+        Ok(RepoDetails {
+            path: path.clone(),
+            branches,
+            files,
+            commits: self.get_repo_commits(path, &name, max_commits, &[])?,
+        })
+    }
+
+    /// Newest commits across all repos but those with `ignore_commits`, only
+    /// those by `authors` (names or emails) unless it is empty
+    pub fn get_recent_commits(&self, max_commits: usize, authors: &[String]) -> Result<Vec<CommitInfo>> {
         let mut all_commits = Vec::new();
 
-        for path in &self.repos {
-            if let Ok(commits) = self.get_repo_commits(path, max_commits) {
+        for repo in self.repos.iter().filter(|repo| !repo.ignore_commits) {
+            if let Ok(commits) = self.get_repo_commits(&repo.path, &repo.name, max_commits, authors) {
                 all_commits.extend(commits);
             }
         }
 
         // Sort by time descending
-        all_commits.sort_by(|a, b| b.time.cmp(&a.time));
+        all_commits.sort_by_key(|commit| std::cmp::Reverse(commit.time));
         all_commits.truncate(max_commits);
 
         Ok(all_commits)
     }
 
-    fn get_repo_commits(&self, path: &PathBuf, max: usize) -> Result<Vec<CommitInfo>> {
+    fn get_repo_commits(
+        &self,
+        path: &PathBuf,
+        repo_name: &str,
+        max: usize,
+        authors: &[String],
+    ) -> Result<Vec<CommitInfo>> {
         let repo = Repository::open(path)?;
         let mut revwalk = repo.revwalk()?;
         revwalk.push_head()?;
 
-        let repo_name = path
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("unknown")
-            .to_string();
-
         let commits: Vec<CommitInfo> = revwalk
-            .take(max)
             .filter_map(|oid| oid.ok())
             .filter_map(|oid| repo.find_commit(oid).ok())
+            .filter(|commit| authors.is_empty() || authored_by(commit, authors))
+            .take(max)
             .map(|commit| {
                 let message = commit
                     .message()
@@ -184,11 +414,254 @@ impl GitTracker {
                     message,
                     author: commit.author().name().unwrap_or("Unknown").to_string(),
                     time: commit.time().seconds(),
-                    repo_name: repo_name.clone(),
+                    repo_name: repo_name.to_string(),
                 }
             })
             .collect();
 
         Ok(commits)
     }
+
+    /// Commits on local branches of every repo but those with `ignore_commits`
+    /// over the last `weeks` weeks, only those authored by `author_email`
+    /// unless it is empty
+    pub fn get_activity(&self, weeks: usize, author_email: &str) -> Activity {
+        let mut activity = Activity::empty(weeks);
+        for repo in self.repos.iter().filter(|repo| !repo.ignore_commits) {
+            let _ = count_commits(&repo.path, author_email, &mut activity);
+        }
+        activity
+    }
+
+    /// Call `on_change` soon after a repo's HEAD, index or branches change
+    /// (commits, checkouts, staging) until it returns false. Watches on a
+    /// background thread; repos that can't be watched are skipped
+    #[cfg(target_os = "linux")]
+    pub fn watch(&self, mut on_change: impl FnMut() -> bool + Send + 'static) -> Result<()> {
+        use inotify::{EventMask, Inotify, WatchMask};
+
+        let mut inotify = Inotify::init().context("Failed to start inotify")?;
+        let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::CREATE | WatchMask::DELETE;
+        let mut branch_dirs = HashMap::new();
+        for tracked in &self.repos {
+            let Ok(repo) = Repository::open(&tracked.path) else {
+                continue;
+            };
+            let _ = inotify.watches().add(repo.path(), mask);
+            watch_tree(&inotify, &repo.path().join("refs/heads"), mask, &mut branch_dirs);
+        }
+
+        std::thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            loop {
+                let Ok(events) = inotify.read_events_blocking(&mut buffer) else {
+                    return;
+                };
+                // Files are written via `.lock` copies renamed into place, and
+                // fetches and our own reads touch others (FETCH_HEAD, ...)
+                let mut relevant = false;
+                let mut new_dirs = Vec::new();
+                for event in events {
+                    let name = event.name.and_then(|name| name.to_str()).unwrap_or_default();
+                    if event.mask.contains(EventMask::IGNORED) {
+                        branch_dirs.remove(&event.wd);
+                    } else if let Some(dir) = branch_dirs.get(&event.wd) {
+                        // Branches named `a/b` live in `refs/heads/a/b`, so the
+                        // first one under a new prefix brings a directory with it
+                        if event.mask.contains(EventMask::ISDIR) {
+                            if event.mask.intersects(EventMask::CREATE | EventMask::MOVED_TO) {
+                                new_dirs.push(dir.join(name));
+                            }
+                            relevant = true;
+                        } else {
+                            relevant |= !name.ends_with(".lock");
+                        }
+                    } else {
+                        relevant |= WATCHED_FILES.contains(&name);
+                    }
+                }
+                // The branch may already be in the new directory by the time
+                // it's watched, which the refresh below picks up anyway
+                for dir in new_dirs {
+                    watch_tree(&inotify, &dir, mask, &mut branch_dirs);
+                }
+                if relevant && !on_change() {
+                    return;
+                }
+            }
+        });
+        Ok(())
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    pub fn watch(&self, _on_change: impl FnMut() -> bool + Send + 'static) -> Result<()> {
+        bail!("Watching repos is only supported on Linux")
+    }
+
+    /// `git fetch` every repo that allows it so ahead/behind counts see the
+    /// remote, returning the names of those that failed
+    pub fn fetch(&self) -> Vec<String> {
+        self.repos
+            .iter()
+            .filter(|repo| repo.fetch && fetch_repo(&repo.path).is_err())
+            .map(|repo| repo.name.clone())
+            .collect()
+    }
+}
+
+/// Watch `dir` and every directory below it, remembering where each watch is
+#[cfg(target_os = "linux")]
+fn watch_tree(
+    inotify: &inotify::Inotify,
+    dir: &Path,
+    mask: inotify::WatchMask,
+    dirs: &mut HashMap<inotify::WatchDescriptor, PathBuf>,
+) {
+    let Ok(wd) = inotify.watches().add(dir, mask) else {
+        return;
+    };
+    dirs.insert(wd, dir.to_path_buf());
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+            watch_tree(inotify, &entry.path(), mask, dirs);
+        }
+    }
+}
+
+fn count_commits(path: &Path, author_email: &str, activity: &mut Activity) -> Result<()> {
+    let repo = Repository::open(path)?;
+    let mut revwalk = repo.revwalk()?;
+    revwalk.push_glob("refs/heads")?;
+    revwalk.set_sorting(Sort::TIME)?;
+
+    let since = activity
+        .start
+        .and_hms_opt(0, 0, 0)
+        .and_then(|midnight| Local.from_local_datetime(&midnight).earliest())
+        .map_or(0, |midnight| midnight.timestamp());
+
+    for oid in revwalk.filter_map(|oid| oid.ok()) {
+        let Ok(commit) = repo.find_commit(oid) else {
+            continue;
+        };
+        // Newest first by commit time, so everything after this is older
+        if commit.time().seconds() < since {
+            break;
+        }
+        let author = commit.author();
+        if !author_email.is_empty()
+            && !author.email().is_some_and(|email| email.eq_ignore_ascii_case(author_email))
+        {
+            continue;
+        }
+        if let Some(when) = Local.timestamp_opt(author.when().seconds(), 0).single() {
+            activity.add(when.date_naive());
+        }
+    }
+    Ok(())
+}
+
+/// Whether the commit's author name or email is one of `authors`
+fn authored_by(commit: &git2::Commit, authors: &[String]) -> bool {
+    let author = commit.author();
+    let (name, email) = (author.name().unwrap_or_default(), author.email().unwrap_or_default());
+    authors
+        .iter()
+        .any(|wanted| wanted.eq_ignore_ascii_case(email) || wanted == name)
+}
+
+/// Lines added and removed between HEAD and the working tree, like
+/// `git diff HEAD --shortstat` (untracked files aren't counted)
+fn diffstat(repo: &Repository) -> Result<(usize, usize)> {
+    let head = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let stats = repo.diff_tree_to_workdir_with_index(head.as_ref(), None)?.stats()?;
+    Ok((stats.insertions(), stats.deletions()))
+}
+
+/// Submodules that are uninitialized or whose checkout differs from the
+/// commit the superproject records
+fn stale_submodules(repo: &Repository) -> usize {
+    let Ok(submodules) = repo.submodules() else {
+        return 0;
+    };
+    submodules
+        .iter()
+        .filter_map(|submodule| submodule.name())
+        .filter_map(|name| repo.submodule_status(name, SubmoduleIgnore::Dirty).ok())
+        .filter(|status| status.intersects(SubmoduleStatus::WD_UNINITIALIZED | SubmoduleStatus::WD_MODIFIED))
+        .count()
+}
+
+/// Short code for a status entry, preferring the staged side; None for
+/// entries that aren't changes (ignored files)
+fn change_code(status: Status) -> Option<(char, bool)> {
+    let code = if status.intersects(Status::INDEX_NEW) {
+        ('A', true)
+    } else if status.intersects(Status::INDEX_DELETED) {
+        ('D', true)
+    } else if status.intersects(Status::INDEX_RENAMED) {
+        ('R', true)
+    } else if status.intersects(Status::INDEX_MODIFIED | Status::INDEX_TYPECHANGE) {
+        ('M', true)
+    } else if status.intersects(Status::WT_NEW) {
+        ('?', false)
+    } else if status.intersects(Status::WT_DELETED) {
+        ('D', false)
+    } else if status.intersects(Status::WT_RENAMED) {
+        ('R', false)
+    } else if status.intersects(Status::WT_MODIFIED | Status::WT_TYPECHANGE) {
+        ('M', false)
+    } else {
+        return None;
+    };
+    Some(code)
+}
+
+/// Top-level directory of the repository containing `path`
+pub fn repo_root(path: &Path) -> Result<PathBuf> {
+    let repo = Repository::discover(path)
+        .with_context(|| format!("{} isn't inside a git repository", path.display()))?;
+    let Some(workdir) = repo.workdir() else {
+        bail!("{} is a bare repository", repo.path().display());
+    };
+    Ok(workdir.canonicalize()?)
+}
+
+fn repo_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("unknown")
+        .to_string()
+}
+
+/// Shells out so credential helpers and the SSH agent work as they do on the
+/// command line. Nothing may prompt while the TUI owns the terminal, so HTTPS
+/// prompts are off and ssh runs in batch mode (on top of `core.sshCommand`)
+fn fetch_repo(path: &Path) -> Result<()> {
+    let ssh = std::env::var("GIT_SSH_COMMAND").ok().or_else(|| {
+        Repository::open(path)
+            .and_then(|repo| repo.config())
+            .and_then(|config| config.get_string("core.sshCommand"))
+            .ok()
+    });
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(path)
+        .args(["fetch", "--quiet"])
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .env(
+            "GIT_SSH_COMMAND",
+            format!("{} -o BatchMode=yes", ssh.as_deref().unwrap_or("ssh")),
+        )
+        .stdin(Stdio::null())
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git fetch: {}", stderr.lines().last().unwrap_or("failed").trim());
+    }
+    Ok(())
 }
@@ -0,0 +1,120 @@
+//! Unread GitHub notifications (mentions, review requests, CI failures, ...)
+//! through the REST API with a personal access token
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::time::SystemTime;
+
+use crate::config::GitHubConfig;
+use crate::modules::net;
+
+const API: &str = "https://api.github.com";
+
+/// Pages are capped at 50; more than that won't fit in a panel anyway
+const PAGE_SIZE: u32 = 50;
+
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// Thread id, used to mark it read
+    pub id: String,
+    /// `owner/name`
+    pub repo: String,
+    pub title: String,
+    /// Why it was sent, e.g. "mention", "review_requested" or "ci_activity"
+    pub reason: String,
+    pub updated_at: SystemTime,
+}
+
+impl Notification {
+    /// Short label for the reason, e.g. "review" for a review request
+    pub fn label(&self) -> &str {
+        match self.reason.as_str() {
+            "mention" | "team_mention" => "mention",
+            "review_requested" => "review",
+            "ci_activity" => "ci",
+            "assign" => "assigned",
+            "security_alert" => "security",
+            "subscribed" => "watching",
+            "state_change" => "state",
+            "invitation" => "invite",
+            other => other,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Thread {
+    id: String,
+    reason: String,
+    updated_at: String,
+    subject: Subject,
+    repository: Repository,
+}
+
+#[derive(Deserialize)]
+struct Subject {
+    title: String,
+}
+
+#[derive(Deserialize)]
+struct Repository {
+    full_name: String,
+}
+
+pub struct GitHubClient {
+    token: String,
+}
+
+impl GitHubClient {
+    /// None when no token is configured (`github.token` or `GITHUB_TOKEN`)
+    pub fn new(config: &GitHubConfig) -> Option<Self> {
+        let token = if config.token.is_empty() {
+            std::env::var("GITHUB_TOKEN").unwrap_or_default()
+        } else {
+            config.token.clone()
+        };
+        (!token.is_empty()).then_some(Self { token })
+    }
+
+    fn request(&self, method: &str, path: &str) -> ureq::Request {
+        net::agent()
+            .request(method, &format!("{}{}", API, path))
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("Accept", "application/vnd.github+json")
+            .set("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    /// Unread notifications, newest first
+    pub fn notifications(&self) -> Result<Vec<Notification>> {
+        let response = match self
+            .request("GET", &format!("/notifications?per_page={}", PAGE_SIZE))
+            .call()
+        {
+            Ok(response) => response,
+            Err(ureq::Error::Status(401 | 403, _)) => {
+                bail!("GitHub rejected the token (check github.token)")
+            }
+            Err(e) => return Err(e).context("Couldn't fetch GitHub notifications"),
+        };
+        let threads: Vec<Thread> = response.into_json()?;
+
+        Ok(threads
+            .into_iter()
+            .map(|thread| Notification {
+                id: thread.id,
+                repo: thread.repository.full_name,
+                title: thread.subject.title,
+                reason: thread.reason,
+                updated_at: chrono::DateTime::parse_from_rfc3339(&thread.updated_at)
+                    .map_or(SystemTime::UNIX_EPOCH, SystemTime::from),
+            })
+            .collect())
+    }
+
+    pub fn mark_read(&self, id: &str) -> Result<()> {
+        self.request("PATCH", &format!("/notifications/threads/{}", id))
+            .call()
+            .context("GitHub refused to mark the notification read")?;
+        Ok(())
+    }
+}
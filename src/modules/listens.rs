@@ -0,0 +1,138 @@
+//! Turns Spotify poll results into listens, shared by scrobbling and the stats log
+
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use crate::modules::spotify::TrackInfo;
+
+/// Tracks shorter than this never count as played
+const MIN_TRACK_LENGTH: Duration = Duration::from_secs(30);
+/// Listening time after which any track counts, however long it is
+const MAX_LISTEN_REQUIRED: Duration = Duration::from_secs(4 * 60);
+/// Jumping back this far after a track counted means it is playing again
+const REPEAT_REWIND_MS: u64 = 30_000;
+
+/// One play of a track
+#[derive(Debug, Clone)]
+pub struct Listen {
+    pub artist: String,
+    pub track: String,
+    pub album: String,
+    pub duration: Duration,
+    /// Unix seconds when playback of the track started
+    pub started_at: u64,
+    pub uri: Option<String>,
+}
+
+pub enum ListenEvent {
+    /// A track started playing
+    Started(Listen),
+    /// Enough of the track was heard to count as played
+    Played(Listen),
+}
+
+/// Listen currently being timed
+struct Playing {
+    key: String,
+    listen: Listen,
+    listened: Duration,
+    last_seen: Instant,
+    last_progress: Option<u64>,
+    was_playing: bool,
+    started: bool,
+    played: bool,
+}
+
+/// Times what is actually heard between polls and applies the standard rule:
+/// a track counts once half of it or four minutes have played, whichever is first
+#[derive(Default)]
+pub struct ListenTracker {
+    playing: Option<Playing>,
+}
+
+impl ListenTracker {
+    /// Feed the latest poll result, returning what happened since the last one
+    pub fn update(&mut self, track: Option<&TrackInfo>) -> Vec<ListenEvent> {
+        let now = Instant::now();
+        let mut events = Vec::new();
+
+        // Keep the listen across polls without a track (errors, ads) but don't count them
+        let Some(track) = track.filter(|t| !t.is_episode) else {
+            if let Some(playing) = self.playing.as_mut() {
+                playing.last_seen = now;
+                playing.was_playing = false;
+            }
+            return events;
+        };
+
+        let key = track
+            .uri
+            .clone()
+            .unwrap_or_else(|| format!("{}\u{1f}{}", track.artist, track.name));
+
+        let is_new = match &self.playing {
+            Some(playing) => {
+                playing.key != key
+                    || (playing.played
+                        && playing.last_progress.zip(track.progress).is_some_and(
+                            |(last, current)| current + REPEAT_REWIND_MS < last,
+                        ))
+            }
+            None => true,
+        };
+
+        if is_new {
+            let elapsed = Duration::from_millis(track.progress.unwrap_or(0));
+            let started_at = SystemTime::now()
+                .checked_sub(elapsed)
+                .unwrap_or_else(SystemTime::now)
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs());
+
+            self.playing = Some(Playing {
+                key,
+                listen: Listen {
+                    artist: track.artist.clone(),
+                    track: track.name.clone(),
+                    album: track.album.clone(),
+                    duration: Duration::from_millis(track.duration),
+                    started_at,
+                    uri: track.uri.clone(),
+                },
+                listened: Duration::ZERO,
+                last_seen: now,
+                last_progress: track.progress,
+                was_playing: track.is_playing,
+                started: false,
+                played: false,
+            });
+        } else if let Some(playing) = self.playing.as_mut() {
+            // Only time spent playing between two polls counts
+            if playing.was_playing && track.is_playing {
+                playing.listened += now.duration_since(playing.last_seen);
+            }
+            playing.last_seen = now;
+            playing.last_progress = track.progress;
+            playing.was_playing = track.is_playing;
+        }
+
+        let Some(playing) = self.playing.as_mut() else {
+            return events;
+        };
+
+        if track.is_playing && !playing.started {
+            playing.started = true;
+            events.push(ListenEvent::Started(playing.listen.clone()));
+        }
+
+        let duration = playing.listen.duration;
+        if !playing.played
+            && duration >= MIN_TRACK_LENGTH
+            && playing.listened >= (duration / 2).min(MAX_LISTEN_REQUIRED)
+        {
+            playing.played = true;
+            events.push(ListenEvent::Played(playing.listen.clone()));
+        }
+
+        events
+    }
+}
@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::modules::net;
+
 /// A single line of lyrics with timestamp
 #[derive(Debug, Clone)]
 pub struct LyricLine {
@@ -32,6 +34,8 @@ struct LrcLibResponse {
 struct LrcLibSearchResult {
     #[serde(rename = "syncedLyrics")]
     synced_lyrics: Option<String>,
+    /// Track length in seconds
+    duration: Option<f64>,
 }
 
 impl SyncedLyrics {
@@ -45,10 +49,10 @@ impl SyncedLyrics {
                 continue;
             }
 
-            if let Some((timestamp_ms, text)) = parse_timestamp_line(line) {
-                if !text.is_empty() {
-                    lines.push(LyricLine { timestamp_ms, text });
-                }
+            if let Some((timestamp_ms, text)) = parse_timestamp_line(line)
+                && !text.is_empty()
+            {
+                lines.push(LyricLine { timestamp_ms, text });
             }
         }
 
@@ -125,6 +129,51 @@ fn parse_timestamp_line(line: &str) -> Option<(u64, String)> {
     Some((timestamp_ms, text))
 }
 
+/// Parenthesized/bracketed groups starting with these are dropped from titles
+const TITLE_GROUP_PREFIXES: [&str; 5] = ["feat", "ft.", "with ", "live", "remaster"];
+/// " - ..." title suffixes starting with these are dropped
+const TITLE_SUFFIX_PREFIXES: [&str; 6] = ["remaster", "live", "feat", "radio edit", "mono", "single version"];
+
+/// Search results further than this from the track length are other versions
+const DURATION_TOLERANCE_SECS: f64 = 5.0;
+
+/// First artist of Spotify's comma-joined list, which is what LRClib indexes by
+fn primary_artist(artist_name: &str) -> &str {
+    artist_name.split(", ").next().unwrap_or(artist_name).trim()
+}
+
+/// Strip decorations LRClib titles usually lack: "(feat. X)", "[Live]",
+/// "(2011 Remaster)", "- Remastered 2009", "- Live at Wembley"
+fn clean_track_name(track_name: &str) -> String {
+    let mut name = track_name.trim().to_string();
+
+    if let Some(idx) = name.find(" - ") {
+        let suffix = name[idx + 3..].to_lowercase();
+        let strip = TITLE_SUFFIX_PREFIXES.iter().any(|p| suffix.starts_with(p))
+            || suffix.contains("remaster");
+        if strip && idx > 0 {
+            name.truncate(idx);
+        }
+    }
+
+    // Peel matching groups off the end, e.g. "Song (Live) (feat. X)"
+    while name.ends_with(')') || name.ends_with(']') {
+        let Some(open) = name.rfind(['(', '[']) else {
+            break;
+        };
+        let group = name[open + 1..name.len() - 1].to_lowercase();
+        let strip = TITLE_GROUP_PREFIXES.iter().any(|p| group.starts_with(p))
+            || group.contains("remaster");
+        if !strip || open == 0 {
+            break;
+        }
+        name.truncate(open);
+        name.truncate(name.trim_end().len());
+    }
+
+    name
+}
+
 /// Fetch lyrics from LRClib API
 pub fn fetch_lyrics(
     track_name: &str,
@@ -132,27 +181,44 @@ pub fn fetch_lyrics(
     album_name: &str,
     duration_secs: u64,
 ) -> LyricsStatus {
+    // Primary artist first, then the full list in case LRClib indexed it that way
+    let primary = primary_artist(artist_name);
+    let mut artists = vec![primary];
+    if primary != artist_name {
+        artists.push(artist_name);
+    }
+
     // Try exact match first
-    let url = format!(
-        "https://lrclib.net/api/get?track_name={}&artist_name={}&album_name={}&duration={}",
-        urlencoding::encode(track_name),
-        urlencoding::encode(artist_name),
-        urlencoding::encode(album_name),
-        duration_secs,
-    );
+    for artist in &artists {
+        let url = format!(
+            "https://lrclib.net/api/get?track_name={}&artist_name={}&album_name={}&duration={}",
+            urlencoding::encode(track_name),
+            urlencoding::encode(artist),
+            urlencoding::encode(album_name),
+            duration_secs,
+        );
+
+        match fetch_from_url(&url) {
+            LyricsStatus::NotFound => {}
+            status => return status,
+        }
+    }
 
-    match fetch_from_url(&url) {
-        LyricsStatus::Available(lyrics) => return LyricsStatus::Available(lyrics),
-        LyricsStatus::NotFound => {
-            // Fallback to search
-            return fetch_lyrics_search(track_name, artist_name);
+    // Fallback to search with a cleaned-up title
+    let title = clean_track_name(track_name);
+    for artist in &artists {
+        match fetch_lyrics_search(&title, artist, duration_secs) {
+            LyricsStatus::NotFound => {}
+            status => return status,
         }
-        status => return status,
     }
+
+    LyricsStatus::NotFound
 }
 
 fn fetch_from_url(url: &str) -> LyricsStatus {
-    let response = match ureq::get(url)
+    let response = match net::agent()
+        .get(url)
         .set("User-Agent", "Phosphor/0.1.0")
         .call()
     {
@@ -175,14 +241,31 @@ fn fetch_from_url(url: &str) -> LyricsStatus {
     }
 }
 
-fn fetch_lyrics_search(track_name: &str, artist_name: &str) -> LyricsStatus {
+/// Drop versions with a very different length (live cuts, extended mixes) and
+/// put the closest first; results without a duration go last
+fn rank_by_duration(results: &mut Vec<LrcLibSearchResult>, duration_secs: u64) {
+    if duration_secs == 0 {
+        return;
+    }
+    let target = duration_secs as f64;
+    let distance = |r: &LrcLibSearchResult| r.duration.map(|d| (d - target).abs());
+    results.retain(|r| distance(r).is_none_or(|d| d <= DURATION_TOLERANCE_SECS));
+    results.sort_by(|a, b| {
+        let a = distance(a).unwrap_or(f64::MAX);
+        let b = distance(b).unwrap_or(f64::MAX);
+        a.total_cmp(&b)
+    });
+}
+
+fn fetch_lyrics_search(track_name: &str, artist_name: &str, duration_secs: u64) -> LyricsStatus {
     let url = format!(
         "https://lrclib.net/api/search?track_name={}&artist_name={}",
         urlencoding::encode(track_name),
         urlencoding::encode(artist_name),
     );
 
-    let response = match ureq::get(&url)
+    let response = match net::agent()
+        .get(&url)
         .set("User-Agent", "Phosphor/0.1.0")
         .call()
     {
@@ -191,21 +274,79 @@ fn fetch_lyrics_search(track_name: &str, artist_name: &str) -> LyricsStatus {
         Err(e) => return LyricsStatus::Error(e.to_string()),
     };
 
-    let results: Vec<LrcLibSearchResult> = match response.into_json() {
+    let mut results: Vec<LrcLibSearchResult> = match response.into_json() {
         Ok(j) => j,
         Err(e) => return LyricsStatus::Error(e.to_string()),
     };
 
+    rank_by_duration(&mut results, duration_secs);
+
     // Find first result with synced lyrics
     for result in results {
-        if let Some(lrc) = result.synced_lyrics {
-            if !lrc.trim().is_empty() {
-                if let Some(lyrics) = SyncedLyrics::parse(&lrc) {
-                    return LyricsStatus::Available(lyrics);
-                }
-            }
+        if let Some(lrc) = result.synced_lyrics
+            && !lrc.trim().is_empty()
+            && let Some(lyrics) = SyncedLyrics::parse(&lrc)
+        {
+            return LyricsStatus::Available(lyrics);
         }
     }
 
     LyricsStatus::NotFound
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(duration: Option<f64>) -> LrcLibSearchResult {
+        LrcLibSearchResult {
+            synced_lyrics: None,
+            duration,
+        }
+    }
+
+    #[test]
+    fn strips_remaster_suffixes() {
+        assert_eq!(clean_track_name("Here Comes the Sun - Remastered 2019"), "Here Comes the Sun");
+        assert_eq!(clean_track_name("Heroes - 2017 Remaster"), "Heroes");
+        assert_eq!(clean_track_name("Song (2011 Remaster)"), "Song");
+    }
+
+    #[test]
+    fn strips_featured_artists_and_live_tags() {
+        assert_eq!(clean_track_name("Song (feat. Someone)"), "Song");
+        assert_eq!(clean_track_name("Song (Live) [feat. Someone]"), "Song");
+        assert_eq!(clean_track_name("Song - Live at Wembley"), "Song");
+    }
+
+    #[test]
+    fn keeps_titles_that_are_only_decoration_lookalikes() {
+        assert_eq!(clean_track_name("Live Forever"), "Live Forever");
+        assert_eq!(clean_track_name("Song (Reprise)"), "Song (Reprise)");
+        assert_eq!(clean_track_name("(Live)"), "(Live)");
+        assert_eq!(clean_track_name("Me - Myself"), "Me - Myself");
+    }
+
+    #[test]
+    fn drops_versions_outside_the_duration_tolerance() {
+        let mut results = vec![result(Some(205.5)), result(Some(200.0)), result(Some(194.0)), result(None)];
+        rank_by_duration(&mut results, 200);
+        let durations: Vec<_> = results.iter().map(|r| r.duration).collect();
+        assert_eq!(durations, [Some(200.0), None]);
+    }
+
+    #[test]
+    fn prefers_the_closest_length() {
+        let mut results = vec![result(None), result(Some(204.0)), result(Some(199.0))];
+        rank_by_duration(&mut results, 200);
+        let durations: Vec<_> = results.iter().map(|r| r.duration).collect();
+        assert_eq!(durations, [Some(199.0), Some(204.0), None]);
+    }
+
+    #[test]
+    fn unknown_track_length_keeps_every_result() {
+        let mut results = vec![result(Some(30.0)), result(Some(600.0))];
+        rank_by_duration(&mut results, 0);
+        assert_eq!(results.len(), 2);
+    }
+}
@@ -1,4 +1,18 @@
 pub mod audio;
+pub mod clipboard;
+pub mod demo;
 pub mod git;
+pub mod github;
+pub mod listens;
 pub mod lyrics;
+pub mod mpd;
+#[cfg(feature = "mpris")]
+pub mod mpris;
+pub mod net;
+pub mod player;
+pub mod raw_output;
+pub mod scrobble;
+pub mod secrets;
 pub mod spotify;
+pub mod stats;
+pub mod volume;
@@ -0,0 +1,306 @@
+//! Music Player Daemon client speaking the plain-text protocol over TCP or a
+//! Unix socket
+
+use anyhow::{bail, Context, Result};
+use std::path::PathBuf;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+
+use crate::config::MpdConfig;
+use crate::modules::player::Player;
+use crate::modules::spotify::TrackInfo;
+
+type Reader = BufReader<Box<dyn AsyncRead + Send + Unpin>>;
+type Writer = Box<dyn AsyncWrite + Send + Unpin>;
+
+struct Connection {
+    reader: Reader,
+    writer: Writer,
+}
+
+/// Error reply (`ACK`) from the server; unlike I/O errors the connection stays usable
+#[derive(Debug)]
+struct Ack(String);
+
+impl std::fmt::Display for Ack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "MPD: {}", self.0)
+    }
+}
+
+impl std::error::Error for Ack {}
+
+/// Response to a command: `key: value` pairs plus the binary chunk, if any
+struct Response {
+    pairs: Vec<(String, String)>,
+    binary: Vec<u8>,
+}
+
+impl Response {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.pairs
+            .iter()
+            .find(|(k, _)| k.eq_ignore_ascii_case(key))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+pub struct MpdClient {
+    config: MpdConfig,
+    /// Reopened on the next command after any I/O error
+    connection: Mutex<Option<Connection>>,
+    /// Song file and the cover written for it, fetched once per song
+    cover: Mutex<Option<(String, Option<String>)>>,
+}
+
+impl MpdClient {
+    pub async fn connect(config: &MpdConfig) -> Result<Self> {
+        let client = Self {
+            config: config.clone(),
+            connection: Mutex::new(None),
+            cover: Mutex::new(None),
+        };
+        // Fail early if the server isn't there
+        client.command("ping").await?;
+        Ok(client)
+    }
+
+    async fn open(&self) -> Result<Connection> {
+        let host = &self.config.host;
+        let (read, write): (Box<dyn AsyncRead + Send + Unpin>, Writer) = if host.starts_with('/') {
+            #[cfg(unix)]
+            {
+                let stream = tokio::net::UnixStream::connect(host)
+                    .await
+                    .with_context(|| format!("Failed to connect to MPD at {}", host))?;
+                let (read, write) = stream.into_split();
+                (Box::new(read), Box::new(write))
+            }
+            #[cfg(not(unix))]
+            bail!("MPD sockets are only supported on Unix")
+        } else {
+            let stream = TcpStream::connect((host.as_str(), self.config.port))
+                .await
+                .with_context(|| format!("Failed to connect to MPD at {}:{}", host, self.config.port))?;
+            let (read, write) = stream.into_split();
+            (Box::new(read), Box::new(write))
+        };
+
+        let mut connection = Connection {
+            reader: BufReader::new(read),
+            writer: write,
+        };
+
+        let mut greeting = String::new();
+        connection.reader.read_line(&mut greeting).await?;
+        if !greeting.starts_with("OK MPD") {
+            bail!("Not an MPD server: {}", greeting.trim());
+        }
+
+        if !self.config.password.is_empty() {
+            let command = format!("password {}", quote(&self.config.password));
+            exchange(&mut connection, &command).await?;
+        }
+
+        Ok(connection)
+    }
+
+    async fn command(&self, command: &str) -> Result<Response> {
+        let mut guard = self.connection.lock().await;
+        if guard.is_none() {
+            *guard = Some(self.open().await?);
+        }
+
+        let connection = guard.as_mut().expect("connection was just opened");
+        match exchange(connection, command).await {
+            Ok(response) => Ok(response),
+            Err(e) => {
+                if e.downcast_ref::<Ack>().is_none() {
+                    *guard = None;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    /// `file://` URL of the song's cover, written to the temp dir on first request.
+    /// Tries the folder image first (`albumart`), then embedded art (`readpicture`)
+    async fn cover_url(&self, file: &str) -> Option<String> {
+        if let Some((cached_file, url)) = self.cover.lock().await.as_ref()
+            && cached_file == file
+        {
+            return url.clone();
+        }
+
+        let mut url = None;
+        for command in ["albumart", "readpicture"] {
+            if let Ok(bytes) = self.read_binary(command, file).await
+                && !bytes.is_empty()
+            {
+                url = write_cover(file, &bytes).ok();
+                break;
+            }
+        }
+
+        *self.cover.lock().await = Some((file.to_string(), url.clone()));
+        url
+    }
+
+    /// Binary responses arrive in chunks; keep asking from the next offset
+    async fn read_binary(&self, command: &str, file: &str) -> Result<Vec<u8>> {
+        let mut data = Vec::new();
+        loop {
+            let response = self
+                .command(&format!("{} {} {}", command, quote(file), data.len()))
+                .await?;
+            let size: usize = response.get("size").and_then(|s| s.parse().ok()).unwrap_or(0);
+            if response.binary.is_empty() {
+                break;
+            }
+            data.extend_from_slice(&response.binary);
+            if data.len() >= size {
+                break;
+            }
+        }
+        Ok(data)
+    }
+}
+
+impl Player for MpdClient {
+    fn name(&self) -> &'static str {
+        "mpd"
+    }
+
+    async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
+        let status = self.command("status").await?;
+        let is_playing = match status.get("state") {
+            Some("play") => true,
+            Some("pause") => false,
+            _ => return Ok(None),
+        };
+
+        let song = self.command("currentsong").await?;
+        let Some(file) = song.get("file").map(str::to_string) else {
+            return Ok(None);
+        };
+
+        let seconds = |value: Option<&str>| {
+            value
+                .and_then(|v| v.parse::<f64>().ok())
+                .map(|secs| (secs * 1000.0) as u64)
+        };
+        // Untagged files fall back to their file name
+        let name = song.get("Title").map(str::to_string).unwrap_or_else(|| {
+            std::path::Path::new(&file)
+                .file_stem()
+                .map_or_else(|| file.clone(), |stem| stem.to_string_lossy().into_owned())
+        });
+
+        Ok(Some(TrackInfo {
+            name,
+            artist: song.get("Artist").unwrap_or_default().to_string(),
+            album: song.get("Album").unwrap_or_default().to_string(),
+            duration: seconds(status.get("duration").or(song.get("duration"))).unwrap_or(0),
+            progress: seconds(status.get("elapsed")),
+            is_playing,
+            album_art_url: self.cover_url(&file).await,
+            is_episode: false,
+            uri: None,
+            saved: None,
+            context: None,
+            device: None,
+        }))
+    }
+
+    async fn play(&self) -> Result<()> {
+        self.command("play").await.map(drop)
+    }
+
+    async fn pause(&self) -> Result<()> {
+        self.command("pause 1").await.map(drop)
+    }
+
+    async fn next(&self) -> Result<()> {
+        self.command("next").await.map(drop)
+    }
+
+    async fn prev(&self) -> Result<()> {
+        self.command("previous").await.map(drop)
+    }
+
+    async fn set_volume(&self, volume: u8) -> Result<()> {
+        self.command(&format!("setvol {}", volume.min(100))).await.map(drop)
+    }
+
+    async fn toggle_playback(&self) -> Result<()> {
+        let status = self.command("status").await?;
+        match status.get("state") {
+            Some("play") => self.pause().await,
+            _ => self.play().await,
+        }
+    }
+
+    async fn seek(&self, position_ms: u64) -> Result<()> {
+        self.command(&format!("seekcur {:.3}", position_ms as f64 / 1000.0))
+            .await
+            .map(drop)
+    }
+}
+
+/// Send one command and read its response up to `OK` or `ACK`
+async fn exchange(connection: &mut Connection, command: &str) -> Result<Response> {
+    connection
+        .writer
+        .write_all(format!("{}\n", command).as_bytes())
+        .await?;
+
+    let mut response = Response {
+        pairs: Vec::new(),
+        binary: Vec::new(),
+    };
+    loop {
+        let mut line = String::new();
+        if connection.reader.read_line(&mut line).await? == 0 {
+            bail!("MPD closed the connection");
+        }
+        let line = line.trim_end_matches('\n');
+
+        if line == "OK" {
+            return Ok(response);
+        }
+        if let Some(error) = line.strip_prefix("ACK ") {
+            // ACK [error@command_listNum] {current_command} message_text
+            let message = error.rsplit_once("} ").map_or(error, |(_, msg)| msg);
+            return Err(Ack(message.to_string()).into());
+        }
+
+        let Some((key, value)) = line.split_once(": ") else {
+            continue;
+        };
+        if key == "binary" {
+            let len: usize = value.parse().context("Bad binary length from MPD")?;
+            let mut chunk = vec![0; len];
+            connection.reader.read_exact(&mut chunk).await?;
+            // The chunk is followed by a newline
+            let mut newline = [0; 1];
+            connection.reader.read_exact(&mut newline).await?;
+            response.binary = chunk;
+        } else {
+            response.pairs.push((key.to_string(), value.to_string()));
+        }
+    }
+}
+
+/// Quote an argument, escaping backslashes and double quotes
+fn quote(arg: &str) -> String {
+    format!("\"{}\"", arg.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_cover(file: &str, bytes: &[u8]) -> Result<String> {
+    let dir: PathBuf = std::env::temp_dir().join("phosphor-mpd-art");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{:x}", md5::compute(file)));
+    std::fs::write(&path, bytes)?;
+    Ok(format!("file://{}", path.display()))
+}
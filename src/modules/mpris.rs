@@ -0,0 +1,183 @@
+//! Local players over MPRIS/D-Bus (spotifyd, mpv, browsers), used when the
+//! Spotify Web API isn't available
+
+use anyhow::{anyhow, Context, Result};
+use mpris::{PlaybackStatus, Player, PlayerFinder};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use crate::modules::spotify::TrackInfo;
+
+enum Command {
+    TogglePlayback,
+    Play,
+    Pause,
+    Next,
+    Prev,
+    SetVolume(u8),
+    Seek(u64),
+}
+
+enum Request {
+    Track(oneshot::Sender<Result<Option<TrackInfo>>>),
+    Control(Command, oneshot::Sender<Result<()>>),
+}
+
+/// Handle to a worker thread that owns the D-Bus connection, since mpris
+/// players can't be shared across threads
+pub struct MprisClient {
+    tx: mpsc::Sender<Request>,
+}
+
+impl MprisClient {
+    /// Connect to the session bus. Succeeds even if no player is running yet,
+    /// the most active one is picked on every request.
+    pub fn connect() -> Result<Self> {
+        let (tx, rx) = mpsc::channel::<Request>();
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<()>>();
+
+        thread::spawn(move || {
+            let finder = match PlayerFinder::new() {
+                Ok(finder) => {
+                    let _ = ready_tx.send(Ok(()));
+                    finder
+                }
+                Err(e) => {
+                    let _ = ready_tx.send(Err(anyhow!("Failed to connect to D-Bus: {}", e)));
+                    return;
+                }
+            };
+
+            while let Ok(request) = rx.recv() {
+                // No player is not an error for polling, only for controls
+                let player = finder.find_active().ok();
+                match request {
+                    Request::Track(reply) => {
+                        let _ = reply.send(Ok(player.as_ref().and_then(track_info)));
+                    }
+                    Request::Control(command, reply) => {
+                        let result = match player {
+                            Some(player) => control(&player, command),
+                            None => Err(anyhow!("No MPRIS player running")),
+                        };
+                        let _ = reply.send(result);
+                    }
+                }
+            }
+        });
+
+        ready_rx
+            .recv()
+            .context("MPRIS worker exited during startup")??;
+
+        Ok(Self { tx })
+    }
+
+    async fn control(&self, command: Command) -> Result<()> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(Request::Control(command, reply))
+            .map_err(|_| anyhow!("MPRIS worker stopped"))?;
+        rx.await.context("MPRIS worker stopped")?
+    }
+}
+
+// Named in full, `Player` here is the mpris crate's
+impl crate::modules::player::Player for MprisClient {
+    fn name(&self) -> &'static str {
+        "mpris"
+    }
+
+    async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
+        let (reply, rx) = oneshot::channel();
+        self.tx
+            .send(Request::Track(reply))
+            .map_err(|_| anyhow!("MPRIS worker stopped"))?;
+        rx.await.context("MPRIS worker stopped")?
+    }
+
+    async fn play(&self) -> Result<()> {
+        self.control(Command::Play).await
+    }
+
+    async fn pause(&self) -> Result<()> {
+        self.control(Command::Pause).await
+    }
+
+    async fn next(&self) -> Result<()> {
+        self.control(Command::Next).await
+    }
+
+    async fn prev(&self) -> Result<()> {
+        self.control(Command::Prev).await
+    }
+
+    async fn set_volume(&self, volume: u8) -> Result<()> {
+        self.control(Command::SetVolume(volume)).await
+    }
+
+    async fn toggle_playback(&self) -> Result<()> {
+        self.control(Command::TogglePlayback).await
+    }
+
+    async fn seek(&self, position_ms: u64) -> Result<()> {
+        self.control(Command::Seek(position_ms)).await
+    }
+}
+
+fn control(player: &Player, command: Command) -> Result<()> {
+    match command {
+        Command::TogglePlayback => player.play_pause(),
+        Command::Play => player.play(),
+        Command::Pause => player.pause(),
+        Command::Next => player.next(),
+        Command::Prev => player.previous(),
+        // MPRIS volume is 0.0-1.0
+        Command::SetVolume(volume) => player.set_volume(volume.min(100) as f64 / 100.0),
+        Command::Seek(position_ms) => seek(player, Duration::from_millis(position_ms)),
+    }
+    .with_context(|| format!("{} rejected the command", player.identity()))
+}
+
+/// Absolute seeks need the track id; players without one only support relative seeks
+fn seek(player: &Player, position: Duration) -> Result<(), mpris::DBusError> {
+    match player.get_metadata()?.track_id() {
+        Some(track_id) => player.set_position(track_id, &position),
+        None => {
+            let current = player.get_position()?;
+            let offset = position.as_micros() as i64 - current.as_micros() as i64;
+            player.seek(offset)
+        }
+    }
+}
+
+fn track_info(player: &Player) -> Option<TrackInfo> {
+    let status = player.get_playback_status().ok()?;
+    if status == PlaybackStatus::Stopped {
+        return None;
+    }
+
+    let metadata = player.get_metadata().ok()?;
+    let name = metadata.title().filter(|t| !t.is_empty())?.to_string();
+
+    Some(TrackInfo {
+        name,
+        artist: metadata
+            .artists()
+            .map(|artists| artists.join(", "))
+            .unwrap_or_default(),
+        album: metadata.album_name().unwrap_or_default().to_string(),
+        duration: metadata.length().map_or(0, |d| d.as_millis() as u64),
+        // Some players (e.g. browsers) don't report a position
+        progress: player.get_position().ok().map(|d| d.as_millis() as u64),
+        is_playing: status == PlaybackStatus::Playing,
+        album_art_url: metadata.art_url().map(|url| url.to_string()),
+        is_episode: false,
+        uri: None,
+        saved: None,
+        context: None,
+        device: Some(player.identity().to_string()),
+    })
+}
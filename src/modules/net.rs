@@ -0,0 +1,63 @@
+//! Shared HTTP setup so every outbound request honors the same proxy settings
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use crate::config::Config;
+
+/// Fail fast on stalled connections instead of freezing the fetch for minutes
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Checked in order; HTTPS first since all our endpoints are https
+const PROXY_VARS: [&str; 6] = [
+    "HTTPS_PROXY",
+    "https_proxy",
+    "HTTP_PROXY",
+    "http_proxy",
+    "ALL_PROXY",
+    "all_proxy",
+];
+
+fn env_proxy() -> Option<String> {
+    PROXY_VARS
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+}
+
+/// Export `[network] proxy` as `HTTPS_PROXY`/`HTTP_PROXY`, since rspotify's
+/// reqwest client only reads proxies from the environment.
+///
+/// Must run before any threads are spawned.
+pub fn export_proxy_env() {
+    // Don't create a config file just to look for a proxy
+    if !Config::path().exists() {
+        return;
+    }
+    let Ok(config) = Config::load() else {
+        return;
+    };
+    if config.network.proxy.is_empty() {
+        return;
+    }
+
+    for var in ["HTTPS_PROXY", "HTTP_PROXY"] {
+        // SAFETY: called at the top of main, before the runtime starts other threads
+        unsafe { std::env::set_var(var, &config.network.proxy) };
+    }
+}
+
+/// Agent for lyrics and album art requests
+pub fn agent() -> &'static ureq::Agent {
+    static AGENT: OnceLock<ureq::Agent> = OnceLock::new();
+
+    AGENT.get_or_init(|| {
+        let mut builder = ureq::AgentBuilder::new()
+            .timeout_connect(CONNECT_TIMEOUT)
+            .timeout_read(READ_TIMEOUT);
+        if let Some(proxy) = env_proxy().and_then(|url| ureq::Proxy::new(url).ok()) {
+            builder = builder.proxy(proxy);
+        }
+        builder.build()
+    })
+}
@@ -0,0 +1,112 @@
+//! Now-playing and transport controls shared by every playback backend
+
+use anyhow::{bail, Result};
+use std::future::Future;
+
+use crate::modules::spotify::{DeviceInfo, HistoryItem, QueueItem, Radio, SearchItem, TrackInfo};
+
+/// A source of track info that can be controlled (Spotify, MPRIS, MPD, the
+/// demo). The TUI and CLI only go through this for playback, so a new backend
+/// is an impl of it: the transport controls are required, and the rest default
+/// to an error for backends without a queue, library, search or devices
+pub trait Player: Send + Sync {
+    /// Short name shown in the UI, e.g. "spotify"
+    fn name(&self) -> &'static str;
+
+    /// The current track, or None when nothing is playing
+    fn get_current_track(&self) -> impl Future<Output = Result<Option<TrackInfo>>> + Send;
+
+    fn play(&self) -> impl Future<Output = Result<()>> + Send;
+
+    fn pause(&self) -> impl Future<Output = Result<()>> + Send;
+
+    fn next(&self) -> impl Future<Output = Result<()>> + Send;
+
+    fn prev(&self) -> impl Future<Output = Result<()>> + Send;
+
+    /// Jump to an absolute position in the current track
+    fn seek(&self, position_ms: u64) -> impl Future<Output = Result<()>> + Send;
+
+    /// Volume in percent (0-100)
+    fn set_volume(&self, volume: u8) -> impl Future<Output = Result<()>> + Send;
+
+    /// Pause when playing, resume otherwise. Backends with a native toggle override this
+    fn toggle_playback(&self) -> impl Future<Output = Result<()>> + Send {
+        async move {
+            match self.get_current_track().await? {
+                Some(track) if track.is_playing => self.pause().await,
+                Some(_) => self.play().await,
+                None => Ok(()),
+            }
+        }
+    }
+
+    /// Upcoming items, not including the current track
+    fn get_queue(&self) -> impl Future<Output = Result<Vec<QueueItem>>> + Send {
+        async move { bail!("{} has no queue", self.name()) }
+    }
+
+    /// The last `limit` played tracks, newest first
+    fn recently_played(&self, limit: u32) -> impl Future<Output = Result<Vec<HistoryItem>>> + Send {
+        let _ = limit;
+        async move { bail!("{} has no listening history", self.name()) }
+    }
+
+    /// Whether a track is in the user's library
+    fn is_saved(&self, uri: &str) -> impl Future<Output = Result<bool>> + Send {
+        let _ = uri;
+        async move { bail!("{} has no library", self.name()) }
+    }
+
+    /// Add a track to the user's library, or remove it
+    fn set_saved(&self, uri: &str, saved: bool) -> impl Future<Output = Result<()>> + Send {
+        let _ = (uri, saved);
+        async move { bail!("{} has no library", self.name()) }
+    }
+
+    /// Tracks, then albums, then playlists matching `query`
+    fn search(&self, query: &str) -> impl Future<Output = Result<Vec<SearchItem>>> + Send {
+        let _ = query;
+        async move { bail!("{} can't search", self.name()) }
+    }
+
+    /// Start playing a track, episode, album or playlist URI
+    fn play_uri(&self, uri: &str) -> impl Future<Output = Result<()>> + Send {
+        let _ = uri;
+        async move { bail!("{} can't play a URI", self.name()) }
+    }
+
+    /// Play these tracks or episodes in order, in place of the current context
+    fn play_uris(&self, uris: &[&str]) -> impl Future<Output = Result<()>> + Send {
+        let _ = uris;
+        async move { bail!("{} can't play a URI", self.name()) }
+    }
+
+    /// Play `uri` from within the album or playlist `context_uri`, carrying on
+    /// through the rest of it
+    fn play_in_context(&self, context_uri: &str, uri: &str) -> impl Future<Output = Result<()>> + Send {
+        let _ = (context_uri, uri);
+        async move { bail!("{} can't play a URI", self.name()) }
+    }
+
+    /// Replace playback with recommendations seeded from the current track
+    fn start_radio(&self) -> impl Future<Output = Result<Radio>> + Send {
+        async move { bail!("{} has no radio", self.name()) }
+    }
+
+    /// Devices that can take over playback
+    fn list_devices(&self) -> impl Future<Output = Result<Vec<DeviceInfo>>> + Send {
+        async move { bail!("{} has no devices to switch between", self.name()) }
+    }
+
+    /// Move playback to another device, keeping it playing
+    fn transfer_playback(&self, device_id: &str) -> impl Future<Output = Result<()>> + Send {
+        let _ = device_id;
+        async move { bail!("{} has no devices to switch between", self.name()) }
+    }
+
+    /// Store refreshed credentials, returning whether the keyring holds them
+    fn persist_token(&self) -> impl Future<Output = bool> + Send {
+        async { false }
+    }
+}
@@ -0,0 +1,147 @@
+//! Bar levels for other programs (LED strips, OBS scripts, ...) in cava's raw
+//! output format, written to a FIFO or sent over UDP every frame
+
+use anyhow::{bail, Context, Result};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::net::{SocketAddr, ToSocketAddrs, UdpSocket};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::time::Duration;
+
+use crate::config::OutputConfig;
+
+/// Frames waiting for the writer; newer ones are dropped while it's stuck
+const QUEUED_FRAMES: usize = 4;
+
+/// How each frame is encoded, following cava's `data_format` / `bit_format`
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    /// Decimal values from 0 to `max`, each followed by ';', one frame per line
+    Ascii { max: u32 },
+    /// One byte per bar
+    Bits8,
+    /// Two bytes per bar, little-endian
+    Bits16,
+}
+
+impl Format {
+    /// Config names
+    const NAMES: [&'static str; 3] = ["ascii", "8bit", "16bit"];
+
+    fn encode(self, levels: &[f32]) -> Vec<u8> {
+        let scaled = |max: f32| levels.iter().map(move |level| (level.clamp(0.0, 1.0) * max).round());
+        match self {
+            Format::Ascii { max } => {
+                let mut line: String = scaled(max as f32).map(|value| format!("{};", value)).collect();
+                line.push('\n');
+                line.into_bytes()
+            }
+            Format::Bits8 => scaled(u8::MAX as f32).map(|value| value as u8).collect(),
+            Format::Bits16 => scaled(u16::MAX as f32)
+                .flat_map(|value| (value as u16).to_le_bytes())
+                .collect(),
+        }
+    }
+}
+
+enum Target {
+    Fifo(String),
+    Udp { socket: UdpSocket, address: SocketAddr },
+}
+
+/// Sends each frame to a writer thread, so a FIFO without a reader never
+/// holds up rendering
+pub struct RawOutput {
+    tx: SyncSender<Vec<u8>>,
+    format: Format,
+    bars: usize,
+}
+
+impl RawOutput {
+    /// None when `audio.output.target` is empty
+    pub fn from_config(config: &OutputConfig) -> Result<Option<Self>> {
+        let target = config.target.trim();
+        if target.is_empty() {
+            return Ok(None);
+        }
+
+        let format = match config.format.trim().to_lowercase().as_str() {
+            "ascii" => Format::Ascii { max: config.ascii_max },
+            "8bit" => Format::Bits8,
+            "16bit" => Format::Bits16,
+            other => bail!(
+                "Unknown audio.output.format '{}' (expected one of: {})",
+                other,
+                Format::NAMES.join(", ")
+            ),
+        };
+        if config.bars == 0 {
+            bail!("audio.output.bars must be at least 1");
+        }
+
+        let target = match target.strip_prefix("udp://") {
+            Some(address) => {
+                let address = address
+                    .to_socket_addrs()
+                    .ok()
+                    .and_then(|mut addresses| addresses.next())
+                    .with_context(|| format!("audio.output.target: can't resolve {}", address))?;
+                let socket = UdpSocket::bind("0.0.0.0:0").context("Failed to open a UDP socket")?;
+                Target::Udp { socket, address }
+            }
+            None => {
+                let path = shellexpand::tilde(target).into_owned();
+                if !std::path::Path::new(&path).exists() {
+                    bail!("audio.output.target {} doesn't exist (create it with `mkfifo {}`)", path, path);
+                }
+                Target::Fifo(path)
+            }
+        };
+        let (tx, rx) = mpsc::sync_channel(QUEUED_FRAMES);
+        std::thread::spawn(move || match target {
+            Target::Udp { socket, address } => send_udp(&socket, address, rx),
+            Target::Fifo(path) => write_fifo(&path, rx),
+        });
+
+        Ok(Some(Self { tx, format, bars: config.bars }))
+    }
+
+    /// Bars per frame
+    pub fn bars(&self) -> usize {
+        self.bars
+    }
+
+    /// Queue a frame of bar levels (0-1)
+    pub fn send(&self, levels: &[f32]) {
+        let _ = self.tx.try_send(self.format.encode(levels));
+    }
+}
+
+fn send_udp(socket: &UdpSocket, address: SocketAddr, rx: Receiver<Vec<u8>>) {
+    for frame in rx {
+        // Nobody listening is fine; the next frame tries again
+        let _ = socket.send_to(&frame, address);
+    }
+}
+
+/// Opening a FIFO waits for a reader, and writes fail once it goes away;
+/// either way, start over and wait for the next one
+fn write_fifo(path: &str, rx: Receiver<Vec<u8>>) {
+    loop {
+        let Ok(mut fifo) = OpenOptions::new().write(true).open(path) else {
+            std::thread::sleep(Duration::from_secs(1));
+            continue;
+        };
+        // Frames queued while no one was reading are stale
+        while rx.try_recv().is_ok() {}
+
+        loop {
+            let Ok(frame) = rx.recv() else {
+                return;
+            };
+            if fifo.write_all(&frame).and_then(|()| fifo.flush()).is_err() {
+                break;
+            }
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! Scrobbling to Last.fm and ListenBrainz
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde_json::{json, Value};
+use std::io::Write;
+
+use crate::config::ScrobbleConfig;
+use crate::modules::listens::{Listen, ListenEvent};
+use crate::modules::net;
+
+const LASTFM_API: &str = "https://ws.audioscrobbler.com/2.0/";
+const LISTENBRAINZ_API: &str = "https://api.listenbrainz.org/1/submit-listens";
+
+/// Submits "now playing" updates and scrobbles for the listens the poller reports
+pub struct Scrobbler {
+    config: ScrobbleConfig,
+}
+
+impl Scrobbler {
+    /// None when no service is configured
+    pub fn new(config: &ScrobbleConfig) -> Option<Self> {
+        (lastfm_enabled(config) || !config.listenbrainz_token.is_empty())
+            .then(|| Self { config: config.clone() })
+    }
+
+    /// Submissions run on the blocking pool
+    pub fn handle(&self, event: &ListenEvent) {
+        let (listen, scrobble) = match event {
+            ListenEvent::Started(listen) => (listen.clone(), false),
+            ListenEvent::Played(listen) => (listen.clone(), true),
+        };
+        let config = self.config.clone();
+        tokio::task::spawn_blocking(move || submit(&config, &listen, scrobble));
+    }
+}
+
+fn lastfm_enabled(config: &ScrobbleConfig) -> bool {
+    !config.lastfm_api_key.is_empty()
+        && !config.lastfm_api_secret.is_empty()
+        && !config.lastfm_session_key.is_empty()
+}
+
+/// Send to every configured service; failures are dropped, the dashboard has
+/// nowhere to report them
+fn submit(config: &ScrobbleConfig, listen: &Listen, scrobble: bool) {
+    if lastfm_enabled(config) {
+        let _ = lastfm_submit(config, listen, scrobble);
+    }
+    if !config.listenbrainz_token.is_empty() {
+        let _ = listenbrainz_submit(&config.listenbrainz_token, listen, scrobble);
+    }
+}
+
+fn lastfm_submit(config: &ScrobbleConfig, listen: &Listen, scrobble: bool) -> Result<()> {
+    let mut params = vec![
+        ("artist", listen.artist.clone()),
+        ("track", listen.track.clone()),
+        ("duration", listen.duration.as_secs().to_string()),
+        ("sk", config.lastfm_session_key.clone()),
+    ];
+    if !listen.album.is_empty() {
+        params.push(("album", listen.album.clone()));
+    }
+    if scrobble {
+        params.push(("method", "track.scrobble".to_string()));
+        params.push(("timestamp", listen.started_at.to_string()));
+    } else {
+        params.push(("method", "track.updateNowPlaying".to_string()));
+    }
+
+    lastfm_call(config, params)?;
+    Ok(())
+}
+
+/// Signed Last.fm API call: `api_sig` is the md5 of the sorted parameters
+/// followed by the shared secret
+fn lastfm_call(config: &ScrobbleConfig, mut params: Vec<(&str, String)>) -> Result<Value> {
+    params.push(("api_key", config.lastfm_api_key.clone()));
+    params.sort_by(|a, b| a.0.cmp(b.0));
+
+    let mut payload: String = params.iter().map(|(k, v)| format!("{}{}", k, v)).collect();
+    payload.push_str(&config.lastfm_api_secret);
+    let signature = format!("{:x}", md5::compute(payload));
+
+    params.push(("api_sig", signature));
+    params.push(("format", "json".to_string()));
+
+    let form: Vec<(&str, &str)> = params.iter().map(|(k, v)| (*k, v.as_str())).collect();
+    let response = match net::agent().post(LASTFM_API).send_form(&form) {
+        Ok(response) => response,
+        // Errors come back as JSON with a message
+        Err(ureq::Error::Status(_, response)) => response,
+        Err(e) => return Err(e.into()),
+    };
+
+    let body: Value = response.into_json()?;
+    if let Some(message) = body.get("message").and_then(Value::as_str)
+        && body.get("error").is_some()
+    {
+        bail!("Last.fm: {}", message);
+    }
+    Ok(body)
+}
+
+fn listenbrainz_submit(token: &str, listen: &Listen, scrobble: bool) -> Result<()> {
+    let mut additional_info = json!({
+        "duration_ms": listen.duration.as_millis() as u64,
+        "media_player": "Spotify",
+        "submission_client": "phosphor",
+        "submission_client_version": env!("CARGO_PKG_VERSION"),
+    });
+    if let Some(id) = listen.uri.as_deref().and_then(|uri| uri.strip_prefix("spotify:track:")) {
+        additional_info["spotify_id"] = json!(format!("https://open.spotify.com/track/{}", id));
+    }
+
+    let mut track_metadata = json!({
+        "artist_name": listen.artist,
+        "track_name": listen.track,
+        "additional_info": additional_info,
+    });
+    if !listen.album.is_empty() {
+        track_metadata["release_name"] = json!(listen.album);
+    }
+
+    let mut entry = json!({ "track_metadata": track_metadata });
+    let listen_type = if scrobble {
+        entry["listened_at"] = json!(listen.started_at);
+        "single"
+    } else {
+        "playing_now"
+    };
+
+    net::agent()
+        .post(LISTENBRAINZ_API)
+        .set("Authorization", &format!("Token {}", token))
+        .send_json(json!({ "listen_type": listen_type, "payload": [entry] }))
+        .context("ListenBrainz rejected the listen")?;
+    Ok(())
+}
+
+/// Run Last.fm's desktop authorization and return `(username, session key)`
+pub fn lastfm_login(config: &ScrobbleConfig) -> Result<(String, String)> {
+    if config.lastfm_api_key.is_empty() || config.lastfm_api_secret.is_empty() {
+        bail!("Set lastfm_api_key and lastfm_api_secret under [scrobble] first");
+    }
+
+    let body = lastfm_call(config, vec![("method", "auth.getToken".to_string())])?;
+    let token = body
+        .get("token")
+        .and_then(Value::as_str)
+        .context("Last.fm returned no token")?
+        .to_string();
+
+    let url = format!(
+        "https://www.last.fm/api/auth/?api_key={}&token={}",
+        config.lastfm_api_key, token
+    );
+    if open::that(&url).is_err() {
+        println!("Open this URL in your browser:");
+    }
+    println!("  {}\n", url);
+    print!("Press Enter after allowing access: ");
+    std::io::stdout().flush()?;
+    std::io::stdin().read_line(&mut String::new())?;
+
+    let body = lastfm_call(
+        config,
+        vec![("method", "auth.getSession".to_string()), ("token", token)],
+    )?;
+    let session = body.get("session").context("Last.fm returned no session")?;
+    let field = |name: &str| {
+        session
+            .get(name)
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| anyhow!("Last.fm session has no {}", name))
+    };
+
+    Ok((field("name")?, field("key")?))
+}
@@ -0,0 +1,65 @@
+//! Credentials in the OS keyring (feature `keyring`), with no-op fallbacks so
+//! callers can keep using plain files when it is disabled or unavailable.
+
+#[cfg(feature = "keyring")]
+const SERVICE: &str = "phosphor";
+
+/// Keyring entry holding a user-provided Spotify client id
+pub const SPOTIFY_CLIENT_ID: &str = "spotify-client-id";
+/// Keyring entry holding the cached Spotify OAuth token (JSON)
+pub const SPOTIFY_TOKEN: &str = "spotify-token";
+
+/// Whether the keyring can be used, i.e. `key` can be read or simply doesn't exist yet
+#[cfg(feature = "keyring")]
+pub fn available(key: &str) -> bool {
+    match keyring::Entry::new(SERVICE, key) {
+        Ok(entry) => matches!(entry.get_password(), Ok(_) | Err(keyring::Error::NoEntry)),
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn available(_key: &str) -> bool {
+    false
+}
+
+/// Read a secret, or None if it is missing or the keyring is unavailable
+#[cfg(feature = "keyring")]
+pub fn get(key: &str) -> Option<String> {
+    keyring::Entry::new(SERVICE, key)
+        .ok()?
+        .get_password()
+        .ok()
+        .filter(|value| !value.is_empty())
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn get(_key: &str) -> Option<String> {
+    None
+}
+
+/// Store a secret, returning whether it was saved
+#[cfg(feature = "keyring")]
+pub fn set(key: &str, value: &str) -> bool {
+    keyring::Entry::new(SERVICE, key)
+        .and_then(|entry| entry.set_password(value))
+        .is_ok()
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn set(_key: &str, _value: &str) -> bool {
+    false
+}
+
+/// Remove a secret, returning whether one was deleted
+#[cfg(feature = "keyring")]
+pub fn delete(key: &str) -> bool {
+    keyring::Entry::new(SERVICE, key)
+        .and_then(|entry| entry.delete_credential())
+        .is_ok()
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn delete(_key: &str) -> bool {
+    false
+}
@@ -1,14 +1,27 @@
-use anyhow::{Context, Result};
+use anyhow::{anyhow, bail, Context, Result};
 use rspotify::{
-    model::{AdditionalType, PlayableItem},
+    model::{
+        AdditionalType, AlbumId, ArtistId, EpisodeId, Market, Offset, PlayContextId, PlayableId,
+        PlayableItem, PlaylistId, RecommendationsAttribute, SearchResult, SearchType, SimplifiedArtist, TrackId, Type,
+    },
     prelude::*,
-    scopes, AuthCodePkceSpotify, Credentials, OAuth,
+    scopes, AuthCodePkceSpotify, Credentials, OAuth, Token,
 };
 use std::io::{BufRead, BufReader, Write};
 use std::net::TcpListener;
 use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use crate::config::Config;
+use crate::modules::mpd::MpdClient;
+#[cfg(feature = "mpris")]
+use crate::modules::mpris::MprisClient;
+use crate::modules::player::Player;
+use crate::modules::secrets;
+
+/// Tracks requested for a radio station (the API maximum)
+const RADIO_SIZE: u32 = 100;
 
 const DEFAULT_CLIENT_ID: &str = "1f14edc73f6548dc97f7791dfec833aa";
 
@@ -22,18 +35,583 @@ pub struct TrackInfo {
     pub progress: Option<u64>,
     pub is_playing: bool,
     pub album_art_url: Option<String>,
+    pub is_episode: bool,
+    /// Spotify URI, when the backend knows it
+    pub uri: Option<String>,
+    /// In the user's library; None until checked or for episodes and MPRIS
+    pub saved: Option<bool>,
+    /// Playlist, album or artist the track is playing from
+    pub context: Option<PlaybackContext>,
+    /// Where it's playing: the Spotify Connect device or MPRIS player
+    pub device: Option<String>,
+}
+
+impl TrackInfo {
+    /// open.spotify.com link to the track or episode
+    pub fn web_url(&self) -> Option<String> {
+        self.uri.as_deref().and_then(web_url)
+    }
+}
+
+/// Turn `spotify:track:ID` (or album, episode, ...) into its open.spotify.com link
+pub fn web_url(uri: &str) -> Option<String> {
+    let mut parts = uri.strip_prefix("spotify:")?.splitn(2, ':');
+    let kind = parts.next()?;
+    let id = parts.next().filter(|id| !id.is_empty() && !id.contains(':'))?;
+    Some(format!("https://open.spotify.com/{}/{}", kind, id))
+}
+
+/// Where playback comes from, e.g. a playlist
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlaybackContext {
+    /// "playlist", "album", "artist" or "library"
+    pub kind: &'static str,
+    /// Empty when the name couldn't be looked up
+    pub name: String,
+    /// e.g. `spotify:playlist:ID`, for playing on from another of its tracks
+    pub uri: String,
+}
+
+impl PlaybackContext {
+    /// `Chill Vibes (playlist)`, or just the kind without a name
+    pub fn label(&self) -> String {
+        if self.name.is_empty() {
+            self.kind.to_string()
+        } else {
+            format!("{} ({})", self.name, self.kind)
+        }
+    }
+}
+
+/// Station started by `start_radio`
+#[derive(Debug, Clone)]
+pub struct Radio {
+    /// `track - artist` the station was seeded from
+    pub seed: String,
+    pub tracks: usize,
+}
+
+/// An upcoming item in the Spotify queue
+#[derive(Debug, Clone)]
+pub struct QueueItem {
+    pub name: String,
+    pub artist: String,
+    pub duration: u64,
+    /// Spotify URI, empty for local files
+    pub uri: String,
+}
+
+/// What a search result starts playing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchKind {
+    Track,
+    Album,
+    Playlist,
+}
+
+/// A track, album or playlist returned by [`SpotifyClient::search`]
+#[derive(Debug, Clone)]
+pub struct SearchItem {
+    pub kind: SearchKind,
+    pub name: String,
+    /// Artists, or the playlist owner
+    pub detail: String,
+    pub uri: String,
+}
+
+/// A recently played track
+#[derive(Debug, Clone)]
+pub struct HistoryItem {
+    pub name: String,
+    pub artist: String,
+    pub uri: String,
+    pub played_at: SystemTime,
+}
+
+/// A Spotify Connect device that playback can be moved to
+#[derive(Debug, Clone)]
+pub struct DeviceInfo {
+    pub id: String,
+    pub name: String,
+    /// Device type as reported by Spotify (Computer, Smartphone, Speaker, ...)
+    pub kind: String,
+    pub is_active: bool,
+    pub volume: Option<u32>,
+}
+
+/// Results fetched per kind, so each list stays short enough to scan
+const SEARCH_LIMIT: u32 = 8;
+
+/// Format a playback position as `MM:SS`, or `H:MM:SS` when `with_hours` is set
+/// (pass the same flag for position and duration so their widths match)
+pub fn format_time(ms: u64, with_hours: bool) -> String {
+    let secs = ms / 1000;
+    if with_hours {
+        format!("{}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Parse a position typed as `SS`, `MM:SS` or `H:MM:SS` into milliseconds
+pub fn parse_time(input: &str) -> Option<u64> {
+    let mut secs: u64 = 0;
+    let parts: Vec<&str> = input.trim().split(':').collect();
+    if parts.len() > 3 {
+        return None;
+    }
+    for (i, part) in parts.iter().enumerate() {
+        let value: u64 = part.parse().ok()?;
+        // Only the leading field may exceed a minute
+        if i > 0 && value >= 60 {
+            return None;
+        }
+        secs = secs * 60 + value;
+    }
+    Some(secs * 1000)
+}
+
+/// Parse a timer length like `30m`, `1h30m` or `90s`; a bare number is minutes
+pub fn parse_duration(input: &str) -> Option<Duration> {
+    let input = input.trim();
+    if let Ok(minutes) = input.parse::<u64>() {
+        return Some(Duration::from_secs(minutes * 60));
+    }
+
+    let mut secs: u64 = 0;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let value: u64 = digits.parse().ok()?;
+        digits.clear();
+        secs += value
+            * match c {
+                'h' => 3_600,
+                'm' => 60,
+                's' => 1,
+                _ => return None,
+            };
+    }
+    // Trailing digits without a unit
+    if !digits.is_empty() || secs == 0 {
+        return None;
+    }
+    Some(Duration::from_secs(secs))
+}
+
+/// How long ago `time` was, e.g. `3m ago`
+pub fn format_ago(time: SystemTime) -> String {
+    let secs = SystemTime::now()
+        .duration_since(time)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    match secs {
+        0..60 => "just now".to_string(),
+        60..3_600 => format!("{}m ago", secs / 60),
+        3_600..86_400 => format!("{}h ago", secs / 3_600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+/// Whether a track is long enough to need hours in its time labels
+pub fn needs_hours(duration_ms: u64) -> bool {
+    duration_ms >= 3_600_000
+}
+
+/// Interpolates the playback position between polls so progress moves every frame
+#[derive(Debug, Clone)]
+pub struct PlaybackClock {
+    last_poll: Instant,
+    last_progress_ms: u64,
+    is_playing: bool,
+    duration: u64,
+}
+
+impl PlaybackClock {
+    pub fn new() -> Self {
+        Self {
+            last_poll: Instant::now(),
+            last_progress_ms: 0,
+            is_playing: false,
+            duration: 0,
+        }
+    }
+
+    /// Re-anchor the clock on a freshly polled track. A missing position (which
+    /// Spotify reports transiently) keeps the current estimate instead of resetting
+    pub fn sync(&mut self, track: &TrackInfo) {
+        let now = Instant::now();
+        self.last_progress_ms = match track.progress {
+            Some(ms) => ms,
+            None => self.at(now),
+        };
+        self.last_poll = now;
+        self.is_playing = track.is_playing;
+        self.duration = track.duration;
+    }
+
+    /// Jump to a position right away, ahead of the next poll confirming it
+    pub fn seek(&mut self, position_ms: u64) {
+        self.last_progress_ms = position_ms;
+        self.last_poll = Instant::now();
+    }
+
+    /// Estimated position right now
+    pub fn now_ms(&self) -> u64 {
+        self.at(Instant::now())
+    }
+
+    /// Estimated position at `now`, clamped to the track length when it is known
+    pub fn at(&self, now: Instant) -> u64 {
+        if !self.is_playing {
+            return self.last_progress_ms;
+        }
+        let elapsed = now.saturating_duration_since(self.last_poll).as_millis() as u64;
+        let progress = self.last_progress_ms + elapsed;
+        if self.duration > 0 {
+            progress.min(self.duration)
+        } else {
+            progress
+        }
+    }
+}
+
+impl Default for PlaybackClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Connection state of the Spotify client, used by panels for empty-state hints
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpotifyStatus {
+    Connecting,
+    Connected,
+    Disconnected,
+}
+
+impl SpotifyStatus {
+    /// Guidance for panels that have nothing to show without a current track
+    pub fn idle_hint(self) -> &'static str {
+        match self {
+            SpotifyStatus::Connecting => "Connecting to Spotify...",
+            SpotifyStatus::Connected => "Nothing playing - start playback on any Spotify device",
+            SpotifyStatus::Disconnected => {
+                "Spotify not connected - run `phosphor spotify login` to sign in"
+            }
+        }
+    }
+}
+
+/// Playback source behind [`SpotifyClient`]
+enum Backend {
+    Web(Box<WebApi>),
+    #[cfg(feature = "mpris")]
+    Mpris(MprisClient),
+    Mpd(Box<MpdClient>),
 }
 
 pub struct SpotifyClient {
-    client: AuthCodePkceSpotify,
+    backend: Backend,
 }
 
 impl SpotifyClient {
+    /// Connect using `player` or `[spotify] backend`: `web`, `mpris`, `mpd`, or
+    /// `auto` (the Web API, falling back to a local MPRIS player when it can't
+    /// be authorized)
     pub async fn new(config: &Config) -> Result<Self> {
-        // Use bundled client ID (PKCE doesn't need secret), allow override via env/config
-        let client_id = std::env::var("SPOTIPY_CLIENT_ID")
-            .or_else(|_| std::env::var("RSPOTIFY_CLIENT_ID"))
-            .unwrap_or_else(|_| {
+        let backend = match config.player_backend().trim().to_lowercase().as_str() {
+            "web" => Backend::Web(Box::new(WebApi::connect(config).await?)),
+            "mpris" => Self::mpris_backend()?,
+            "mpd" => Backend::Mpd(Box::new(MpdClient::connect(&config.mpd).await?)),
+            "auto" => match WebApi::connect(config).await {
+                Ok(web) => Backend::Web(Box::new(web)),
+                Err(e) => Self::mpris_backend().map_err(|_| e)?,
+            },
+            other => bail!(
+                "Unknown player '{}' (expected auto, web, mpris or mpd)",
+                other
+            ),
+        };
+
+        Ok(Self { backend })
+    }
+
+    /// Run the Web API sign-in even if a token is cached, storing the new token
+    pub async fn login(config: &Config, flow: AuthFlow) -> Result<TokenInfo> {
+        let web = WebApi::authorize(config, Some(flow)).await?;
+        Ok(web.token_info().await)
+    }
+
+    /// Delete the stored Web API token from the keyring and the data directory,
+    /// returning whether there was one
+    pub fn logout() -> Result<bool> {
+        let mut removed = secrets::delete(secrets::SPOTIFY_TOKEN);
+
+        let path = WebApi::cache_path();
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+            removed = true;
+        }
+
+        Ok(removed)
+    }
+
+    #[cfg(feature = "mpris")]
+    fn mpris_backend() -> Result<Backend> {
+        Ok(Backend::Mpris(MprisClient::connect()?))
+    }
+
+    #[cfg(not(feature = "mpris"))]
+    fn mpris_backend() -> Result<Backend> {
+        bail!("phosphor was built without MPRIS support (rebuild with --features mpris)")
+    }
+
+    /// Append a track or episode (URI or bare track id) to the queue
+    pub async fn add_to_queue(&self, uri: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.add_to_queue(uri).await,
+            _ => bail!("The queue is only available through the Spotify Web API"),
+        }
+    }
+}
+
+impl Player for SpotifyClient {
+    fn name(&self) -> &'static str {
+        match &self.backend {
+            Backend::Web(web) => web.name(),
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.name(),
+            Backend::Mpd(mpd) => mpd.name(),
+        }
+    }
+
+    async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
+        match &self.backend {
+            Backend::Web(web) => web.get_current_track().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.get_current_track().await,
+            Backend::Mpd(mpd) => mpd.get_current_track().await,
+        }
+    }
+
+    async fn play(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.play().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.play().await,
+            Backend::Mpd(mpd) => mpd.play().await,
+        }
+    }
+
+    async fn pause(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.pause().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.pause().await,
+            Backend::Mpd(mpd) => mpd.pause().await,
+        }
+    }
+
+    async fn next(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.next().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.next().await,
+            Backend::Mpd(mpd) => mpd.next().await,
+        }
+    }
+
+    async fn prev(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.prev().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.prev().await,
+            Backend::Mpd(mpd) => mpd.prev().await,
+        }
+    }
+
+    async fn set_volume(&self, volume: u8) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.set_volume(volume).await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.set_volume(volume).await,
+            Backend::Mpd(mpd) => mpd.set_volume(volume).await,
+        }
+    }
+
+    async fn toggle_playback(&self) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.toggle_playback().await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.toggle_playback().await,
+            Backend::Mpd(mpd) => mpd.toggle_playback().await,
+        }
+    }
+
+    async fn seek(&self, position_ms: u64) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.seek(position_ms).await,
+            #[cfg(feature = "mpris")]
+            Backend::Mpris(mpris) => mpris.seek(position_ms).await,
+            Backend::Mpd(mpd) => mpd.seek(position_ms).await,
+        }
+    }
+
+    async fn persist_token(&self) -> bool {
+        match &self.backend {
+            Backend::Web(web) => web.persist_token().await,
+            _ => false,
+        }
+    }
+
+    async fn get_queue(&self) -> Result<Vec<QueueItem>> {
+        match &self.backend {
+            Backend::Web(web) => web.get_queue().await,
+            _ => bail!("The queue is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn recently_played(&self, limit: u32) -> Result<Vec<HistoryItem>> {
+        match &self.backend {
+            Backend::Web(web) => web.recently_played(limit).await,
+            _ => bail!("History is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn is_saved(&self, uri: &str) -> Result<bool> {
+        match &self.backend {
+            Backend::Web(web) => web.is_saved(uri).await,
+            _ => bail!("The library is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn set_saved(&self, uri: &str, saved: bool) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.set_saved(uri, saved).await,
+            _ => bail!("The library is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchItem>> {
+        match &self.backend {
+            Backend::Web(web) => web.search(query).await,
+            _ => bail!("Search is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn play_uri(&self, uri: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.play_uri(uri).await,
+            _ => bail!("Playing a URI is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn play_uris(&self, uris: &[&str]) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.play_uris(uris).await,
+            _ => bail!("Playing a URI is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn play_in_context(&self, context_uri: &str, uri: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.play_in_context(context_uri, uri).await,
+            _ => bail!("Playing a URI is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn start_radio(&self) -> Result<Radio> {
+        match &self.backend {
+            Backend::Web(web) => web.start_radio().await,
+            _ => bail!("Radio is only available through the Spotify Web API"),
+        }
+    }
+
+    async fn list_devices(&self) -> Result<Vec<DeviceInfo>> {
+        match &self.backend {
+            Backend::Web(web) => web.list_devices().await,
+            _ => bail!("Devices are only available through the Spotify Web API"),
+        }
+    }
+
+    async fn transfer_playback(&self, device_id: &str) -> Result<()> {
+        match &self.backend {
+            Backend::Web(web) => web.transfer_playback(device_id).await,
+            _ => bail!("Devices are only available through the Spotify Web API"),
+        }
+    }
+}
+
+/// Details of a freshly stored Web API token, for `phosphor spotify login`
+pub struct TokenInfo {
+    pub scopes: Vec<String>,
+    /// When the access token expires; it is refreshed automatically after that
+    pub expires_at: Option<SystemTime>,
+    /// Where the token is kept (keyring or file path)
+    pub storage: String,
+}
+
+/// How to finish the OAuth sign-in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthFlow {
+    /// Open a browser and catch the redirect on a local server
+    Browser,
+    /// Print the URL and read the redirected address back from stdin
+    Manual,
+}
+
+/// `host:port` the local callback server listens on, taken from the redirect URI
+fn redirect_address(redirect_uri: &str) -> Result<String> {
+    let rest = redirect_uri
+        .strip_prefix("http://")
+        .with_context(|| format!("Redirect URI must start with http:// ({})", redirect_uri))?;
+    let authority = rest.split('/').next().unwrap_or(rest);
+    if authority.contains(':') {
+        Ok(authority.to_string())
+    } else {
+        Ok(format!("{}:80", authority))
+    }
+}
+
+/// Pull the authorization code out of a redirect URL, or take a bare pasted code
+fn auth_code(input: &str) -> Option<&str> {
+    let code = match input.split_once("code=") {
+        Some((_, rest)) => rest.split(['&', '#']).next()?,
+        None if !input.contains(['/', '?', '=', ' ']) => input,
+        None => return None,
+    };
+    (!code.is_empty()).then_some(code)
+}
+
+/// Spotify Web API client authorized with PKCE
+struct WebApi {
+    client: AuthCodePkceSpotify,
+    /// Token lives in the OS keyring instead of the plaintext cache file
+    keyring: bool,
+    /// Last token JSON written to the keyring, to skip redundant writes
+    saved_token: Mutex<Option<String>>,
+    /// Context URI and what it resolved to, so names are looked up once
+    context_cache: Mutex<Option<(String, Option<PlaybackContext>)>>,
+}
+
+impl WebApi {
+    async fn connect(config: &Config) -> Result<Self> {
+        Self::authorize(config, None).await
+    }
+
+    /// Set up the client, reusing the cached token unless `login` forces a fresh
+    /// sign-in with the given flow
+    async fn authorize(config: &Config, login: Option<AuthFlow>) -> Result<Self> {
+        // Use bundled client ID (PKCE doesn't need secret), allow override via keyring/env/config
+        let client_id = secrets::get(secrets::SPOTIFY_CLIENT_ID)
+            .or_else(|| std::env::var("SPOTIPY_CLIENT_ID").ok())
+            .or_else(|| std::env::var("RSPOTIFY_CLIENT_ID").ok())
+            .unwrap_or_else(|| {
                 if !config.spotify.client_id.is_empty() {
                     config.spotify.client_id.clone()
                 } else {
@@ -52,41 +630,142 @@ impl SpotifyClient {
             scopes: scopes!(
                 "user-read-playback-state",
                 "user-modify-playback-state",
-                "user-read-currently-playing"
+                "user-read-currently-playing",
+                "user-library-read",
+                "user-library-modify",
+                "user-read-recently-played"
             ),
             ..Default::default()
         };
 
+        // Prefer the keyring for the token; fall back to the cache file without it
+        let keyring = config.spotify.keyring && secrets::available(secrets::SPOTIFY_TOKEN);
+        let cache_path = Self::cache_path();
+
+        // Keyring turned off in config: move its token back into the file
+        if !keyring
+            && !cache_path.exists()
+            && let Some(token) = secrets::get(secrets::SPOTIFY_TOKEN)
+                .and_then(|json| serde_json::from_str::<Token>(&json).ok())
+            && token.write_cache(&cache_path).is_ok()
+        {
+            secrets::delete(secrets::SPOTIFY_TOKEN);
+        }
+
         let config_rspotify = rspotify::Config {
-            cache_path: Self::cache_path(),
-            token_cached: true,
+            cache_path: cache_path.clone(),
+            token_cached: !keyring,
             token_refreshing: true,
             ..Default::default()
         };
 
+        let scopes = oauth.scopes.clone();
         let mut client = AuthCodePkceSpotify::with_config(creds, oauth, config_rspotify);
 
         // Try to read cached token first
-        match client.read_token_cache(false).await {
-            Ok(Some(token)) => {
+        let cached = if login.is_some() {
+            None
+        } else if keyring {
+            secrets::get(secrets::SPOTIFY_TOKEN)
+                .and_then(|json| serde_json::from_str::<Token>(&json).ok())
+                // Pick up a token from the cache file so it can be moved into the keyring
+                .or_else(|| Token::from_cache(&cache_path).ok())
+        } else {
+            // An expired access token is fine, it is refreshed on the first request
+            client.read_token_cache(true).await.ok().flatten()
+        };
+
+        // Only `phosphor spotify login` opens a browser, never the TUI or other commands
+        match (cached, login) {
+            (Some(token), _) => {
+                // Tokens granted before new scopes were added need a fresh sign-in
+                if !scopes.is_subset(&token.scopes) {
+                    bail!("Spotify needs new permissions - run `phosphor spotify login` again");
+                }
                 *client.token.lock().await.unwrap() = Some(token);
             }
-            _ => {
+            (None, Some(flow)) => {
                 let auth_url = client.get_authorize_url(None)?;
-                Self::authenticate_with_local_server(&mut client, &auth_url).await?;
+                match flow {
+                    AuthFlow::Browser => {
+                        Self::authenticate_with_local_server(&mut client, &auth_url).await?
+                    }
+                    AuthFlow::Manual => Self::authenticate_manually(&mut client, &auth_url).await?,
+                }
             }
+            (None, None) => bail!("Not signed in to Spotify - run `phosphor spotify login`"),
+        }
+
+        let spotify = Self {
+            client,
+            keyring,
+            saved_token: Mutex::new(None),
+            context_cache: Mutex::new(None),
+        };
+
+        // Once the token is safely in the keyring, drop the plaintext copy
+        if spotify.persist_token().await {
+            let _ = std::fs::remove_file(&cache_path);
+        }
+
+        Ok(spotify)
+    }
+
+    /// Save the current token to the keyring if it changed (Spotify rotates refresh
+    /// tokens), returning whether the keyring holds it. No-op without the keyring.
+    async fn persist_token(&self) -> bool {
+        if !self.keyring {
+            return false;
         }
 
-        Ok(Self { client })
+        let json = match self.client.token.lock().await {
+            Ok(token) => token.as_ref().and_then(|t| serde_json::to_string(t).ok()),
+            Err(_) => None,
+        };
+        let Some(json) = json else {
+            return false;
+        };
+
+        let mut saved = self.saved_token.lock().unwrap();
+        if saved.as_deref() == Some(json.as_str()) {
+            return true;
+        }
+        if secrets::set(secrets::SPOTIFY_TOKEN, &json) {
+            *saved = Some(json);
+            return true;
+        }
+        false
+    }
+
+    async fn token_info(&self) -> TokenInfo {
+        let token = self.client.token.lock().await.ok().and_then(|t| t.clone());
+        let mut scopes: Vec<String> = token
+            .as_ref()
+            .map(|t| t.scopes.iter().cloned().collect())
+            .unwrap_or_default();
+        scopes.sort();
+
+        TokenInfo {
+            scopes,
+            expires_at: token
+                .and_then(|t| t.expires_at)
+                .map(|at| UNIX_EPOCH + Duration::from_secs(at.timestamp().max(0) as u64)),
+            storage: if self.keyring {
+                "system keyring".to_string()
+            } else {
+                Self::cache_path().display().to_string()
+            },
+        }
     }
 
     async fn authenticate_with_local_server(
         client: &mut AuthCodePkceSpotify,
         auth_url: &str,
     ) -> Result<()> {
-        // Start local server to catch the callback
-        let listener = TcpListener::bind("127.0.0.1:8888")
-            .context("Failed to bind to port 8888 for OAuth callback")?;
+        // Listen where the redirect URI points, so a custom host/port works too
+        let addr = redirect_address(&client.oauth.redirect_uri)?;
+        let listener = TcpListener::bind(&addr)
+            .with_context(|| format!("Failed to bind to {} for OAuth callback", addr))?;
 
         // Open browser for auth
         if open::that(auth_url).is_err() {
@@ -102,18 +781,13 @@ impl SpotifyClient {
         let mut request_line = String::new();
         reader.read_line(&mut request_line)?;
 
-        // Extract the code from the callback URL
         // Format: GET /callback?code=XXX HTTP/1.1
         let url = request_line
             .split_whitespace()
             .nth(1)
             .context("Invalid callback request")?;
 
-        let code = url
-            .split("code=")
-            .nth(1)
-            .and_then(|s| s.split('&').next())
-            .context("No code in callback URL")?;
+        let code = auth_code(url).context("No code in callback URL")?;
 
         // Send a nice response to the browser
         let response = "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\n\r\n\
@@ -127,17 +801,343 @@ impl SpotifyClient {
         Ok(())
     }
 
+    /// Sign in without a local browser (e.g. over SSH): the user opens the URL on
+    /// any device and pastes back the address the redirect lands on
+    async fn authenticate_manually(client: &mut AuthCodePkceSpotify, auth_url: &str) -> Result<()> {
+        println!("Open this URL in a browser on any device and approve access:\n");
+        println!("  {}\n", auth_url);
+        println!("The browser is then sent to {}, which will fail to load.", client.oauth.redirect_uri);
+        print!("Paste the full address from its address bar here: ");
+        std::io::stdout().flush()?;
+
+        let mut input = String::new();
+        std::io::stdin()
+            .read_line(&mut input)
+            .context("Failed to read the redirect URL")?;
+
+        let code = auth_code(input.trim()).context("No code found in the pasted URL")?;
+        client.request_token(code).await?;
+
+        Ok(())
+    }
+
     fn cache_path() -> PathBuf {
-        dirs::home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".phosphor-spotify-token")
+        let data_dir = Config::data_dir();
+        let path = data_dir.join("spotify-token.json");
+
+        // rspotify writes the cache file directly, so the directory must exist
+        let _ = std::fs::create_dir_all(&data_dir);
+
+        // One-time migration of the token from its old home-directory location
+        if !path.exists()
+            && let Some(legacy) = dirs::home_dir().map(|h| h.join(".phosphor-spotify-token"))
+            && legacy.exists()
+            && std::fs::rename(&legacy, &path).is_err()
+            // rename fails across filesystems; fall back to copy + remove
+            && std::fs::copy(&legacy, &path).is_ok()
+        {
+            let _ = std::fs::remove_file(&legacy);
+        }
+
+        path
+    }
+
+    /// Resolve the context URI to a name; cached per context so the lookup
+    /// happens once, failures included
+    async fn playback_context(
+        &self,
+        source: Option<&rspotify::model::Context>,
+    ) -> Option<PlaybackContext> {
+        let source = source?;
+        if let Some((uri, cached)) = self.context_cache.lock().unwrap().as_ref()
+            && *uri == source.uri
+        {
+            return cached.clone();
+        }
+
+        let named = |kind, name: Option<String>| PlaybackContext {
+            kind,
+            name: name.unwrap_or_default(),
+            uri: source.uri.clone(),
+        };
+        let resolved = match source._type {
+            // Spotify's own mixes can't be fetched, keep showing that it's a playlist
+            Type::Playlist => Some(named(
+                "playlist",
+                match PlaylistId::from_uri(&source.uri) {
+                    Ok(id) => self.client.playlist(id, Some("name"), None).await.ok().map(|p| p.name),
+                    Err(_) => None,
+                },
+            )),
+            Type::Album => Some(named(
+                "album",
+                match AlbumId::from_uri(&source.uri) {
+                    Ok(id) => self.client.album(id, None).await.ok().map(|a| a.name),
+                    Err(_) => None,
+                },
+            )),
+            Type::Artist => Some(named(
+                "artist",
+                match ArtistId::from_uri(&source.uri) {
+                    Ok(id) => self.client.artist(id).await.ok().map(|a| a.name),
+                    Err(_) => None,
+                },
+            )),
+            Type::Collection => Some(named("library", Some("Liked Songs".to_string()))),
+            _ => None,
+        };
+
+        *self.context_cache.lock().unwrap() = Some((source.uri.clone(), resolved.clone()));
+        resolved
+    }
+
+    async fn recently_played(&self, limit: u32) -> Result<Vec<HistoryItem>> {
+        let page = self
+            .client
+            .current_user_recently_played(Some(limit.clamp(1, 50)), None)
+            .await
+            .context("Failed to fetch recently played tracks")?;
+
+        Ok(page
+            .items
+            .into_iter()
+            .filter_map(|entry| {
+                let millis = entry.played_at.timestamp_millis().max(0) as u64;
+                Some(HistoryItem {
+                    artist: join_artists(&entry.track.artists),
+                    uri: entry.track.id?.uri(),
+                    name: entry.track.name,
+                    played_at: UNIX_EPOCH + Duration::from_millis(millis),
+                })
+            })
+            .collect())
+    }
+
+    async fn is_saved(&self, uri: &str) -> Result<bool> {
+        let id = TrackId::from_id_or_uri(uri).map_err(|_| anyhow!("Invalid track URI: {}", uri))?;
+        let saved = self
+            .client
+            .current_user_saved_tracks_contains([id])
+            .await
+            .context("Failed to check library")?;
+        Ok(saved.first().copied().unwrap_or(false))
+    }
+
+    async fn set_saved(&self, uri: &str, saved: bool) -> Result<()> {
+        let id = TrackId::from_id_or_uri(uri).map_err(|_| anyhow!("Invalid track URI: {}", uri))?;
+        if saved {
+            self.client
+                .current_user_saved_tracks_add([id])
+                .await
+                .context("Failed to save track")?;
+        } else {
+            self.client
+                .current_user_saved_tracks_delete([id])
+                .await
+                .context("Failed to remove track")?;
+        }
+        Ok(())
+    }
+
+    async fn get_queue(&self) -> Result<Vec<QueueItem>> {
+        let queue = self
+            .client
+            .current_user_queue()
+            .await
+            .context("Failed to fetch queue")?;
+        Ok(queue.queue.into_iter().filter_map(queue_item).collect())
+    }
+
+    async fn add_to_queue(&self, uri: &str) -> Result<()> {
+        self.client
+            .add_item_to_queue(playable_id(uri)?, None)
+            .await
+            .context("Failed to add to queue")?;
+        Ok(())
+    }
+
+    async fn search(&self, query: &str) -> Result<Vec<SearchItem>> {
+        let mut items = Vec::new();
+
+        for kind in [SearchType::Track, SearchType::Album, SearchType::Playlist] {
+            let result = self
+                .client
+                .search(query, kind, Some(Market::FromToken), None, Some(SEARCH_LIMIT), None)
+                .await
+                .context("Search failed")?;
+
+            match result {
+                SearchResult::Tracks(page) => {
+                    items.extend(page.items.into_iter().filter_map(|track| {
+                        Some(SearchItem {
+                            kind: SearchKind::Track,
+                            detail: join_artists(&track.artists),
+                            uri: track.id?.uri(),
+                            name: track.name,
+                        })
+                    }));
+                }
+                SearchResult::Albums(page) => {
+                    items.extend(page.items.into_iter().filter_map(|album| {
+                        Some(SearchItem {
+                            kind: SearchKind::Album,
+                            detail: join_artists(&album.artists),
+                            uri: album.id?.uri(),
+                            name: album.name,
+                        })
+                    }));
+                }
+                SearchResult::Playlists(page) => {
+                    items.extend(page.items.into_iter().map(|playlist| SearchItem {
+                        kind: SearchKind::Playlist,
+                        detail: playlist.owner.display_name.unwrap_or_default(),
+                        uri: playlist.id.uri(),
+                        name: playlist.name,
+                    }));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(items)
+    }
+
+    async fn list_devices(&self) -> Result<Vec<DeviceInfo>> {
+        let devices = self.client.device().await.context("Failed to list devices")?;
+        Ok(devices
+            .into_iter()
+            // Restricted devices have no id and can't be controlled
+            .filter_map(|device| {
+                Some(DeviceInfo {
+                    id: device.id?,
+                    kind: format!("{:?}", device._type),
+                    name: device.name,
+                    is_active: device.is_active,
+                    volume: device.volume_percent,
+                })
+            })
+            .collect())
+    }
+
+    async fn transfer_playback(&self, device_id: &str) -> Result<()> {
+        self.client
+            .transfer_playback(device_id, Some(true))
+            .await
+            .context("Failed to transfer playback")?;
+        Ok(())
+    }
+
+    async fn start_radio(&self) -> Result<Radio> {
+        let playing = self
+            .client
+            .current_playing(None, None::<Vec<&AdditionalType>>)
+            .await
+            .context("Failed to get the current track")?;
+        let Some(PlayableItem::Track(track)) = playing.and_then(|p| p.item) else {
+            bail!("Nothing playing to start a radio from");
+        };
+        let track_id = track.id.context("Local files can't seed a radio")?;
+        // Seeded by the track and, when it has an id, its first artist
+        let artist_id = track.artists.first().and_then(|a| a.id.clone());
+
+        let recommendations = self
+            .client
+            .recommendations(
+                Vec::<RecommendationsAttribute>::new(),
+                Some(artist_id),
+                None::<Vec<&str>>,
+                Some([track_id.clone()]),
+                None,
+                Some(RADIO_SIZE),
+            )
+            .await
+            // Spotify answers 404 for apps registered after the endpoint was restricted
+            .context("Spotify returned no recommendations (newer apps no longer have access)")?;
+
+        let ids: Vec<PlayableId> = recommendations
+            .tracks
+            .into_iter()
+            .filter_map(|t| t.id)
+            .filter(|id| *id != track_id)
+            .map(Into::into)
+            .collect();
+        if ids.is_empty() {
+            bail!("Spotify found nothing similar to {}", track.name);
+        }
+
+        let tracks = ids.len();
+        self.client
+            .start_uris_playback(ids, None, None, None)
+            .await
+            .context("Failed to start playback")?;
+
+        Ok(Radio {
+            seed: format!("{} - {}", track.name, join_artists(&track.artists)),
+            tracks,
+        })
+    }
+
+    async fn play_uri(&self, uri: &str) -> Result<()> {
+        // Albums and playlists play as a context so playback continues through them
+        let context: Option<PlayContextId> = if uri.starts_with("spotify:album:") {
+            AlbumId::from_uri(uri).ok().map(Into::into)
+        } else if uri.starts_with("spotify:playlist:") {
+            PlaylistId::from_uri(uri).ok().map(Into::into)
+        } else {
+            None
+        };
+
+        match context {
+            Some(context) => self
+                .client
+                .start_context_playback(context, None, None, None)
+                .await
+                .context("Failed to start playback")?,
+            None => self.play_uris(&[uri]).await?,
+        }
+        Ok(())
+    }
+
+    async fn play_uris(&self, uris: &[&str]) -> Result<()> {
+        let ids = uris.iter().map(|uri| playable_id(uri)).collect::<Result<Vec<_>>>()?;
+        self.client
+            .start_uris_playback(ids, None, None, None)
+            .await
+            .context("Failed to start playback")?;
+        Ok(())
     }
 
-    pub async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
+    async fn play_in_context(&self, context_uri: &str, uri: &str) -> Result<()> {
+        // Only albums and playlists take an offset into them
+        let context: PlayContextId = if context_uri.starts_with("spotify:album:") {
+            AlbumId::from_uri(context_uri)?.into()
+        } else if context_uri.starts_with("spotify:playlist:") {
+            PlaylistId::from_uri(context_uri)?.into()
+        } else {
+            bail!("Can't start {} from one of its tracks", context_uri);
+        };
+        self.client
+            .start_context_playback(context, None, Some(Offset::Uri(uri.to_string())), None)
+            .await
+            .context("Failed to start playback")?;
+        Ok(())
+    }
+}
+
+impl Player for WebApi {
+    fn name(&self) -> &'static str {
+        "spotify"
+    }
+
+    async fn get_current_track(&self) -> Result<Option<TrackInfo>> {
         // Handle parse errors gracefully (ads, unsupported content types, etc.)
+        // Episodes must be requested explicitly or Spotify returns a null item for podcasts
         let context = match self
             .client
-            .current_playing(None, Some([&AdditionalType::Track]))
+            .current_playback(
+                None,
+                Some([&AdditionalType::Track, &AdditionalType::Episode]),
+            )
             .await
         {
             Ok(ctx) => ctx,
@@ -148,6 +1148,9 @@ impl SpotifyClient {
             return Ok(None);
         };
 
+        let playing_from = self.playback_context(context.context.as_ref()).await;
+        let device = Some(context.device.name.clone());
+
         let Some(item) = context.item else {
             return Ok(None);
         };
@@ -171,6 +1174,11 @@ impl SpotifyClient {
                     progress: context.progress.map(|d| d.num_milliseconds() as u64),
                     is_playing: context.is_playing,
                     album_art_url,
+                    is_episode: false,
+                    uri: track.id.map(|id| id.uri()),
+                    saved: None,
+                    context: playing_from,
+                    device,
                 }
             }
             PlayableItem::Episode(episode) => TrackInfo {
@@ -181,46 +1189,58 @@ impl SpotifyClient {
                 progress: context.progress.map(|d| d.num_milliseconds() as u64),
                 is_playing: context.is_playing,
                 album_art_url: episode.images.first().map(|i| i.url.clone()),
+                is_episode: true,
+                uri: Some(episode.id.uri()),
+                saved: None,
+                context: playing_from,
+                device,
             },
             PlayableItem::Unknown(v) => {
                 // rspotify sometimes fails to parse valid tracks, extract manually
-                let obj = v.as_object();
-                if let Some(obj) = obj {
-                    if obj.get("type").and_then(|t| t.as_str()) == Some("track") {
-                        let name = obj.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown").to_string();
-                        let artists = obj.get("artists")
-                            .and_then(|a| a.as_array())
-                            .map(|arr| arr.iter()
-                                .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
-                                .collect::<Vec<_>>()
-                                .join(", "))
-                            .unwrap_or_else(|| "Unknown".to_string());
-                        let album = obj.get("album")
-                            .and_then(|a| a.get("name"))
-                            .and_then(|n| n.as_str())
-                            .unwrap_or("Unknown")
-                            .to_string();
-                        let duration = obj.get("duration_ms")
-                            .and_then(|d| d.as_u64())
-                            .unwrap_or(0);
-                        let album_art_url = obj.get("album")
-                            .and_then(|a| a.get("images"))
-                            .and_then(|imgs| imgs.as_array())
-                            .and_then(|arr| arr.first())
-                            .and_then(|img| img.get("url"))
-                            .and_then(|u| u.as_str())
-                            .map(|s| s.to_string());
-
-                        return Ok(Some(TrackInfo {
-                            name,
-                            artist: artists,
-                            album,
-                            duration,
-                            progress: context.progress.map(|d| d.num_milliseconds() as u64),
-                            is_playing: context.is_playing,
-                            album_art_url,
-                        }));
-                    }
+                if let Some(obj) = v.as_object()
+                    && obj.get("type").and_then(|t| t.as_str()) == Some("track")
+                {
+                    let name = obj.get("name").and_then(|n| n.as_str()).unwrap_or("Unknown").to_string();
+                    let artists = obj.get("artists")
+                        .and_then(|a| a.as_array())
+                        .map(|arr| arr.iter()
+                            .filter_map(|a| a.get("name").and_then(|n| n.as_str()))
+                            .collect::<Vec<_>>()
+                            .join(", "))
+                        .unwrap_or_else(|| "Unknown".to_string());
+                    let album = obj.get("album")
+                        .and_then(|a| a.get("name"))
+                        .and_then(|n| n.as_str())
+                        .unwrap_or("Unknown")
+                        .to_string();
+                    let duration = obj.get("duration_ms")
+                        .and_then(|d| d.as_u64())
+                        .unwrap_or(0);
+                    let album_art_url = obj.get("album")
+                        .and_then(|a| a.get("images"))
+                        .and_then(|imgs| imgs.as_array())
+                        .and_then(|arr| arr.first())
+                        .and_then(|img| img.get("url"))
+                        .and_then(|u| u.as_str())
+                        .map(|s| s.to_string());
+                    let uri = obj.get("uri")
+                        .and_then(|u| u.as_str())
+                        .map(|s| s.to_string());
+
+                    return Ok(Some(TrackInfo {
+                        name,
+                        artist: artists,
+                        album,
+                        duration,
+                        progress: context.progress.map(|d| d.num_milliseconds() as u64),
+                        is_playing: context.is_playing,
+                        album_art_url,
+                        is_episode: false,
+                        uri,
+                        saved: None,
+                        context: playing_from,
+                        device,
+                    }));
                 }
                 return Ok(None);
             }
@@ -229,7 +1249,7 @@ impl SpotifyClient {
         Ok(Some(track_info))
     }
 
-    pub async fn play(&self) -> Result<()> {
+    async fn play(&self) -> Result<()> {
         self.client
             .resume_playback(None, None)
             .await
@@ -237,7 +1257,7 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn pause(&self) -> Result<()> {
+    async fn pause(&self) -> Result<()> {
         self.client
             .pause_playback(None)
             .await
@@ -245,7 +1265,7 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn next(&self) -> Result<()> {
+    async fn next(&self) -> Result<()> {
         self.client
             .next_track(None)
             .await
@@ -253,7 +1273,7 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn prev(&self) -> Result<()> {
+    async fn prev(&self) -> Result<()> {
         self.client
             .previous_track(None)
             .await
@@ -261,7 +1281,7 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn set_volume(&self, volume: u8) -> Result<()> {
+    async fn set_volume(&self, volume: u8) -> Result<()> {
         self.client
             .volume(volume, None)
             .await
@@ -269,14 +1289,119 @@ impl SpotifyClient {
         Ok(())
     }
 
-    pub async fn toggle_playback(&self) -> Result<()> {
-        if let Some(track) = self.get_current_track().await? {
-            if track.is_playing {
-                self.pause().await?;
-            } else {
-                self.play().await?;
-            }
-        }
+    async fn seek(&self, position_ms: u64) -> Result<()> {
+        self.client
+            .seek_track(chrono::Duration::milliseconds(position_ms as i64), None)
+            .await
+            .context("Failed to seek")?;
         Ok(())
     }
 }
+
+fn join_artists(artists: &[SimplifiedArtist]) -> String {
+    artists
+        .iter()
+        .map(|a| a.name.clone())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn queue_item(item: PlayableItem) -> Option<QueueItem> {
+    match item {
+        PlayableItem::Track(track) => Some(QueueItem {
+            artist: join_artists(&track.artists),
+            duration: track.duration.num_milliseconds() as u64,
+            uri: track.id.map(|id| id.uri()).unwrap_or_default(),
+            name: track.name,
+        }),
+        PlayableItem::Episode(episode) => Some(QueueItem {
+            artist: episode.show.name,
+            duration: episode.duration.num_milliseconds() as u64,
+            uri: episode.id.uri(),
+            name: episode.name,
+        }),
+        PlayableItem::Unknown(_) => None,
+    }
+}
+
+/// Parse `spotify:track:...`, `spotify:episode:...` or a bare track id
+fn playable_id(uri: &str) -> Result<PlayableId<'_>> {
+    let id: PlayableId = if uri.starts_with("spotify:episode:") {
+        EpisodeId::from_uri(uri)
+            .map_err(|_| anyhow!("Invalid episode URI: {}", uri))?
+            .into()
+    } else {
+        TrackId::from_id_or_uri(uri)
+            .map_err(|_| anyhow!("Invalid track URI: {}", uri))?
+            .into()
+    };
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn track(progress: Option<u64>, is_playing: bool, duration: u64) -> TrackInfo {
+        TrackInfo {
+            name: "Track".to_string(),
+            artist: "Artist".to_string(),
+            album: "Album".to_string(),
+            duration,
+            progress,
+            is_playing,
+            album_art_url: None,
+            is_episode: false,
+            uri: None,
+            saved: None,
+            context: None,
+            device: None,
+        }
+    }
+
+    fn synced(progress: Option<u64>, is_playing: bool, duration: u64) -> PlaybackClock {
+        let mut clock = PlaybackClock::new();
+        clock.sync(&track(progress, is_playing, duration));
+        clock
+    }
+
+    #[test]
+    fn advances_while_playing() {
+        let clock = synced(Some(10_000), true, 180_000);
+        assert_eq!(clock.at(clock.last_poll), 10_000);
+        assert_eq!(clock.at(clock.last_poll + Duration::from_millis(2_500)), 12_500);
+    }
+
+    #[test]
+    fn frozen_while_paused() {
+        let clock = synced(Some(10_000), false, 180_000);
+        assert_eq!(clock.at(clock.last_poll + Duration::from_secs(30)), 10_000);
+    }
+
+    #[test]
+    fn clamps_at_duration() {
+        let clock = synced(Some(179_000), true, 180_000);
+        assert_eq!(clock.at(clock.last_poll + Duration::from_secs(5)), 180_000);
+    }
+
+    #[test]
+    fn unknown_duration_is_not_clamped() {
+        let clock = synced(Some(179_000), true, 0);
+        assert_eq!(clock.at(clock.last_poll + Duration::from_secs(5)), 184_000);
+    }
+
+    #[test]
+    fn missing_progress_keeps_the_estimate() {
+        let mut clock = synced(Some(10_000), false, 180_000);
+        clock.sync(&track(None, false, 180_000));
+        assert_eq!(clock.at(clock.last_poll), 10_000);
+    }
+
+    #[test]
+    fn seek_resets_the_base() {
+        let mut clock = synced(Some(10_000), true, 180_000);
+        clock.seek(60_000);
+        assert_eq!(clock.at(clock.last_poll), 60_000);
+        assert_eq!(clock.at(clock.last_poll + Duration::from_secs(1)), 61_000);
+    }
+}
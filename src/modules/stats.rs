@@ -0,0 +1,157 @@
+//! Local listening log (JSON lines in the data directory) and the summaries
+//! behind `phosphor stats`
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::PathBuf;
+
+use crate::config::Config;
+use crate::modules::listens::Listen;
+
+/// One counted play, as stored in the log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayRecord {
+    /// Unix seconds when the track started
+    pub played_at: u64,
+    pub artist: String,
+    pub track: String,
+    #[serde(default)]
+    pub album: String,
+    #[serde(default)]
+    pub uri: Option<String>,
+    pub duration_ms: u64,
+}
+
+impl From<&Listen> for PlayRecord {
+    fn from(listen: &Listen) -> Self {
+        Self {
+            played_at: listen.started_at,
+            artist: listen.artist.clone(),
+            track: listen.track.clone(),
+            album: listen.album.clone(),
+            uri: listen.uri.clone(),
+            duration_ms: listen.duration.as_millis() as u64,
+        }
+    }
+}
+
+pub fn log_path() -> PathBuf {
+    Config::data_dir().join("listens.jsonl")
+}
+
+/// Append a play to the log
+pub fn record(listen: &Listen) -> Result<()> {
+    let path = log_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open {}", path.display()))?;
+    writeln!(file, "{}", serde_json::to_string(&PlayRecord::from(listen))?)?;
+    Ok(())
+}
+
+/// Every play in the log, oldest first; unreadable lines are skipped
+pub fn load() -> Result<Vec<PlayRecord>> {
+    let path = log_path();
+    let file = match std::fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    };
+
+    Ok(BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str(&line).ok())
+        .collect())
+}
+
+pub struct Stats {
+    pub plays: usize,
+    pub first_day: Option<NaiveDate>,
+    /// Most played artists and "track - artist", with play counts
+    pub top_artists: Vec<(String, usize)>,
+    pub top_tracks: Vec<(String, usize)>,
+    /// Hours listened on each of the last days, oldest first (days without plays included)
+    pub daily_hours: Vec<(NaiveDate, f64)>,
+    /// Consecutive days with plays up to today (or yesterday, if nothing yet today)
+    pub current_streak: usize,
+    pub longest_streak: usize,
+}
+
+fn local_day(played_at: u64) -> Option<NaiveDate> {
+    DateTime::from_timestamp(played_at as i64, 0).map(|t| t.with_timezone(&Local).date_naive())
+}
+
+fn top(counts: HashMap<String, usize>, limit: usize) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    counts.truncate(limit);
+    counts
+}
+
+/// Summarize the log; hours count each play at its full track length
+pub fn compute(records: &[PlayRecord], top_n: usize, days: usize) -> Stats {
+    let mut artists: HashMap<String, usize> = HashMap::new();
+    let mut tracks: HashMap<String, usize> = HashMap::new();
+    let mut per_day: BTreeMap<NaiveDate, u64> = BTreeMap::new();
+
+    for record in records {
+        *artists.entry(record.artist.clone()).or_default() += 1;
+        *tracks
+            .entry(format!("{} - {}", record.track, record.artist))
+            .or_default() += 1;
+        if let Some(day) = local_day(record.played_at) {
+            *per_day.entry(day).or_default() += record.duration_ms;
+        }
+    }
+
+    let today = Local::now().date_naive();
+    let daily_hours = (0..days as u64)
+        .rev()
+        .filter_map(|back| today.checked_sub_days(chrono::Days::new(back)))
+        .map(|day| {
+            let ms = per_day.get(&day).copied().unwrap_or(0);
+            (day, ms as f64 / 3_600_000.0)
+        })
+        .collect();
+
+    // Walk the active days in order, counting runs of consecutive dates
+    let mut longest_streak = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for &day in per_day.keys() {
+        run = match previous {
+            Some(prev) if prev.succ_opt() == Some(day) => run + 1,
+            _ => 1,
+        };
+        longest_streak = longest_streak.max(run);
+        previous = Some(day);
+    }
+    let last_active = previous;
+    let yesterday = today.pred_opt();
+    let current_streak = if last_active == Some(today) || last_active == yesterday {
+        run
+    } else {
+        0
+    };
+
+    Stats {
+        plays: records.len(),
+        first_day: per_day.keys().next().copied(),
+        top_artists: top(artists, top_n),
+        top_tracks: top(tracks, top_n),
+        daily_hours,
+        current_streak,
+        longest_streak,
+    }
+}
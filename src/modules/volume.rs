@@ -0,0 +1,97 @@
+//! Volume changes through the player, or the local PulseAudio / PipeWire sink
+//! via `pactl` when the player can't (e.g. Spotify without Premium)
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+use crate::config::SpotifyConfig;
+use crate::modules::player::Player;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Mode {
+    /// The player, switching to the sink the first time it refuses
+    #[default]
+    Auto,
+    Player,
+    Sink,
+}
+
+#[derive(Default)]
+pub struct VolumeControl {
+    mode: Mode,
+    /// Auto mode gave up on the player
+    fell_back: bool,
+}
+
+impl VolumeControl {
+    pub fn new(config: &SpotifyConfig) -> Result<Self> {
+        let mode = match config.volume.trim().to_lowercase().as_str() {
+            "auto" => Mode::Auto,
+            "player" => Mode::Player,
+            "pulse" => Mode::Sink,
+            other => bail!(
+                "Unknown spotify.volume '{}' (expected auto, player or pulse)",
+                other
+            ),
+        };
+        Ok(Self { mode, fell_back: false })
+    }
+
+    fn uses_sink(&self) -> bool {
+        self.mode == Mode::Sink || self.fell_back
+    }
+
+    /// Where volume changes currently go, e.g. "spotify" or "pulse"
+    pub fn label(&self, player: &impl Player) -> &'static str {
+        if self.uses_sink() { "pulse" } else { player.name() }
+    }
+
+    /// Current volume when it can be read (only the sink reports it)
+    pub fn level(&self) -> Option<u8> {
+        self.uses_sink().then(sink_volume).and_then(Result::ok)
+    }
+
+    /// Set the volume in percent (0-100)
+    pub async fn set(&mut self, player: &impl Player, volume: u8) -> Result<()> {
+        if !self.uses_sink() {
+            match player.set_volume(volume).await {
+                Ok(()) => return Ok(()),
+                Err(e) if self.mode == Mode::Player => return Err(e),
+                Err(_) => self.fell_back = true,
+            }
+        }
+        tokio::task::spawn_blocking(move || set_sink_volume(volume)).await?
+    }
+}
+
+fn pactl(args: &[&str]) -> Result<String> {
+    let output = Command::new("pactl")
+        .args(args)
+        .output()
+        .context("Failed to run pactl (is PulseAudio or PipeWire installed?)")?;
+    if !output.status.success() {
+        bail!("pactl: {}", String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Default sink volume in percent (first channel)
+pub fn sink_volume() -> Result<u8> {
+    // "Volume: front-left: 32768 /  50% / -18.06 dB,   front-right: ..."
+    let output = pactl(&["get-sink-volume", "@DEFAULT_SINK@"])?;
+    output
+        .split('/')
+        .find_map(|part| part.trim().strip_suffix('%')?.parse::<u32>().ok())
+        // Boosted sinks go past 100%
+        .map(|percent| percent.min(100) as u8)
+        .context("Couldn't read the sink volume from pactl")
+}
+
+pub fn set_sink_volume(volume: u8) -> Result<()> {
+    pactl(&[
+        "set-sink-volume",
+        "@DEFAULT_SINK@",
+        &format!("{}%", volume.min(100)),
+    ])
+    .map(drop)
+}
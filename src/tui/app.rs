@@ -1,202 +1,871 @@
+use std::collections::BTreeSet;
+use std::future::Future;
 use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use crossterm::{
-    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
+    cursor::Show as ShowCursor,
+    event::{
+        self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture, Event,
+        KeyCode, KeyEvent, KeyEventKind, MouseButton, MouseEvent, MouseEventKind,
+    },
     execute,
     style::SetBackgroundColor,
     terminal::{disable_raw_mode, enable_raw_mode, Clear as TermClear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Constraint, Layout, Rect},
-    style::Style,
-    widgets::{Block, Clear},
+    layout::{Alignment, Constraint, Layout, Position, Rect},
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Clear, Paragraph, Wrap},
     Frame, Terminal,
 };
 use tokio::sync::mpsc;
 
-use crate::config::Config;
+use crate::config::{Config, GitConfig, PanelRows};
 use crate::modules::{
-    audio::{AudioData, AudioSource, SmoothedAudio},
-    git::{CommitInfo, GitTracker, RepoStatus},
+    audio::{
+        AnalysisOptions, AudioData, AudioSource, AutoGain, BeatDetector, MockAudioCapture,
+        SmoothedAudio, SpectrumHistory, SpectrumPeaks,
+    },
+    clipboard,
+    demo::{self, DemoPlayer},
+    git::{Activity, CommitInfo, GitTracker, RepoDetails, RepoStatus},
+    github::{GitHubClient, Notification},
     lyrics::{fetch_lyrics, LyricsStatus, SyncedLyrics},
-    spotify::{SpotifyClient, TrackInfo},
+    listens::{ListenEvent, ListenTracker},
+    player::Player,
+    raw_output::RawOutput,
+    scrobble::Scrobbler,
+    spotify::{
+        DeviceInfo, HistoryItem, PlaybackClock, QueueItem, SearchItem, SpotifyClient,
+        SpotifyStatus, TrackInfo,
+    },
+    stats,
+    volume::VolumeControl,
 };
-use crate::tui::theme::Theme;
+use crate::tui::crt::CrtEffects;
+use crate::tui::keymap::{describe_keys, Action, Keymap, Lookup, Press};
+use crate::tui::layout::{GridLayout, Panel};
+use crate::tui::panel_cache::PanelCache;
+use crate::tui::theme::{Preset, Theme};
 use crate::tui::widgets::{
     album_art::{AlbumArtWidget, ArtStyle, ImageCache},
-    git::HelpWidget,
+    boot::{BootWidget, BOOT_DURATION},
+    devices::DevicesWidget,
+    git::{GitWidget, HelpWidget},
+    github::NotificationsWidget,
+    heatmap::HeatmapWidget,
+    history::HistoryWidget,
     lyrics::LyricsWidget,
+    queue::QueueWidget,
+    screensaver::{ScreensaverWidget, CLOCK_ROWS},
+    search::SearchWidget,
+    splash::SplashWidget,
     spotify::SpotifyWidget,
-    visualizer::{SpectrumWidget, WaveformWidget},
+    status_bar::StatusBarWidget,
+    toast::{Level, MessagesWidget, Toasts, ToastsWidget},
+    visualizer::{
+        ScopeWidget, SpectrogramWidget, SpectrumWidget, VisualizerOptions, WaveformWidget,
+    },
 };
 use image::DynamicImage;
 
-#[derive(Clone, Copy, PartialEq, Eq)]
-enum Panel {
-    Spotify,
-    Lyrics,
-    Spectrum,
-    Waveform,
-    AlbumArt,
-}
+/// How long the boot splash stays up, including its fade-out
+const SPLASH_DURATION: Duration = Duration::from_millis(1200);
+const SPLASH_FADE: Duration = Duration::from_millis(400);
 
-impl Panel {
-    fn next(self) -> Self {
-        match self {
-            Panel::Spotify => Panel::Lyrics,
-            Panel::Lyrics => Panel::Spectrum,
-            Panel::Spectrum => Panel::Waveform,
-            Panel::Waveform => Panel::AlbumArt,
-            Panel::AlbumArt => Panel::Spotify,
-        }
-    }
-}
+/// How long the logo takes to sweep in after the boot sequence
+const SPLASH_REVEAL: Duration = Duration::from_millis(300);
+
+/// Fixed height of the now-playing panel (track info, progress and controls)
+const SPOTIFY_ROWS: u16 = 9;
+
+/// Its two-line compact view, used on screens shorter than `COMPACT_HEIGHT`,
+/// where flexible rows also make do with `MIN_PANEL_ROWS`
+const SPOTIFY_COMPACT_ROWS: u16 = 4;
+const COMPACT_HEIGHT: u16 = 24;
+
+/// Panels narrower than this make way for the rest of their row, and rows
+/// of flexible panels shorter than this for the rest of the screen
+const MIN_PANEL_WIDTH: u16 = 24;
+const MIN_PANEL_ROWS: u16 = 5;
+
+/// Below this the dashboard gives up and asks for a bigger terminal
+const MIN_SIZE: (u16, u16) = (40, 10);
+
+/// Fixed height of the heatmap: month labels and a row per weekday
+const HEATMAP_ROWS: u16 = 10;
+
+/// How far Left/Right jump within the track
+const SEEK_STEP_MS: u64 = 5_000;
+
+/// Lines PageUp/PageDown move through the lyrics
+const LYRICS_PAGE: isize = 10;
+
+/// How long after the last scroll key the lyrics go back to following playback
+const LYRICS_FOLLOW: Duration = Duration::from_secs(5);
+
+/// How often the main loop picks up what the Spotify, lyrics, album art, git
+/// and GitHub tasks have sent, whatever the fps. The tasks keep their own
+/// schedules for talking to the outside world
+const DATA_POLL: Duration = Duration::from_millis(100);
+
+/// How long the main loop gets to shut down cleanly after SIGTERM and co
+/// before the terminal is restored and the process exits regardless
+const SIGNAL_GRACE: Duration = Duration::from_secs(2);
+
+/// Frame rate while `ui.power_save` has things winding down
+const POWER_SAVE_FPS: u32 = 2;
+
+/// How often the queue is re-fetched while it is on screen
+const QUEUE_REFRESH: Duration = Duration::from_secs(5);
+
+/// How often recently played tracks are re-fetched while on screen
+const HISTORY_REFRESH: Duration = Duration::from_secs(30);
+
+/// Tracks shown in the history panel
+const HISTORY_LIMIT: u32 = 30;
+
+/// How often the git task re-reads the tracked repos (`r` refreshes sooner).
+/// On Linux commits and checkouts also trigger a refresh as they happen
+const GIT_REFRESH: Duration = Duration::from_secs(30);
+
+/// Wait after a repo changes before reading it, so a commit's several writes
+/// cause one refresh
+const GIT_SETTLE: Duration = Duration::from_millis(300);
+
+/// How often GitHub notifications are re-fetched (GitHub asks clients not to poll faster)
+const GITHUB_REFRESH: Duration = Duration::from_secs(60);
+
+/// Sleep timer lengths `z` steps through before switching the timer off
+const SLEEP_PRESETS: [Duration; 5] = [
+    Duration::from_secs(15 * 60),
+    Duration::from_secs(30 * 60),
+    Duration::from_secs(45 * 60),
+    Duration::from_secs(60 * 60),
+    Duration::from_secs(90 * 60),
+];
+
+/// How long the screensaver's clock takes to drift across the screen and
+/// back, sideways and up and down
+const DRIFT_PERIOD: (f32, f32) = (240.0, 170.0);
+
+/// How far colors fade towards the background once the sleep timer dims the screen
+const SLEEP_DIM: f32 = 0.7;
+
+/// Spectra kept for the spectrogram, one per column; wider panels leave the rest blank
+const SPECTROGRAM_FRAMES: usize = 512;
 
 enum SpotifyCommand {
     Refresh,
     TogglePlayback,
+    Pause,
     Next,
     Prev,
     SetVolume(u8),
+    RefreshQueue,
+    RefreshHistory,
+    /// Play from this item of the queue as shown, checked against its URI
+    JumpTo { index: usize, uri: String },
+    /// Drop this item of the queue as shown by skipping it when it comes up
+    RemoveFromQueue { index: usize, uri: String },
+    Search(String),
+    PlayUri(String),
+    /// Jump to a position in the current track (ms)
+    Seek(u64),
+    /// Add the current track to Liked Songs, or remove it
+    ToggleSaved,
+    ListDevices,
+    /// Move playback to this device id
+    Transfer(String),
+    /// Play recommendations seeded from the current track
+    Radio,
+}
+
+enum SpotifyEvent {
+    Status(SpotifyStatus),
+    Track(Option<TrackInfo>),
+    /// None when the backend has no queue
+    Queue(Option<Vec<QueueItem>>),
+    /// None when the backend has no history
+    History(Option<Vec<HistoryItem>>),
+    /// Results for a query, or why the search failed
+    SearchResults(String, Result<Vec<SearchItem>, String>),
+    Devices(Result<Vec<DeviceInfo>, String>),
+    /// Outcome of a command worth telling the user about
+    Notice(String),
+    /// A command or poll that failed
+    Error(String),
+    /// Where volume changes go, and the current level when it is known
+    Volume(&'static str, Option<u8>),
+}
+
+/// State of the `d` (Spotify Connect) and `D` (audio capture) device popups
+#[derive(Default)]
+struct DevicePicker {
+    devices: Vec<DeviceInfo>,
+    selected: usize,
+    /// Shown until the device list arrives, or if it can't be used
+    message: Option<String>,
+    /// Lists capture sources for the visualizer instead of playback devices
+    audio: bool,
+}
+
+/// State of the `/` search overlay
+#[derive(Default)]
+struct SearchState {
+    query: String,
+    /// Query the current results belong to, if any search has run
+    searched: Option<String>,
+    results: Vec<SearchItem>,
+    selected: usize,
+    error: Option<String>,
+    pending: bool,
+}
+
+impl SearchState {
+    /// Text shown in place of the results list
+    fn message(&self) -> Option<&str> {
+        if self.pending {
+            Some("Searching...")
+        } else if let Some(ref error) = self.error {
+            Some(error)
+        } else if self.searched.is_none() {
+            Some("Type a track, album or playlist and press Enter")
+        } else if self.results.is_empty() {
+            Some("No results")
+        } else {
+            None
+        }
+    }
+}
+
+/// Data sources backing the dashboard, injectable so it can run without real services
+pub struct Sources {
+    audio: AudioSource,
+    git: GitSource,
+    player: PlayerSource,
+    /// None without a GitHub token
+    github: Option<GitHubSource>,
+    content: Content,
+}
+
+/// Where lyrics and album art come from and where settings saved from the
+/// dashboard go, so the demo stays off the network and the config file
+struct Content {
+    lyrics: LyricsSource,
+    art: ArtSource,
+    store: ConfigStore,
+}
+
+enum LyricsSource {
+    /// Looked up on LRCLIB for each track
+    Lrclib,
+    /// The same lyrics for every track
+    Static(SyncedLyrics),
+}
+
+enum ArtSource {
+    /// Downloaded from each track's art URL, kept in memory
+    Download(ImageCache),
+    /// The same cover for every track
+    Static(DynamicImage),
+}
+
+impl ArtSource {
+    /// The cover shown whatever is playing, if there is one
+    fn fixed(&self) -> Option<DynamicImage> {
+        match self {
+            ArtSource::Download(_) => None,
+            ArtSource::Static(image) => Some(image.clone()),
+        }
+    }
+}
+
+enum ConfigStore {
+    /// The config file
+    File,
+    /// Nowhere; saving reports why
+    ReadOnly(&'static str),
+}
+
+impl ConfigStore {
+    /// Apply `edit` to the config and save it, starting from the file so edits
+    /// made since launch survive
+    fn update(&self, edit: impl FnOnce(&mut Config)) -> Result<()> {
+        match self {
+            ConfigStore::File => {
+                let mut config = Config::load()?;
+                edit(&mut config);
+                config.save()
+            }
+            ConfigStore::ReadOnly(reason) => anyhow::bail!("{}", reason),
+        }
+    }
+}
+
+enum GitSource {
+    Tracker(GitTracker),
+    Static {
+        repos: Vec<RepoStatus>,
+        commits: Vec<CommitInfo>,
+        details: Vec<RepoDetails>,
+        activity: Activity,
+    },
+}
+
+/// Repo statuses and recent commits from one git refresh
+struct GitUpdate {
+    repos: Vec<RepoStatus>,
+    commits: Vec<CommitInfo>,
+    /// Repos the background fetch before this refresh failed for
+    fetch_failed: Vec<String>,
+    /// Commits per day, only read while the heatmap is in the layout
+    activity: Option<Activity>,
+}
+
+/// Requests from the app to the git task
+enum GitCommand {
+    /// Re-read the repos before the next scheduled refresh
+    Refresh,
+    /// Read this repo's details now and on every refresh, or stop (None)
+    Expand(Option<PathBuf>),
+}
+
+enum GitEvent {
+    Update(GitUpdate),
+    Details(RepoDetails),
+}
+
+/// The app's ends of the channels to the git task
+struct GitLink {
+    tx: mpsc::UnboundedSender<GitCommand>,
+    rx: mpsc::UnboundedReceiver<GitEvent>,
+}
+
+impl GitSource {
+    /// Spawn the background task polling this source, so libgit2 never
+    /// blocks a frame on big repos
+    fn spawn(self, config: &Config) -> GitLink {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<GitCommand>();
+        let (event_tx, event_rx) = mpsc::unbounded_channel::<GitEvent>();
+
+        match self {
+            GitSource::Tracker(tracker) => {
+                let heatmap = config
+                    .layout
+                    .rows
+                    .iter()
+                    .flatten()
+                    .any(|name| Panel::from_name(name) == Some(Panel::Heatmap));
+                tokio::spawn(git_background_task(
+                    tracker,
+                    config.git.clone(),
+                    heatmap,
+                    cmd_rx,
+                    event_tx,
+                ));
+            }
+            GitSource::Static { repos, commits, details, activity } => {
+                let update = GitUpdate {
+                    repos,
+                    commits,
+                    fetch_failed: Vec::new(),
+                    activity: Some(activity),
+                };
+                tokio::spawn(static_git_task(update, details, cmd_rx, event_tx));
+            }
+        }
+
+        GitLink {
+            tx: cmd_tx,
+            rx: event_rx,
+        }
+    }
+}
+
+enum GitHubSource {
+    Api(GitHubClient),
+    Static(Vec<Notification>),
+}
+
+enum GitHubCommand {
+    /// Mark this thread read
+    MarkRead(String),
+}
+
+enum GitHubEvent {
+    /// Unread notifications, or why they couldn't be fetched
+    Notifications(Result<Vec<Notification>, String>),
+    /// Marking a notification read failed
+    Error(String),
+}
+
+/// The app's ends of the channels to the notifications task
+struct GitHubLink {
+    tx: mpsc::UnboundedSender<GitHubCommand>,
+    rx: mpsc::UnboundedReceiver<GitHubEvent>,
+}
+
+impl GitHubSource {
+    /// Spawn the background task polling notifications
+    fn spawn(self) -> GitHubLink {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<GitHubCommand>();
+        let (event_tx, event_rx) = mpsc::unbounded_channel::<GitHubEvent>();
+
+        match self {
+            GitHubSource::Api(client) => {
+                tokio::spawn(github_background_task(client, cmd_rx, event_tx));
+            }
+            GitHubSource::Static(notifications) => {
+                tokio::spawn(static_github_task(notifications, cmd_rx, event_tx));
+            }
+        }
+
+        GitHubLink {
+            tx: cmd_tx,
+            rx: event_rx,
+        }
+    }
+}
+
+/// Starts a player's background task on the app's config and channel ends
+type SpawnPlayer = Box<
+    dyn FnOnce(Config, mpsc::UnboundedReceiver<SpotifyCommand>, mpsc::UnboundedSender<SpotifyEvent>)
+        + Send,
+>;
+
+/// A player to connect to, driven from a background task once it's up
+struct PlayerSource {
+    spawn: SpawnPlayer,
+}
+
+/// The app's ends of the command/event channels to a player task
+struct PlayerLink {
+    tx: mpsc::UnboundedSender<SpotifyCommand>,
+    rx: mpsc::UnboundedReceiver<SpotifyEvent>,
+}
+
+impl PlayerSource {
+    /// The player `connect` resolves to, or a disconnected status when it fails
+    fn new<P: Player + 'static>(connect: impl Future<Output = Result<P>> + Send + 'static) -> Self {
+        Self {
+            spawn: Box::new(|config, cmd_rx, track_tx| {
+                tokio::spawn(async move {
+                    match connect.await {
+                        Ok(player) => player_background_task(player, config, cmd_rx, track_tx).await,
+                        // Not configured, let panels explain how to connect
+                        Err(_) => {
+                            let _ = track_tx.send(SpotifyEvent::Status(SpotifyStatus::Disconnected));
+                        }
+                    }
+                });
+            }),
+        }
+    }
+
+    /// Spawn the background task driving this player
+    fn spawn(self, config: &Config) -> PlayerLink {
+        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<SpotifyCommand>();
+        let (track_tx, track_rx) = mpsc::unbounded_channel::<SpotifyEvent>();
+
+        (self.spawn)(config.clone(), cmd_rx, track_tx);
+
+        PlayerLink {
+            tx: cmd_tx,
+            rx: track_rx,
+        }
+    }
+}
+
+impl Sources {
+    /// Real audio capture, tracked repos and the Spotify Web API
+    pub fn live(config: &Config) -> Result<Self> {
+        let analysis = AnalysisOptions::from_config(&config.audio)?;
+        Ok(Self {
+            audio: AudioSource::new(&config.audio.device, analysis),
+            git: GitSource::Tracker(GitTracker::new(&config.git)),
+            player: PlayerSource::new({
+                let config = config.clone();
+                async move { SpotifyClient::new(&config).await }
+            }),
+            github: GitHubClient::new(&config.github).map(GitHubSource::Api),
+            content: Content {
+                lyrics: LyricsSource::Lrclib,
+                art: ArtSource::Download(ImageCache::new()),
+                store: ConfigStore::File,
+            },
+        })
+    }
+
+    /// Synthetic audio, repos and playback for `phosphor demo`
+    pub fn demo(config: &Config) -> Result<Self> {
+        let analysis = AnalysisOptions::from_config(&config.audio)?;
+        Ok(Self {
+            audio: AudioSource::Mock(MockAudioCapture::new(analysis)),
+            git: GitSource::Static {
+                repos: demo::repos(),
+                commits: demo::commits(),
+                details: demo::repo_details(),
+                activity: demo::activity(),
+            },
+            player: PlayerSource::new(async { Ok(DemoPlayer::new()) }),
+            github: Some(GitHubSource::Static(demo::notifications())),
+            content: Content {
+                lyrics: LyricsSource::Static(demo::lyrics()),
+                art: ArtSource::Static(demo::album_art()),
+                store: ConfigStore::ReadOnly("the demo leaves the config alone"),
+            },
+        })
+    }
 }
 
 struct App {
     config: Config,
     theme: Theme,
+    layout: GridLayout,
+    /// Terminal size, for fitting the layout outside of drawing
+    screen: Rect,
+    /// Panels as last drawn, reused until something they show changes
+    panel_cache: PanelCache,
+    /// Whether the terminal window has focus, as far as it reports
+    terminal_focused: bool,
+    /// Running slow with the audio capture paused, for `ui.power_save`
+    power_saving: bool,
     audio: AudioSource,
     audio_smoother: SmoothedAudio,
-    git: GitTracker,
+    /// Falling peak markers over the spectrum bars
+    peaks: SpectrumPeaks,
+    /// Spectrum auto gain, None when `audio.agc` is off
+    gain: Option<AutoGain>,
+    /// Bar levels for other programs, None unless `audio.output` is set
+    output: Option<RawOutput>,
+    /// Recent spectra for the spectrogram
+    spectrum_history: SpectrumHistory,
+    beat: BeatDetector,
+    /// Configured accent, which `ui.beat_accent` brightens on beats
+    accent: Color,
+    git_tx: mpsc::UnboundedSender<GitCommand>,
+    git_rx: mpsc::UnboundedReceiver<GitEvent>,
+    /// No git update has arrived yet
+    git_loading: bool,
+    /// Highlighted repo while the git panel is focused
+    git_selected: usize,
+    /// Repo drilled into with Enter, and its details once they arrive
+    git_expanded: Option<PathBuf>,
+    git_details: Option<RepoDetails>,
     track_info: Option<TrackInfo>,
     audio_data: AudioData,
     repo_statuses: Vec<RepoStatus>,
     commits: Vec<CommitInfo>,
+    /// Commits per day for the heatmap, once the git task has read them
+    activity: Option<Activity>,
     focused_panel: Panel,
     show_help: bool,
-    last_git_update: Instant,
+    keymap: Keymap,
+    /// Keys of a chord typed so far
+    pending_keys: Vec<KeyCode>,
     volume: u8,
+    /// Where volume changes go ("spotify", "pulse", ...), once the player is up
+    volume_backend: Option<&'static str>,
     spotify_tx: mpsc::UnboundedSender<SpotifyCommand>,
-    spotify_rx: mpsc::UnboundedReceiver<Option<TrackInfo>>,
+    spotify_rx: mpsc::UnboundedReceiver<SpotifyEvent>,
+    spotify_status: SpotifyStatus,
     // Album art
-    image_cache: ImageCache,
+    art_source: ArtSource,
     current_album_art: Option<DynamicImage>,
     last_album_art_url: Option<String>,
+    /// Downloads finished by background tasks, keyed by URL
+    art_tx: mpsc::UnboundedSender<(String, Option<DynamicImage>)>,
+    art_rx: mpsc::UnboundedReceiver<(String, Option<DynamicImage>)>,
+    art_loading: bool,
     art_style: ArtStyle,
+    /// Channel mode and frequency scale of the visualizers
+    visuals: VisualizerOptions,
     // Lyrics
+    lyrics_source: LyricsSource,
     lyrics_status: LyricsStatus,
     current_lyrics: Option<SyncedLyrics>,
     last_lyrics_track: Option<(String, String)>,
+    /// Lookups finished by background tasks, keyed by (track, artist)
+    lyrics_tx: mpsc::UnboundedSender<((String, String), LyricsStatus)>,
+    lyrics_rx: mpsc::UnboundedReceiver<((String, String), LyricsStatus)>,
     show_lyrics: bool,
-    last_spotify_poll: Instant,
-    last_known_progress_ms: u64,
-    was_playing: bool,
+    /// Line picked by scrolling the lyrics, and when it last moved
+    lyrics_cursor: Option<(usize, Instant)>,
+    clock: PlaybackClock,
+    // Queue
+    queue: Option<Vec<QueueItem>>,
+    queue_selected: usize,
+    last_queue_refresh: Instant,
+    // History
+    history: Option<Vec<HistoryItem>>,
+    history_selected: usize,
+    last_history_refresh: Instant,
+    // GitHub
+    /// Channels to the notifications task, None without a token
+    github: Option<GitHubLink>,
+    /// None until the first fetch comes back
+    notifications: Option<Vec<Notification>>,
+    notifications_error: Option<String>,
+    notifications_selected: usize,
+    /// What the git cell shows: git, or the queue / history toggled over it
+    git_cell: Panel,
+    /// Open search overlay
+    search: Option<SearchState>,
+    /// Open device popup
+    device_picker: Option<DevicePicker>,
+    // Sleep timer
+    sleep_at: Option<Instant>,
+    /// Index into SLEEP_PRESETS of the running timer
+    sleep_preset: Option<usize>,
+    /// Screen dimmed after the timer went off, until the next key
+    dimmed: bool,
+    /// Last key press or click, for the screensaver
+    last_input: Instant,
+    /// Scanlines, glow and flicker, None when all are off
+    crt: Option<CrtEffects>,
+    /// Notices and errors, shown as toasts and kept for the `M` scrollback
+    toasts: Toasts,
+    show_messages: bool,
+    /// Messages scrolled past in the scrollback
+    messages_scroll: usize,
+    /// Where saving panels from the dashboard writes to
+    config_store: ConfigStore,
 }
 
 impl App {
-    async fn new(config: Config) -> Result<Self> {
+    /// Build the app state without touching the terminal, network or disk, so it
+    /// can be driven by any player link (real task or scripted channels)
+    fn new(
+        config: Config,
+        layout: GridLayout,
+        audio: AudioSource,
+        git: GitLink,
+        player: PlayerLink,
+        content: Content,
+        visuals: VisualizerOptions,
+    ) -> Self {
         let theme = Theme::from_config(&config.theme);
+        let accent = theme.accent;
+        let crt = CrtEffects::from_config(&config.ui.crt);
 
-        // Initialize audio capture
-        let audio = AudioSource::new(&config.audio.device, config.audio.fft_size);
+        // Request initial track info
+        let _ = player.tx.send(SpotifyCommand::Refresh);
 
-        // Initialize git tracker
-        let git = GitTracker::new(&config.git.repos);
+        // Fast attack and slower decay by default, for nice visuals
+        let smoothing = &config.audio.smoothing;
+        let audio_smoother = SmoothedAudio::new(
+            config.audio.fft_size,
+            smoothing.attack,
+            smoothing.decay,
+            smoothing.waveform,
+        );
 
-        // Set up channels for async Spotify communication
-        let (cmd_tx, cmd_rx) = mpsc::unbounded_channel::<SpotifyCommand>();
-        let (track_tx, track_rx) = mpsc::unbounded_channel::<Option<TrackInfo>>();
+        // Start focused on the top-left panel
+        let focused_panel = layout.rows()[0][0];
 
-        // Spawn background Spotify task
-        let config_clone = config.clone();
-        tokio::spawn(async move {
-            spotify_background_task(config_clone, cmd_rx, track_tx).await;
-        });
-
-        // Request initial track info
-        let _ = cmd_tx.send(SpotifyCommand::Refresh);
+        let (art_tx, art_rx) = mpsc::unbounded_channel();
+        let (lyrics_tx, lyrics_rx) = mpsc::unbounded_channel();
 
-        // Smoother with fast attack (0.6) and slower decay (0.15) for nice visuals
-        let audio_smoother = SmoothedAudio::new(config.audio.fft_size, 0.6, 0.15);
-
-        let mut app = Self {
+        Self {
             theme,
+            layout,
             audio,
             audio_smoother,
-            git,
+            peaks: SpectrumPeaks::new(config.audio.spectrum.gravity),
+            gain: config
+                .audio
+                .agc
+                .enabled
+                .then(|| AutoGain::new(config.audio.agc.attack, config.audio.agc.release)),
+            output: None,
+            screen: Rect::default(),
+            panel_cache: PanelCache::default(),
+            terminal_focused: true,
+            power_saving: false,
+            spectrum_history: SpectrumHistory::new(SPECTROGRAM_FRAMES),
+            beat: BeatDetector::new(),
+            accent,
+            git_tx: git.tx,
+            git_rx: git.rx,
+            git_loading: true,
+            git_selected: 0,
+            git_expanded: None,
+            git_details: None,
             track_info: None,
-            audio_data: AudioData {
-                spectrum: vec![0.0; config.audio.fft_size / 2],
-                waveform: vec![0.0; config.audio.fft_size],
-            },
+            audio_data: AudioData::silent(config.audio.fft_size),
             repo_statuses: Vec::new(),
             commits: Vec::new(),
-            focused_panel: Panel::Spotify,
+            activity: None,
+            focused_panel,
             show_help: false,
-            last_git_update: Instant::now() - Duration::from_secs(10),
+            keymap: Keymap::default(),
+            pending_keys: Vec::new(),
             volume: 50,
+            volume_backend: None,
             config,
-            spotify_tx: cmd_tx,
-            spotify_rx: track_rx,
+            spotify_tx: player.tx,
+            spotify_rx: player.rx,
+            spotify_status: SpotifyStatus::Connecting,
             // Album art
-            image_cache: ImageCache::new(),
-            current_album_art: None,
+            current_album_art: content.art.fixed(),
+            art_source: content.art,
             last_album_art_url: None,
+            art_tx,
+            art_rx,
+            art_loading: false,
             art_style: ArtStyle::Braille,
+            visuals,
             // Lyrics
+            lyrics_source: content.lyrics,
             lyrics_status: LyricsStatus::NotFound,
             current_lyrics: None,
             last_lyrics_track: None,
+            lyrics_tx,
+            lyrics_rx,
             show_lyrics: true,
-            last_spotify_poll: Instant::now(),
-            last_known_progress_ms: 0,
-            was_playing: false,
-        };
+            lyrics_cursor: None,
+            clock: PlaybackClock::new(),
+            queue: Some(Vec::new()),
+            queue_selected: 0,
+            last_queue_refresh: Instant::now() - QUEUE_REFRESH,
+            history: Some(Vec::new()),
+            history_selected: 0,
+            last_history_refresh: Instant::now() - HISTORY_REFRESH,
+            github: None,
+            notifications: None,
+            notifications_error: None,
+            notifications_selected: 0,
+            git_cell: Panel::Git,
+            search: None,
+            device_picker: None,
+            sleep_at: None,
+            sleep_preset: None,
+            dimmed: false,
+            last_input: Instant::now(),
+            crt,
+            toasts: Toasts::default(),
+            show_messages: false,
+            messages_scroll: 0,
+            config_store: content.store,
+        }
+    }
 
-        // Initial git fetch
-        app.update_git();
+    /// Draw `panels` afresh next frame rather than reusing their last one
+    fn mark_dirty(&mut self, panels: &[Panel]) {
+        for &panel in panels {
+            self.panel_cache.mark(panel);
+        }
+    }
 
-        Ok(app)
+    /// Panels that move along with playback on their own: the progress bar,
+    /// the sung lyrics line and the sleep countdown
+    fn mark_playback_dirty(&mut self) {
+        let playing = self.track_info.as_ref().is_some_and(|track| track.is_playing);
+        if playing || self.sleep_at.is_some() {
+            self.mark_dirty(&[Panel::Spotify, Panel::Lyrics]);
+        }
     }
 
     fn poll_spotify(&mut self) {
         // Non-blocking receive of track updates from background task
-        while let Ok(track_info) = self.spotify_rx.try_recv() {
+        while let Ok(event) = self.spotify_rx.try_recv() {
+            self.mark_dirty(&[Panel::Spotify, Panel::Lyrics, Panel::AlbumArt, Panel::Queue, Panel::History]);
+            let track_info = match event {
+                SpotifyEvent::Status(status) => {
+                    self.spotify_status = status;
+                    continue;
+                }
+                SpotifyEvent::SearchResults(query, results) => {
+                    // Ignore results for a query the overlay has moved on from
+                    if let Some(search) = self.search.as_mut()
+                        && search.searched.as_deref() == Some(query.as_str())
+                    {
+                        search.pending = false;
+                        match results {
+                            Ok(results) => search.results = results,
+                            Err(error) => search.error = Some(error),
+                        }
+                    }
+                    continue;
+                }
+                SpotifyEvent::Devices(result) => {
+                    if let Some(picker) = self.device_picker.as_mut().filter(|p| !p.audio) {
+                        match result {
+                            Ok(devices) if devices.is_empty() => {
+                                picker.message =
+                                    Some("No devices found - open Spotify on a device first".to_string());
+                            }
+                            Ok(devices) => {
+                                // Start on the device that is playing now
+                                picker.selected = devices.iter().position(|d| d.is_active).unwrap_or(0);
+                                picker.devices = devices;
+                                picker.message = None;
+                            }
+                            Err(error) => picker.message = Some(error),
+                        }
+                    }
+                    continue;
+                }
+                SpotifyEvent::Notice(message) => {
+                    self.notify(message);
+                    continue;
+                }
+                SpotifyEvent::Error(message) => {
+                    self.notify_error(message);
+                    continue;
+                }
+                SpotifyEvent::Volume(backend, level) => {
+                    self.volume_backend = Some(backend);
+                    if let Some(level) = level {
+                        self.volume = level;
+                    }
+                    continue;
+                }
+                SpotifyEvent::History(history) => {
+                    if let Some(ref history) = history {
+                        self.history_selected =
+                            self.history_selected.min(history.len().saturating_sub(1));
+                    }
+                    self.history = history;
+                    continue;
+                }
+                SpotifyEvent::Queue(queue) => {
+                    if let Some(ref queue) = queue {
+                        self.queue_selected = self.queue_selected.min(queue.len().saturating_sub(1));
+                    }
+                    self.queue = queue;
+                    continue;
+                }
+                SpotifyEvent::Track(track_info) => track_info,
+            };
+
             // Check if album art URL changed
             let new_url = track_info.as_ref().and_then(|t| t.album_art_url.clone());
             if new_url != self.last_album_art_url {
                 self.last_album_art_url = new_url.clone();
-                // Fetch new album art
-                self.current_album_art = new_url
-                    .as_ref()
-                    .and_then(|url| self.image_cache.get_or_fetch(url));
+                self.load_album_art(new_url);
             }
 
             // Track progress for lyrics interpolation
             if let Some(ref track) = track_info {
-                self.last_known_progress_ms = track.progress.unwrap_or(0);
-                self.was_playing = track.is_playing;
-                self.last_spotify_poll = Instant::now();
+                self.clock.sync(track);
 
                 // Check if track changed for lyrics
                 let track_key = (track.name.clone(), track.artist.clone());
                 if self.last_lyrics_track.as_ref() != Some(&track_key) {
                     self.last_lyrics_track = Some(track_key);
-                    self.lyrics_status = LyricsStatus::Loading;
-                    self.current_lyrics = None;
-
-                    // Fetch lyrics
-                    let status = fetch_lyrics(
-                        &track.name,
-                        &track.artist,
-                        &track.album,
-                        track.duration / 1000, // Convert ms to seconds
-                    );
-                    if let LyricsStatus::Available(ref lyrics) = status {
-                        self.current_lyrics = Some(lyrics.clone());
-                    }
-                    self.lyrics_status = status;
+                    self.load_lyrics(track);
+                    // The queue and history moved on with the track
+                    self.last_queue_refresh = Instant::now() - QUEUE_REFRESH;
+                    self.last_history_refresh = Instant::now() - HISTORY_REFRESH;
                 }
             }
 
@@ -204,174 +873,1225 @@ impl App {
         }
     }
 
-    fn current_progress_ms(&self) -> u64 {
-        if !self.was_playing {
-            return self.last_known_progress_ms;
+    /// Show cached art right away, otherwise download it in the background
+    fn load_album_art(&mut self, url: Option<String>) {
+        // A fixed cover stays whatever plays
+        let ArtSource::Download(cache) = &self.art_source else {
+            return;
+        };
+        let cache = cache.clone();
+        self.current_album_art = url.as_deref().and_then(|url| cache.get(url));
+        self.art_loading = false;
+
+        let Some(url) = url else {
+            return;
+        };
+        if self.current_album_art.is_some() {
+            return;
         }
-        let elapsed = self.last_spotify_poll.elapsed().as_millis() as u64;
-        self.last_known_progress_ms + elapsed
+
+        self.art_loading = true;
+        let tx = self.art_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let image = cache.get_or_fetch(&url);
+            let _ = tx.send((url, image));
+        });
+    }
+
+    fn poll_album_art(&mut self) {
+        while let Ok((url, image)) = self.art_rx.try_recv() {
+            self.mark_dirty(&[Panel::AlbumArt]);
+            // Ignore downloads for a track that has already been skipped
+            if self.last_album_art_url.as_deref() == Some(url.as_str()) {
+                self.current_album_art = image;
+                self.art_loading = false;
+            }
+        }
+    }
+
+    fn load_lyrics(&mut self, track: &TrackInfo) {
+        self.lyrics_status = LyricsStatus::Loading;
+        self.current_lyrics = None;
+
+        if let LyricsSource::Static(lyrics) = &self.lyrics_source {
+            self.set_lyrics(LyricsStatus::Available(lyrics.clone()));
+            return;
+        }
+        // Podcast episodes never have lyrics
+        if track.is_episode {
+            self.set_lyrics(LyricsStatus::NotFound);
+            return;
+        }
+
+        // LRCLIB can take seconds, so keep showing Loading until the task reports back
+        let key = (track.name.clone(), track.artist.clone());
+        let album = track.album.clone();
+        let duration_secs = track.duration / 1000;
+        let tx = self.lyrics_tx.clone();
+        tokio::task::spawn_blocking(move || {
+            let status = fetch_lyrics(&key.0, &key.1, &album, duration_secs);
+            let _ = tx.send((key, status));
+        });
+    }
+
+    fn poll_lyrics(&mut self) {
+        while let Ok((key, status)) = self.lyrics_rx.try_recv() {
+            self.mark_dirty(&[Panel::Lyrics]);
+            // Drop results for a track that is no longer playing
+            if self.last_lyrics_track.as_ref() == Some(&key) {
+                if let LyricsStatus::Error(ref e) = status {
+                    self.notify_error(format!("Lyrics fetch failed: {}", e));
+                }
+                self.set_lyrics(status);
+            }
+        }
+    }
+
+    fn set_lyrics(&mut self, status: LyricsStatus) {
+        self.current_lyrics = match status {
+            LyricsStatus::Available(ref lyrics) => Some(lyrics.clone()),
+            _ => None,
+        };
+        self.lyrics_status = status;
+        self.lyrics_cursor = None;
+    }
+
+    /// The scrolled-to lyrics line, until auto-follow takes over again
+    fn lyrics_cursor(&self) -> Option<usize> {
+        self.lyrics_cursor
+            .filter(|(_, moved)| moved.elapsed() < LYRICS_FOLLOW)
+            .map(|(line, _)| line)
+    }
+
+    /// Move the lyrics cursor, starting from the line being sung
+    fn scroll_lyrics(&mut self, lines: isize) {
+        let Some(lyrics) = self.current_lyrics.as_ref().filter(|l| !l.lines.is_empty()) else {
+            return;
+        };
+        let from = self
+            .lyrics_cursor()
+            .or_else(|| lyrics.current_line_index(self.clock.now_ms()))
+            .unwrap_or(0);
+        let line = from.saturating_add_signed(lines).min(lyrics.lines.len() - 1);
+        self.lyrics_cursor = Some((line, Instant::now()));
+    }
+
+    /// Jump playback to the scrolled-to line and follow from there
+    fn seek_to_lyrics_cursor(&mut self) {
+        let line = self.lyrics_cursor().zip(self.current_lyrics.as_ref());
+        if let Some(timestamp) = line.and_then(|(line, lyrics)| lyrics.lines.get(line)).map(|l| l.timestamp_ms) {
+            self.seek_to(timestamp);
+        }
+        self.lyrics_cursor = None;
+    }
+
+    /// Retry the lyrics lookup for the current track (after a failure or bad match)
+    fn refetch_lyrics(&mut self) {
+        if let Some(track) = self.track_info.clone() {
+            self.last_lyrics_track = Some((track.name.clone(), track.artist.clone()));
+            self.load_lyrics(&track);
+        }
+    }
+
+    /// Seek relative to the interpolated position, updating the display right away
+    fn seek_by(&mut self, offset_ms: i64) {
+        self.seek_to((self.clock.now_ms() as i64 + offset_ms).max(0) as u64);
+    }
+
+    fn seek_to(&mut self, target: u64) {
+        let Some(track) = self.track_info.as_mut() else {
+            return;
+        };
+        let target = if track.duration > 0 { target.min(track.duration) } else { target };
+
+        track.progress = Some(target);
+        self.clock.seek(target);
+        let _ = self.spotify_tx.send(SpotifyCommand::Seek(target));
+    }
+
+    /// Keep the queue and history current while they are on screen
+    fn update_lists(&mut self) {
+        let visible = self.visible_panels();
+        if visible.contains(&Panel::Queue) && self.last_queue_refresh.elapsed() >= QUEUE_REFRESH {
+            self.last_queue_refresh = Instant::now();
+            let _ = self.spotify_tx.send(SpotifyCommand::RefreshQueue);
+        }
+        if visible.contains(&Panel::History)
+            && self.last_history_refresh.elapsed() >= HISTORY_REFRESH
+        {
+            self.last_history_refresh = Instant::now();
+            let _ = self.spotify_tx.send(SpotifyCommand::RefreshHistory);
+        }
+    }
+
+    /// Show `panel` in the git cell, or git again if it is already there
+    fn toggle_git_cell(&mut self, panel: Panel) {
+        self.git_cell = if self.git_cell == panel { Panel::Git } else { panel };
+        self.last_queue_refresh = Instant::now() - QUEUE_REFRESH;
+        self.last_history_refresh = Instant::now() - HISTORY_REFRESH;
+        self.ensure_focus_visible();
+    }
+
+    fn selected_queue_uri(&self) -> Option<String> {
+        let item = self.queue.as_ref()?.get(self.queue_selected)?;
+        // Local files can't be addressed by URI
+        (!item.uri.is_empty()).then(|| item.uri.clone())
+    }
+
+    fn notify(&mut self, message: impl Into<String>) {
+        self.toasts.push(Level::Info, message.into());
+    }
+
+    fn notify_error(&mut self, message: impl Into<String>) {
+        self.toasts.push(Level::Error, message.into());
+    }
+
+    /// Link to the current track, or a notice saying why there is none
+    fn track_url(&mut self) -> Option<String> {
+        let url = self.track_info.as_ref().and_then(TrackInfo::web_url);
+        if url.is_none() {
+            self.notify("No Spotify link for this track");
+        }
+        url
+    }
+
+    fn copy_track_url(&mut self) {
+        let Some(url) = self.track_url() else {
+            return;
+        };
+        match clipboard::copy(&url) {
+            Ok(target) => self.notify(format!("Link copied to {}", target)),
+            Err(_) => self.notify("Couldn't copy the link"),
+        }
+    }
+
+    fn open_track_url(&mut self) {
+        let Some(url) = self.track_url() else {
+            return;
+        };
+        match open::that_detached(&url) {
+            Ok(()) => self.notify("Opened in browser"),
+            Err(_) => self.notify("Couldn't open a browser"),
+        }
+    }
+
+    /// Step through the sleep timer presets, then off
+    fn cycle_sleep_timer(&mut self) {
+        let next = match self.sleep_preset {
+            None => Some(0),
+            Some(i) if i + 1 < SLEEP_PRESETS.len() => Some(i + 1),
+            Some(_) => None,
+        };
+        self.sleep_preset = next;
+        self.sleep_at = next.map(|i| Instant::now() + SLEEP_PRESETS[i]);
+    }
+
+    fn sleep_remaining(&self) -> Option<Duration> {
+        self.sleep_at.map(|at| at.saturating_duration_since(Instant::now()))
     }
 
-    fn update_git(&mut self) {
-        if self.last_git_update.elapsed() < Duration::from_secs(30) {
+    /// Pause once the sleep timer runs out
+    fn update_sleep(&mut self) {
+        if self.sleep_at.is_none_or(|at| Instant::now() < at) {
             return;
         }
-        self.last_git_update = Instant::now();
+        self.sleep_at = None;
+        self.sleep_preset = None;
+        let _ = self.spotify_tx.send(SpotifyCommand::Pause);
+        self.dimmed = self.config.ui.sleep_dim;
+    }
 
-        self.repo_statuses = self.git.get_status().unwrap_or_default();
-        self.commits = self
-            .git
-            .get_recent_commits(self.config.git.max_commits)
-            .unwrap_or_default();
+    /// Repos then commits, which the git panel's selection moves through
+    fn git_items(&self) -> usize {
+        self.repo_statuses.len() + self.commits.len()
+    }
+
+    fn poll_git(&mut self) {
+        while let Ok(event) = self.git_rx.try_recv() {
+            self.mark_dirty(&[Panel::Git, Panel::Heatmap]);
+            match event {
+                GitEvent::Update(update) => {
+                    self.repo_statuses = update.repos;
+                    self.commits = update.commits;
+                    if update.activity.is_some() {
+                        self.activity = update.activity;
+                    }
+                    self.git_loading = false;
+                    self.git_selected = self.git_selected.min(self.git_items().saturating_sub(1));
+                    if !update.fetch_failed.is_empty() {
+                        self.notify_error(format!("git fetch failed: {}", update.fetch_failed.join(", ")));
+                    }
+                }
+                // Drop details of a repo that was collapsed in the meantime
+                GitEvent::Details(details) => {
+                    if self.git_expanded.as_ref() == Some(&details.path) {
+                        self.git_details = Some(details);
+                    }
+                }
+            }
+        }
+    }
+
+    fn poll_github(&mut self) {
+        while let Some(event) = self.github.as_mut().and_then(|link| link.rx.try_recv().ok()) {
+            self.mark_dirty(&[Panel::GitHub]);
+            match event {
+                GitHubEvent::Notifications(Ok(notifications)) => {
+                    self.notifications_selected =
+                        self.notifications_selected.min(notifications.len().saturating_sub(1));
+                    self.notifications = Some(notifications);
+                    self.notifications_error = None;
+                }
+                GitHubEvent::Notifications(Err(e)) => {
+                    // Once per new error rather than on every poll
+                    if self.notifications_error.as_ref() != Some(&e) {
+                        self.notify_error(e.clone());
+                    }
+                    self.notifications_error = Some(e);
+                }
+                GitHubEvent::Error(message) => self.notify_error(message),
+            }
+        }
+    }
+
+    /// Text shown in place of the notifications list
+    fn notifications_message(&self) -> Option<&str> {
+        if self.github.is_none() {
+            Some("Set github.token (or GITHUB_TOKEN) to see notifications")
+        } else if let Some(ref error) = self.notifications_error {
+            Some(error)
+        } else if self.notifications.is_none() {
+            Some("Loading notifications...")
+        } else {
+            None
+        }
+    }
+
+    /// Drop the selected notification from the list and mark it read on GitHub
+    fn mark_notification_read(&mut self) {
+        let (Some(link), Some(notifications)) = (self.github.as_ref(), self.notifications.as_mut())
+        else {
+            return;
+        };
+        if self.notifications_selected >= notifications.len() {
+            return;
+        }
+        let notification = notifications.remove(self.notifications_selected);
+        self.notifications_selected =
+            self.notifications_selected.min(notifications.len().saturating_sub(1));
+        let _ = link.tx.send(GitHubCommand::MarkRead(notification.id));
     }
 
     fn force_update_git(&mut self) {
-        self.last_git_update = Instant::now() - Duration::from_secs(60);
-        self.update_git();
+        let _ = self.git_tx.send(GitCommand::Refresh);
+    }
+
+    /// Drill into the highlighted repo, or back out to the list (None)
+    fn expand_git(&mut self, path: Option<PathBuf>) {
+        self.git_details = None;
+        self.git_expanded = path.clone();
+        let _ = self.git_tx.send(GitCommand::Expand(path));
+    }
+
+    /// Slow down and pause the audio capture while the terminal is in the
+    /// background or nothing plays, and pick back up when that changes
+    fn update_power_save(&mut self) {
+        let playing = self.track_info.as_ref().is_some_and(|track| track.is_playing);
+        let save = self.config.ui.power_save && (!self.terminal_focused || !playing);
+        if save == self.power_saving {
+            return;
+        }
+        self.power_saving = save;
+        if save {
+            self.audio.pause();
+        } else if let Err(e) = self.audio.resume() {
+            self.notify_error(format!("Audio capture didn't resume: {}", e));
+        }
     }
 
     fn update_audio(&mut self) {
-        let raw_data = self.audio.get_data();
+        if self.power_saving {
+            // Nothing new is captured
+            return;
+        }
+        let mut raw_data = self.audio.get_data();
+        if self.config.audio.trigger {
+            raw_data.trigger();
+        }
+        self.beat.update(&raw_data.spectrum);
+        if self.config.ui.beat_accent {
+            self.theme.accent = self.theme.brighten(self.accent, self.beat.level() * 0.5);
+            // Every border and highlight follows the beat
+            self.panel_cache.mark_all();
+        } else if self.config.ui.beat_pulse {
+            self.mark_dirty(&[Panel::Spotify]);
+        }
+        let frame = Duration::from_secs(1) / self.config.audio.fps.max(1);
+        if let Some(gain) = self.gain.as_mut() {
+            gain.apply(&mut raw_data, frame, |hz| self.visuals.weight(hz));
+        }
         self.audio_data = self.audio_smoother.update(&raw_data);
+        self.peaks.update(&self.audio_data, frame);
+        self.spectrum_history.push(&self.audio_data.spectrum);
+        if let Some(output) = &self.output {
+            output.send(&self.visuals.levels(&self.audio_data, output.bars(), self.gain.is_some()));
+        }
+    }
+
+    /// How long the screensaver has been up, None while it's off
+    fn screensaver_time(&self) -> Option<Duration> {
+        let idle = Duration::from_secs(self.config.ui.screensaver_mins * 60);
+        let elapsed = self.last_input.elapsed();
+        (!idle.is_zero() && elapsed >= idle).then(|| elapsed - idle)
+    }
+
+    /// Note some input, returning whether it only woke the screensaver
+    fn wake(&mut self) -> bool {
+        let asleep = self.screensaver_time().is_some();
+        self.last_input = Instant::now();
+        asleep
+    }
+
+    /// A key from the terminal, returning true to quit. Modifiers are checked
+    /// here so Ctrl+C doesn't run whatever `c` is bound to
+    fn handle_key_event(&mut self, key: KeyEvent) -> bool {
+        match Press::from_event(key) {
+            Press::Interrupt => true,
+            Press::Key(code) => self.handle_key(code),
+            Press::Unbound => false,
+        }
     }
 
     fn handle_key(&mut self, code: KeyCode) -> bool {
-        match code {
-            KeyCode::Char('q') | KeyCode::Esc => {
+        // Focus, selections and toggles can change any panel
+        self.panel_cache.mark_all();
+        if self.wake() {
+            return false;
+        }
+        // The first key after the sleep timer only wakes the screen
+        if self.dimmed {
+            self.dimmed = false;
+            return false;
+        }
+        if self.search.is_some() {
+            self.handle_search_key(code);
+            return false;
+        }
+        if self.device_picker.is_some() {
+            self.handle_device_key(code);
+            return false;
+        }
+
+        // Navigation in the focused panel comes first, except mid-chord
+        if self.pending_keys.is_empty() && self.handle_panel_key(code) {
+            return false;
+        }
+
+        self.pending_keys.push(code);
+        match self.keymap.lookup(&self.pending_keys) {
+            Lookup::Action(action) => {
+                self.pending_keys.clear();
+                self.run_action(action)
+            }
+            Lookup::Pending => false,
+            // A chord gone wrong starts over from the key that broke it
+            Lookup::None if self.pending_keys.len() > 1 => {
+                self.pending_keys.clear();
+                self.handle_key(code)
+            }
+            Lookup::None => {
+                self.pending_keys.clear();
+                false
+            }
+        }
+    }
+
+    /// Returns true to quit
+    fn run_action(&mut self, action: Action) -> bool {
+        match action {
+            Action::Quit => {
                 if self.show_help {
                     self.show_help = false;
+                } else if self.show_messages {
+                    self.show_messages = false;
                 } else {
-                    return true; // Quit
+                    return true;
                 }
             }
-            KeyCode::Char('?') => {
+            Action::Help => {
                 self.show_help = !self.show_help;
             }
-            KeyCode::Tab => {
-                self.focused_panel = self.focused_panel.next();
+            Action::Messages => {
+                self.show_messages = !self.show_messages;
+                self.messages_scroll = 0;
             }
-            KeyCode::Char(' ') => {
+            Action::FocusNext => {
+                self.cycle_focus(true);
+            }
+            Action::FocusPrev => {
+                self.cycle_focus(false);
+            }
+            Action::PlayPause => {
                 let _ = self.spotify_tx.send(SpotifyCommand::TogglePlayback);
             }
-            KeyCode::Char('n') => {
+            Action::Next => {
                 let _ = self.spotify_tx.send(SpotifyCommand::Next);
             }
-            KeyCode::Char('p') => {
+            Action::Prev => {
                 let _ = self.spotify_tx.send(SpotifyCommand::Prev);
             }
-            KeyCode::Char('+') | KeyCode::Char('=') => {
+            Action::VolumeUp => {
                 self.volume = (self.volume + 5).min(100);
                 let _ = self.spotify_tx.send(SpotifyCommand::SetVolume(self.volume));
             }
-            KeyCode::Char('-') => {
-                self.volume = self.volume.saturating_sub(5);
-                let _ = self.spotify_tx.send(SpotifyCommand::SetVolume(self.volume));
+            Action::VolumeDown => {
+                self.volume = self.volume.saturating_sub(5);
+                let _ = self.spotify_tx.send(SpotifyCommand::SetVolume(self.volume));
+            }
+            Action::ToggleSaved => {
+                // Flip the marker right away; the task confirms it on the next poll
+                if let Some(saved) = self.track_info.as_mut().and_then(|t| t.saved.as_mut()) {
+                    *saved = !*saved;
+                    let _ = self.spotify_tx.send(SpotifyCommand::ToggleSaved);
+                }
+            }
+            Action::SeekBack => self.seek_by(-(SEEK_STEP_MS as i64)),
+            Action::SeekForward => self.seek_by(SEEK_STEP_MS as i64),
+            Action::GitRefresh => {
+                self.force_update_git();
+            }
+            Action::ArtStyle => {
+                // Toggle album art style
+                self.art_style = match self.art_style {
+                    ArtStyle::Blocks => ArtStyle::Braille,
+                    ArtStyle::Braille => ArtStyle::Blocks,
+                };
+            }
+            Action::ToggleLyrics => {
+                // Toggle lyrics display
+                self.show_lyrics = !self.show_lyrics;
+                self.ensure_focus_visible();
+            }
+            Action::RefetchLyrics => {
+                if self.focused_panel == Panel::Lyrics {
+                    self.refetch_lyrics();
+                }
+            }
+            Action::Search => {
+                self.search = Some(SearchState::default());
+            }
+            Action::Devices => {
+                self.device_picker = Some(DevicePicker {
+                    message: Some("Loading devices...".to_string()),
+                    ..Default::default()
+                });
+                let _ = self.spotify_tx.send(SpotifyCommand::ListDevices);
+            }
+            Action::AudioDevices => self.open_audio_devices(),
+            Action::SleepTimer => self.cycle_sleep_timer(),
+            Action::Channels => self.visuals.channels = self.visuals.channels.next(),
+            Action::SpectrumMode => self.visuals.mode = self.visuals.mode.next(),
+            Action::WaveformStyle => self.visuals.waveform = self.visuals.waveform.next(),
+            Action::FftSize => {
+                let analysis = self.audio.analysis().next_fft_size();
+                self.audio.set_analysis(analysis);
+                self.notify(format!("FFT size: {}", analysis.fft_size));
+            }
+            Action::CopyUrl => self.copy_track_url(),
+            Action::OpenUrl => self.open_track_url(),
+            Action::Radio => {
+                self.notify("Starting radio...");
+                let _ = self.spotify_tx.send(SpotifyCommand::Radio);
+            }
+            Action::ToggleQueue => self.toggle_git_cell(Panel::Queue),
+            Action::ToggleHistory => self.toggle_git_cell(Panel::History),
+            Action::ToggleGitHub => self.toggle_git_cell(Panel::GitHub),
+            Action::TogglePanel(number) => match self.layout.toggle(number) {
+                Ok((panel, shown)) => {
+                    self.ensure_focus_visible();
+                    let verb = if shown { "Showing" } else { "Hid" };
+                    self.notify(format!("{} {} ({})", verb, panel.name(), number));
+                }
+                Err(e) => self.notify(e.to_string()),
+            },
+            Action::SavePanels => self.save_hidden_panels(),
+        }
+        false
+    }
+
+    /// Write the hidden panels to `layout.hidden`
+    fn save_hidden_panels(&mut self) {
+        let hidden = self.layout.hidden_names();
+        match self.config_store.update(|config| config.layout.hidden = hidden) {
+            Ok(()) => self.notify("Saved hidden panels to the config"),
+            Err(e) => self.notify(format!("Couldn't save the config: {:#}", e)),
+        }
+    }
+
+    /// Fixed keys for moving around the focused panel, returning whether the
+    /// key was used
+    fn handle_panel_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Esc if self.show_help => {
+                self.show_help = false;
+            }
+            KeyCode::Esc if self.show_messages => {
+                self.show_messages = false;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.show_messages => {
+                self.messages_scroll = (self.messages_scroll + 1).min(self.toasts.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.show_messages => {
+                self.messages_scroll = self.messages_scroll.saturating_sub(1);
+            }
+            KeyCode::Esc if self.focused_panel == Panel::Git && self.git_expanded.is_some() => {
+                self.expand_git(None);
+            }
+            KeyCode::Down | KeyCode::Char('j')
+                if self.focused_panel == Panel::Git && self.git_expanded.is_none() =>
+            {
+                self.git_selected = (self.git_selected + 1).min(self.git_items().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k')
+                if self.focused_panel == Panel::Git && self.git_expanded.is_none() =>
+            {
+                self.git_selected = self.git_selected.saturating_sub(1);
+            }
+            KeyCode::Enter if self.focused_panel == Panel::Git => {
+                let path = self.repo_statuses.get(self.git_selected).map(|r| r.path.clone());
+                if path.is_some() {
+                    self.expand_git(path);
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.focused_panel == Panel::Lyrics => {
+                self.scroll_lyrics(1);
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.focused_panel == Panel::Lyrics => {
+                self.scroll_lyrics(-1);
+            }
+            KeyCode::PageDown if self.focused_panel == Panel::Lyrics => {
+                self.scroll_lyrics(LYRICS_PAGE);
+            }
+            KeyCode::PageUp if self.focused_panel == Panel::Lyrics => {
+                self.scroll_lyrics(-LYRICS_PAGE);
+            }
+            KeyCode::Enter if self.focused_panel == Panel::Lyrics && self.lyrics_cursor().is_some() => {
+                self.seek_to_lyrics_cursor();
+            }
+            KeyCode::Esc if self.focused_panel == Panel::Lyrics && self.lyrics_cursor().is_some() => {
+                self.lyrics_cursor = None;
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.focused_panel == Panel::Queue => {
+                let len = self.queue.as_ref().map_or(0, |q| q.len());
+                self.queue_selected = (self.queue_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.focused_panel == Panel::Queue => {
+                self.queue_selected = self.queue_selected.saturating_sub(1);
+            }
+            KeyCode::Enter if self.focused_panel == Panel::Queue => {
+                if let Some(uri) = self.selected_queue_uri() {
+                    let index = self.queue_selected;
+                    let _ = self.spotify_tx.send(SpotifyCommand::JumpTo { index, uri });
+                    self.queue_selected = 0;
+                }
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.focused_panel == Panel::Queue => {
+                if let Some(uri) = self.selected_queue_uri() {
+                    let index = self.queue_selected;
+                    let _ = self.spotify_tx.send(SpotifyCommand::RemoveFromQueue { index, uri });
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.focused_panel == Panel::History => {
+                let len = self.history.as_ref().map_or(0, |h| h.len());
+                self.history_selected = (self.history_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.focused_panel == Panel::History => {
+                self.history_selected = self.history_selected.saturating_sub(1);
+            }
+            KeyCode::Enter if self.focused_panel == Panel::History => {
+                // Replay the selected track
+                let item = self.history.as_ref().and_then(|h| h.get(self.history_selected));
+                if let Some(item) = item {
+                    let _ = self.spotify_tx.send(SpotifyCommand::PlayUri(item.uri.clone()));
+                }
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.focused_panel == Panel::GitHub => {
+                let len = self.notifications.as_ref().map_or(0, |n| n.len());
+                self.notifications_selected =
+                    (self.notifications_selected + 1).min(len.saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') if self.focused_panel == Panel::GitHub => {
+                self.notifications_selected = self.notifications_selected.saturating_sub(1);
+            }
+            KeyCode::Char('x') | KeyCode::Delete if self.focused_panel == Panel::GitHub => {
+                self.mark_notification_read();
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Clicks focus the panel under the pointer (seeking when they land on
+    /// the progress bar) and the wheel moves through its list like ↑/↓
+    fn handle_mouse(&mut self, mouse: MouseEvent, area: Rect) {
+        if mouse.kind == MouseEventKind::Moved {
+            return;
+        }
+        self.panel_cache.mark_all();
+        if self.wake() {
+            return;
+        }
+        let key = match mouse.kind {
+            MouseEventKind::ScrollDown => KeyCode::Down,
+            MouseEventKind::ScrollUp => KeyCode::Up,
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.handle_click(mouse.column, mouse.row, area);
+                return;
+            }
+            _ => return,
+        };
+        // Overlays and the dimmed screen take the wheel as they do the arrows
+        let overlay = self.dimmed
+            || self.show_help
+            || self.show_messages
+            || self.search.is_some()
+            || self.device_picker.is_some();
+        if !overlay {
+            let Some((panel, _)) = self.panel_at(mouse.column, mouse.row, area) else {
+                return;
+            };
+            self.focused_panel = panel;
+        }
+        self.handle_key(key);
+    }
+
+    fn handle_click(&mut self, column: u16, row: u16, area: Rect) {
+        if self.dimmed {
+            self.dimmed = false;
+            return;
+        }
+        if self.search.is_some() || self.device_picker.is_some() {
+            return;
+        }
+        self.show_help = false;
+        self.show_messages = false;
+
+        let Some((panel, cell)) = self.panel_at(column, row, area) else {
+            return;
+        };
+        self.focused_panel = panel;
+        if panel == Panel::Spotify {
+            let target = self
+                .track_info
+                .as_ref()
+                .and_then(|track| SpotifyWidget::seek_target(track, &self.theme, cell, column, row));
+            if let Some(target) = target {
+                self.seek_to(target);
+            }
+        }
+    }
+
+    /// The panel drawn at a screen position, and its area
+    fn panel_at(&self, column: u16, row: u16, area: Rect) -> Option<(Panel, Rect)> {
+        let position = Position::new(column, row);
+        self.panel_areas(area).into_iter().find(|(_, cell)| cell.contains(position))
+    }
+
+    /// Keys while the search overlay is open: typing edits the query, Enter
+    /// searches (or plays the selection once results are in)
+    fn handle_search_key(&mut self, code: KeyCode) {
+        let Some(search) = self.search.as_mut() else {
+            return;
+        };
+
+        match code {
+            KeyCode::Esc => self.search = None,
+            KeyCode::Enter => {
+                let query = search.query.trim().to_string();
+                if search.searched.as_deref() == Some(query.as_str()) && !search.results.is_empty() {
+                    let selected = search.selected.min(search.results.len() - 1);
+                    let uri = search.results[selected].uri.clone();
+                    let _ = self.spotify_tx.send(SpotifyCommand::PlayUri(uri));
+                    self.search = None;
+                } else if !query.is_empty() {
+                    search.results.clear();
+                    search.selected = 0;
+                    search.error = None;
+                    search.pending = true;
+                    search.searched = Some(query.clone());
+                    let _ = self.spotify_tx.send(SpotifyCommand::Search(query));
+                }
+            }
+            KeyCode::Down => {
+                search.selected = (search.selected + 1).min(search.results.len().saturating_sub(1));
+            }
+            KeyCode::Up => {
+                search.selected = search.selected.saturating_sub(1);
+            }
+            KeyCode::Backspace => {
+                search.query.pop();
+            }
+            KeyCode::Char(c) => {
+                search.query.push(c);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_device_key(&mut self, code: KeyCode) {
+        let Some(picker) = self.device_picker.as_mut() else {
+            return;
+        };
+
+        match code {
+            KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('d') | KeyCode::Char('D') => {
+                self.device_picker = None
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                picker.selected = (picker.selected + 1).min(picker.devices.len().saturating_sub(1));
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                picker.selected = picker.selected.saturating_sub(1);
+            }
+            KeyCode::Enter if picker.audio => {
+                if let Some(device) = picker.devices.get(picker.selected) {
+                    let name = device.id.clone();
+                    self.device_picker = None;
+                    match self.audio.switch_device(&name) {
+                        Ok(()) => self.notify(format!("Audio: {}", name)),
+                        Err(e) => self.notify(format!("Audio: {:#}", e)),
+                    }
+                }
+            }
+            KeyCode::Enter => {
+                if let Some(device) = picker.devices.get(picker.selected) {
+                    let _ = self.spotify_tx.send(SpotifyCommand::Transfer(device.id.clone()));
+                    self.device_picker = None;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Capture sources come straight from the audio backend, so the list is
+    /// filled right away rather than through the Spotify task
+    fn open_audio_devices(&mut self) {
+        let mut picker = DevicePicker {
+            audio: true,
+            ..Default::default()
+        };
+        match self.audio.devices() {
+            Ok(names) if names.is_empty() => {
+                picker.message = Some("No capture devices found".to_string());
+            }
+            Ok(names) => {
+                let backend = self.audio.backend();
+                let current = self.audio.device_name();
+                picker.devices = names
+                    .into_iter()
+                    .map(|name| DeviceInfo {
+                        is_active: current == Some(name.as_str()),
+                        id: name.clone(),
+                        name,
+                        kind: backend.to_string(),
+                        volume: None,
+                    })
+                    .collect();
+                picker.selected = picker.devices.iter().position(|d| d.is_active).unwrap_or(0);
+            }
+            Err(e) => picker.message = Some(e.to_string()),
+        }
+        self.device_picker = Some(picker);
+    }
+
+    /// What a layout cell shows right now (`l` swaps lyrics and album art,
+    /// `u` / `h` / `g` show the queue / history / notifications in place of git)
+    fn display_panel(&self, panel: Panel) -> Panel {
+        match panel {
+            Panel::Git => self.git_cell,
+            Panel::Lyrics | Panel::AlbumArt if !self.show_lyrics => panel.swapped(),
+            _ => panel,
+        }
+    }
+
+    /// Panels rendered this frame, in screen order (left to right, top to bottom)
+    fn visible_panels(&self) -> Vec<Panel> {
+        let mut visible = Vec::new();
+        for &panel in self.fitted_rows(self.dashboard_area(self.screen)).iter().flatten() {
+            let panel = self.display_panel(panel);
+            if !visible.contains(&panel) {
+                visible.push(panel);
+            }
+        }
+        visible
+    }
+
+    /// Move focus to the next (or previous) panel on screen
+    fn cycle_focus(&mut self, forward: bool) {
+        let visible = self.visible_panels();
+        let len = visible.len();
+        if len == 0 {
+            return;
+        }
+        self.focused_panel = match visible.iter().position(|&p| p == self.focused_panel) {
+            Some(i) if forward => visible[(i + 1) % len],
+            Some(i) => visible[(i + len - 1) % len],
+            None => visible[0],
+        };
+    }
+
+    /// The terminal changed size: panels may have made way (or come back)
+    fn resize(&mut self, screen: Rect) {
+        self.screen = screen;
+        self.panel_cache.mark_all();
+        self.ensure_focus_visible();
+    }
+
+    /// Move focus off a panel that just got hidden
+    fn ensure_focus_visible(&mut self) {
+        let visible = self.visible_panels();
+        if !visible.contains(&self.focused_panel) {
+            // Follow the swap so focus stays in the same cell
+            let focused = self.focused_panel;
+            let cell = self.layout.rows().iter().flatten().copied().find(|&panel| {
+                panel == focused
+                    || panel.swapped() == focused
+                    || (panel == Panel::Git
+                        && matches!(focused, Panel::Queue | Panel::History | Panel::GitHub))
+            });
+            self.focused_panel = cell
+                .map(|panel| self.display_panel(panel))
+                .filter(|panel| visible.contains(panel))
+                .unwrap_or(visible[0]);
+        }
+    }
+
+    /// Rows with a flexible panel share the leftover space; the rest get the
+    /// tallest fixed height among their panels
+    fn row_constraint(&self, row: &[Panel], height: u16) -> Constraint {
+        let layout = &self.config.layout;
+        let mut fixed = 0;
+        for &panel in row {
+            let rows = match panel {
+                Panel::Spotify if height < COMPACT_HEIGHT => SPOTIFY_COMPACT_ROWS,
+                Panel::Spotify => SPOTIFY_ROWS,
+                Panel::Heatmap => HEATMAP_ROWS,
+                // Visualizers take a share of the screen, kept within their configured rows
+                Panel::Spectrum => panel_rows(&layout.spectrum, 20, height),
+                Panel::Spectrogram => panel_rows(&layout.spectrogram, 20, height),
+                Panel::Waveform => panel_rows(&layout.waveform, 20, height),
+                Panel::Scope => panel_rows(&layout.scope, 20, height),
+                Panel::Git
+                | Panel::Lyrics
+                | Panel::AlbumArt
+                | Panel::Queue
+                | Panel::History
+                | Panel::GitHub => {
+                    return Constraint::Min(if height < COMPACT_HEIGHT { MIN_PANEL_ROWS } else { 10 });
+                }
+            };
+            fixed = fixed.max(rows);
+        }
+        Constraint::Length(fixed)
+    }
+
+    /// The screen less the status bar's line, when it's shown
+    fn dashboard_area(&self, area: Rect) -> Rect {
+        if self.config.ui.status_bar {
+            Rect { height: area.height.saturating_sub(1), ..area }
+        } else {
+            area
+        }
+    }
+
+    /// Whether `area` is big enough to draw the dashboard in at all
+    fn fits(area: Rect) -> bool {
+        area.width >= MIN_SIZE.0 && area.height >= MIN_SIZE.1
+    }
+
+    /// The layout's rows less whatever doesn't fit in `area`: the lowest
+    /// priority panels go first, from a row too narrow for its panels if
+    /// there is one, until the rows' minimum heights fit
+    fn fitted_rows(&self, area: Rect) -> Vec<Vec<Panel>> {
+        let mut rows = self.layout.rows();
+        if area.width == 0 || area.height == 0 {
+            // Not sized yet
+            return rows;
+        }
+        loop {
+            let needed: u16 = rows
+                .iter()
+                .map(|row| match self.row_constraint(row, area.height) {
+                    Constraint::Length(rows) => rows,
+                    Constraint::Min(rows) => rows.min(MIN_PANEL_ROWS),
+                    _ => 0,
+                })
+                .sum();
+            let narrow = rows.iter().position(|row| row.len() > 1 && area.width / (row.len() as u16) < MIN_PANEL_WIDTH);
+            if (needed <= area.height && narrow.is_none()) || rows.iter().flatten().count() <= 1 {
+                return rows;
+            }
+
+            let candidates = match narrow {
+                Some(row) => vec![row],
+                None => (0..rows.len()).collect(),
+            };
+            let Some((row, column)) = candidates
+                .into_iter()
+                .flat_map(|row| (0..rows[row].len()).map(move |column| (row, column)))
+                .min_by_key(|&(row, column)| rows[row][column].priority())
+            else {
+                return rows;
+            };
+            rows[row].remove(column);
+            rows.retain(|row| !row.is_empty());
+        }
+    }
+
+    /// Where each layout cell goes in `area` and what it shows: rows stacked
+    /// vertically, each split into equal columns
+    fn panel_areas(&self, area: Rect) -> Vec<(Panel, Rect)> {
+        let area = self.dashboard_area(area);
+        if !Self::fits(area) {
+            return Vec::new();
+        }
+        let rows = self.fitted_rows(area);
+        let row_constraints = rows.iter().map(|row| self.row_constraint(row, area.height));
+        let row_areas = Layout::vertical(row_constraints).split(area);
+
+        let mut areas = Vec::new();
+        for (row, &row_area) in rows.iter().zip(row_areas.iter()) {
+            let column = Constraint::Ratio(1, row.len() as u32);
+            let columns = Layout::horizontal(vec![column; row.len()]).split(row_area);
+            for (&panel, &cell) in row.iter().zip(columns.iter()) {
+                areas.push((self.display_panel(panel), cell));
+            }
+        }
+        areas
+    }
+
+    fn draw_panel(&self, frame: &mut Frame, panel: Panel, area: Rect) {
+        let focused = self.focused_panel == panel;
+        match panel {
+            Panel::Spotify => {
+                let spotify_widget = SpotifyWidget::new(
+                    self.track_info.as_ref(),
+                    self.spotify_status,
+                    &self.theme,
+                    focused,
+                    self.config.ui.beat_pulse.then(|| self.beat.level()),
+                )
+                .sleep(self.sleep_remaining())
+                .volume_backend(self.volume_backend)
+                .bpm(self.beat.bpm());
+                frame.render_widget(spotify_widget, area);
+            }
+            Panel::Git => {
+                let git_widget = GitWidget::new(
+                    &self.repo_statuses,
+                    &self.commits,
+                    &self.config.git,
+                    &self.theme,
+                    focused,
+                )
+                .loading(self.git_loading)
+                .selected(focused.then_some(self.git_selected))
+                .expanded(self.git_expanded.as_ref(), self.git_details.as_ref());
+                frame.render_widget(git_widget, area);
+            }
+            Panel::Spectrum => {
+                let spectrum_widget = SpectrumWidget::new(&self.audio_data, &self.theme, focused)
+                    .channels(self.visuals.channels)
+                    .mode(self.visuals.mode)
+                    .scale(self.visuals.scale)
+                    .shape(self.visuals.shape)
+                    .peaks((self.config.audio.spectrum.gravity > 0.0).then_some(&self.peaks))
+                    .auto_gain(self.gain.is_some());
+                frame.render_widget(spectrum_widget, area);
+            }
+            Panel::Spectrogram => {
+                let spectrogram_widget =
+                    SpectrogramWidget::new(&self.spectrum_history, &self.theme, focused)
+                        .sample_rate(self.audio_data.sample_rate)
+                        .scale(self.visuals.scale)
+                        .shape(self.visuals.shape);
+                frame.render_widget(spectrogram_widget, area);
+            }
+            Panel::Waveform => {
+                let waveform_widget = WaveformWidget::new(&self.audio_data, &self.theme, focused)
+                    .channels(self.visuals.channels)
+                    .style(self.visuals.waveform);
+                frame.render_widget(waveform_widget, area);
+            }
+            Panel::Scope => {
+                let scope_widget = ScopeWidget::new(&self.audio_data, &self.theme, focused);
+                frame.render_widget(scope_widget, area);
+            }
+            Panel::Lyrics => {
+                let lyrics_widget = LyricsWidget::new(
+                    self.current_lyrics.as_ref(),
+                    &self.lyrics_status,
+                    self.clock.now_ms(),
+                    self.spotify_status,
+                    self.track_info.is_some(),
+                    &self.theme,
+                    focused,
+                )
+                .config(&self.config.lyrics)
+                .cursor(self.lyrics_cursor());
+                frame.render_widget(lyrics_widget, area);
+            }
+            Panel::AlbumArt => {
+                let album_art_widget = AlbumArtWidget::new(
+                    self.current_album_art.as_ref(),
+                    self.spotify_status,
+                    self.track_info.is_some(),
+                    &self.theme,
+                    focused,
+                    self.art_style,
+                )
+                .config(&self.config.albumart)
+                .loading(self.art_loading);
+                frame.render_widget(album_art_widget, area);
             }
-            KeyCode::Char('r') => {
-                self.force_update_git();
+            Panel::Queue => {
+                let queue_widget = QueueWidget::new(
+                    self.queue.as_deref(),
+                    self.queue_selected,
+                    self.spotify_status,
+                    &self.theme,
+                    focused,
+                );
+                frame.render_widget(queue_widget, area);
             }
-            KeyCode::Char('a') => {
-                // Toggle album art style
-                self.art_style = match self.art_style {
-                    ArtStyle::Blocks => ArtStyle::Braille,
-                    ArtStyle::Braille => ArtStyle::Blocks,
-                };
+            Panel::History => {
+                let history_widget = HistoryWidget::new(
+                    self.history.as_deref(),
+                    self.history_selected,
+                    self.spotify_status,
+                    &self.theme,
+                    focused,
+                );
+                frame.render_widget(history_widget, area);
             }
-            KeyCode::Char('l') => {
-                // Toggle lyrics display
-                self.show_lyrics = !self.show_lyrics;
+            Panel::Heatmap => {
+                let heatmap_widget = HeatmapWidget::new(self.activity.as_ref(), &self.theme, focused);
+                frame.render_widget(heatmap_widget, area);
+            }
+            Panel::GitHub => {
+                let notifications_widget = NotificationsWidget::new(
+                    self.notifications.as_deref().unwrap_or_default(),
+                    self.notifications_selected,
+                    self.notifications_message(),
+                    &self.theme,
+                    focused,
+                );
+                frame.render_widget(notifications_widget, area);
             }
-            _ => {}
         }
-        false
     }
 
-    fn draw(&self, frame: &mut Frame) {
+    /// Status bar hints: how to finish a chord being typed, or else the help key
+    fn key_hints(&self) -> Vec<(String, String)> {
+        if self.pending_keys.is_empty() {
+            return self
+                .keymap
+                .keys(Action::Help)
+                .into_iter()
+                .take(1)
+                .map(|key| (key, "help".to_string()))
+                .collect();
+        }
+
+        let mut hints = vec![(describe_keys(&self.pending_keys), "…".to_string())];
+        for (keys, action) in self.keymap.continuations(&self.pending_keys) {
+            hints.push((keys, action.replace('_', " ")));
+        }
+        hints
+    }
+
+    /// What the terminal is and what it needs to be, in place of the dashboard
+    fn draw_too_small(&self, frame: &mut Frame, area: Rect) {
+        let lines = vec![
+            Line::styled("Terminal too small", Style::default().fg(self.theme.accent)),
+            Line::styled(format!("{}×{}", area.width, area.height), Style::default().fg(self.theme.foreground)),
+            Line::styled(
+                format!("need {}×{}", MIN_SIZE.0, MIN_SIZE.1 + self.config.ui.status_bar as u16),
+                Style::default().fg(self.theme.dim),
+            ),
+        ];
+        let y = area.y + area.height.saturating_sub(lines.len() as u16) / 2;
+        let text_area = Rect { y, height: area.bottom() - y, ..area };
+        frame.render_widget(Paragraph::new(lines).alignment(Alignment::Center).wrap(Wrap { trim: true }), text_area);
+    }
+
+    /// The clock and spectrum alone, drifting around the screen
+    fn draw_screensaver(&self, frame: &mut Frame, time: Duration) {
         let area = frame.area();
+        let width = (area.width * 2 / 3).max(area.width.min(40));
+        let height = (area.height * 2 / 3).max(area.height.min(CLOCK_ROWS + 8));
 
+        // Each axis swings edge to edge and back on its own period
+        let swing = |period: f32| 0.5 - 0.5 * (time.as_secs_f32() / period * std::f32::consts::TAU).cos();
+        let x = area.x + ((area.width - width) as f32 * swing(DRIFT_PERIOD.0)).round() as u16;
+        let y = area.y + ((area.height - height) as f32 * swing(DRIFT_PERIOD.1)).round() as u16;
+        let drift = Rect::new(x, y, width, height);
+
+        let [clock_area, spectrum_area] =
+            Layout::vertical([Constraint::Length(CLOCK_ROWS + 1), Constraint::Min(0)]).areas(drift);
+        let track = self.track_info.as_ref();
+        frame.render_widget(ScreensaverWidget::new(&self.theme, chrono::Local::now(), track), clock_area);
+        let spectrum_widget = SpectrumWidget::new(&self.audio_data, &self.theme, false)
+            .channels(self.visuals.channels)
+            .mode(self.visuals.mode)
+            .scale(self.visuals.scale)
+            .shape(self.visuals.shape)
+            .auto_gain(self.gain.is_some());
+        frame.render_widget(spectrum_widget, spectrum_area);
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
         // Fill entire background
-        for y in area.y..area.y + area.height {
-            for x in area.x..area.x + area.width {
-                frame.buffer_mut()[(x, y)]
-                    .set_bg(self.theme.background)
-                    .set_char(' ');
-            }
-        }
-
-        // Stacked vertical layout: Spotify, Lyrics/AlbumArt, Spectrum, Waveform
-        let rows = if self.show_lyrics {
-            Layout::vertical([
-                Constraint::Length(9),      // Spotify - fixed height for track info
-                Constraint::Min(10),        // Lyrics - same size as album art
-                Constraint::Percentage(20), // Spectrum
-                Constraint::Percentage(20), // Waveform
-            ])
-            .split(area)
-        } else {
-            Layout::vertical([
-                Constraint::Length(9),      // Spotify - fixed height for track info
-                Constraint::Percentage(20), // Spectrum - smaller
-                Constraint::Percentage(20), // Waveform - smaller
-                Constraint::Min(10),        // Album Art - more space
-            ])
-            .split(area)
-        };
+        fill_background(frame, &self.theme);
 
-        // Render Spotify widget
-        let spotify_widget = SpotifyWidget::new(
-            self.track_info.as_ref(),
-            &self.theme,
-            self.focused_panel == Panel::Spotify,
-        );
-        frame.render_widget(spotify_widget, rows[0]);
-
-        if self.show_lyrics {
-            // Lyrics mode: Lyrics, Spectrum, Waveform
-            let lyrics_widget = LyricsWidget::new(
-                self.current_lyrics.as_ref(),
-                &self.lyrics_status,
-                self.current_progress_ms(),
-                &self.theme,
-                self.focused_panel == Panel::Lyrics,
-            );
-            frame.render_widget(lyrics_widget, rows[1]);
+        match self.screensaver_time() {
+            Some(time) => self.draw_screensaver(frame, time),
+            None => self.draw_dashboard(frame),
+        }
+        if let Some(ref crt) = self.crt {
+            crt.apply(frame.buffer_mut(), &self.theme);
+        }
+    }
 
-            let spectrum_widget = SpectrumWidget::new(
-                &self.audio_data,
-                &self.theme,
-                self.focused_panel == Panel::Spectrum,
-            );
-            frame.render_widget(spectrum_widget, rows[2]);
+    fn draw_dashboard(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        if !Self::fits(self.dashboard_area(area)) {
+            self.draw_too_small(frame, area);
+            return;
+        }
+        for (panel, cell) in self.panel_areas(area) {
+            if !self.panel_cache.restore(panel, cell, frame.buffer_mut()) {
+                self.draw_panel(frame, panel, cell);
+                self.panel_cache.store(panel, cell, frame.buffer_mut());
+            }
+        }
 
-            let waveform_widget = WaveformWidget::new(
-                &self.audio_data,
-                &self.theme,
-                self.focused_panel == Panel::Waveform,
-            );
-            frame.render_widget(waveform_widget, rows[3]);
-        } else {
-            // Album art mode: Spectrum, Waveform, AlbumArt
-            let spectrum_widget = SpectrumWidget::new(
-                &self.audio_data,
-                &self.theme,
-                self.focused_panel == Panel::Spectrum,
-            );
-            frame.render_widget(spectrum_widget, rows[1]);
+        if self.config.ui.status_bar && area.height > 1 {
+            let bar_area = Rect { y: area.bottom() - 1, height: 1, ..area };
+            let status_bar = StatusBarWidget::new(&self.theme, self.audio.backend())
+                .device(self.track_info.as_ref().and_then(|track| track.device.as_deref()))
+                .volume(self.volume, self.volume_backend)
+                .hints(self.key_hints());
+            frame.render_widget(status_bar, bar_area);
+        }
 
-            let waveform_widget = WaveformWidget::new(
-                &self.audio_data,
-                &self.theme,
-                self.focused_panel == Panel::Waveform,
-            );
-            frame.render_widget(waveform_widget, rows[2]);
+        frame.render_widget(ToastsWidget::new(&self.toasts, &self.theme), self.dashboard_area(area));
 
-            let album_art_widget = AlbumArtWidget::new(
-                self.current_album_art.as_ref(),
-                &self.theme,
-                self.focused_panel == Panel::AlbumArt,
-                self.art_style,
-            );
-            frame.render_widget(album_art_widget, rows[3]);
+        if self.show_messages {
+            let messages_area = centered_rect(60, 50, area);
+            frame.render_widget(Clear, messages_area);
+            let messages_widget = MessagesWidget::new(&self.toasts, self.messages_scroll, &self.theme);
+            frame.render_widget(messages_widget, messages_area);
         }
 
         // Render help overlay if active
@@ -379,50 +2099,375 @@ impl App {
             let help_area = centered_rect(40, 50, area);
             frame.render_widget(Clear, help_area);
             let help_block = Block::default()
-                .style(Style::default().bg(self.theme.background));
+                .style(Style::default().bg(self.theme.bg()));
             frame.render_widget(help_block, help_area);
-            let help_widget = HelpWidget::new(&self.theme);
+            let help_widget = HelpWidget::new(&self.theme, &self.keymap);
             frame.render_widget(help_widget, help_area);
         }
+
+        if let Some(ref search) = self.search {
+            let search_area = centered_rect(60, 60, area);
+            frame.render_widget(Clear, search_area);
+            let search_widget = SearchWidget::new(
+                &search.query,
+                &search.results,
+                search.selected,
+                search.message(),
+                &self.theme,
+            );
+            frame.render_widget(search_widget, search_area);
+        }
+
+        if let Some(ref picker) = self.device_picker {
+            let picker_area = centered_rect(50, 40, area);
+            frame.render_widget(Clear, picker_area);
+            let mut devices_widget = DevicesWidget::new(
+                &picker.devices,
+                picker.selected,
+                picker.message.as_deref(),
+                &self.theme,
+            );
+            if picker.audio {
+                devices_widget = devices_widget
+                    .title(" Audio capture ")
+                    .hint(" Enter capture · ↑↓ select · Esc close ");
+            }
+            frame.render_widget(devices_widget, picker_area);
+        }
+
+        if self.dimmed {
+            dim_frame(frame, &self.theme);
+        }
+    }
+}
+
+/// Read the repos right away, then every `GIT_REFRESH` (or their own
+/// `refresh_secs`) or when asked, with a `git fetch` first whenever
+/// `git.fetch_interval_mins` has passed. libgit2
+/// and git run on the blocking pool so a slow repo never stalls the runtime
+async fn git_background_task(
+    tracker: GitTracker,
+    config: GitConfig,
+    heatmap: bool,
+    mut cmd_rx: mpsc::UnboundedReceiver<GitCommand>,
+    event_tx: mpsc::UnboundedSender<GitEvent>,
+) {
+    let tracker = Arc::new(tracker);
+    let max_commits = config.max_commits;
+    let heatmap_weeks = config.heatmap_weeks;
+    let fetch_interval = Duration::from_secs(config.fetch_interval_mins * 60);
+    let mut last_fetch: Option<Instant> = None;
+    let mut fetch_failed = Vec::new();
+    let mut expanded: Option<PathBuf> = None;
+    // Read every repo rather than only those due, after a change or fetch
+    let mut force = true;
+
+    // Without a watcher the sender is dropped and only the schedule refreshes
+    let (changed_tx, mut changed_rx) = mpsc::unbounded_channel::<()>();
+    let _ = tracker.watch(move || changed_tx.send(()).is_ok());
+
+    // Details of the expanded repo, if any; false once the app is gone
+    let send_details = async |expanded: &Option<PathBuf>| {
+        let Some(path) = expanded.clone() else {
+            return true;
+        };
+        let git = tracker.clone();
+        match tokio::task::spawn_blocking(move || git.get_details(&path, max_commits)).await {
+            Ok(Ok(details)) => event_tx.send(GitEvent::Details(details)).is_ok(),
+            _ => true,
+        }
+    };
+
+    loop {
+        let git = tracker.clone();
+        let author = config.heatmap_author.clone();
+        let authors = config.only_authors.clone();
+        let update = tokio::task::spawn_blocking(move || GitUpdate {
+            repos: git.get_status_cached(GIT_REFRESH, force),
+            commits: git.get_recent_commits(max_commits, &authors).unwrap_or_default(),
+            fetch_failed,
+            activity: heatmap.then(|| git.get_activity(heatmap_weeks, &author)),
+        })
+        .await;
+        let Ok(update) = update else {
+            return;
+        };
+        if event_tx.send(GitEvent::Update(update)).is_err() || !send_details(&expanded).await {
+            return;
+        }
+        fetch_failed = Vec::new();
+        force = true;
+        // Presses of `r` during the refresh are covered by it
+        while let Ok(command) = cmd_rx.try_recv() {
+            if let GitCommand::Expand(path) = command {
+                expanded = path;
+                send_details(&expanded).await;
+            }
+        }
+
+        // Fetch after the local read so the panel doesn't wait on the network,
+        // then read again straight away
+        if !fetch_interval.is_zero() && last_fetch.is_none_or(|at| at.elapsed() >= fetch_interval) {
+            last_fetch = Some(Instant::now());
+            let git = tracker.clone();
+            fetch_failed = tokio::task::spawn_blocking(move || git.fetch())
+                .await
+                .unwrap_or_default();
+            continue;
+        }
+
+        // Expanding a repo reads just that one and keeps the refresh schedule
+        let next_refresh = tokio::time::Instant::now() + tracker.refresh_interval(GIT_REFRESH);
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(next_refresh) => {
+                    force = false;
+                    break;
+                }
+                Some(()) = changed_rx.recv() => {
+                    tokio::time::sleep(GIT_SETTLE).await;
+                    while changed_rx.try_recv().is_ok() {}
+                    break;
+                }
+                command = cmd_rx.recv() => match command {
+                    Some(GitCommand::Refresh) => break,
+                    Some(GitCommand::Expand(path)) => {
+                        expanded = path;
+                        if !send_details(&expanded).await {
+                            return;
+                        }
+                    }
+                    // The app is gone
+                    None => return,
+                },
+            }
+        }
+    }
+}
+
+/// Fixed repos for the demo: sent once and never refreshed, with their
+/// details on Enter
+async fn static_git_task(
+    update: GitUpdate,
+    details: Vec<RepoDetails>,
+    mut cmd_rx: mpsc::UnboundedReceiver<GitCommand>,
+    event_tx: mpsc::UnboundedSender<GitEvent>,
+) {
+    if event_tx.send(GitEvent::Update(update)).is_err() {
+        return;
+    }
+
+    while let Some(command) = cmd_rx.recv().await {
+        let GitCommand::Expand(Some(path)) = command else {
+            continue;
+        };
+        let Some(repo) = details.iter().find(|repo| repo.path == path) else {
+            continue;
+        };
+        if event_tx.send(GitEvent::Details(repo.clone())).is_err() {
+            return;
+        }
+    }
+}
+
+/// Poll notifications every GITHUB_REFRESH, marking threads read as asked
+async fn github_background_task(
+    client: GitHubClient,
+    mut cmd_rx: mpsc::UnboundedReceiver<GitHubCommand>,
+    event_tx: mpsc::UnboundedSender<GitHubEvent>,
+) {
+    let client = Arc::new(client);
+
+    loop {
+        let github = client.clone();
+        let fetched =
+            tokio::task::spawn_blocking(move || github.notifications().map_err(|e| e.to_string()))
+                .await;
+        let Ok(notifications) = fetched else {
+            return;
+        };
+        if event_tx.send(GitHubEvent::Notifications(notifications)).is_err() {
+            return;
+        }
+
+        let next_refresh = tokio::time::Instant::now() + GITHUB_REFRESH;
+        loop {
+            tokio::select! {
+                _ = tokio::time::sleep_until(next_refresh) => break,
+                command = cmd_rx.recv() => match command {
+                    Some(GitHubCommand::MarkRead(id)) => {
+                        let github = client.clone();
+                        let marked = tokio::task::spawn_blocking(move || github.mark_read(&id)).await;
+                        // The next refresh brings the notification back
+                        if !matches!(marked, Ok(Ok(()))) {
+                            let error = "Couldn't mark the notification read".to_string();
+                            let _ = event_tx.send(GitHubEvent::Error(error));
+                        }
+                    }
+                    // The app is gone
+                    None => return,
+                },
+            }
+        }
+    }
+}
+
+/// Fixed notifications for the demo; marking one read only drops it from the list
+async fn static_github_task(
+    notifications: Vec<Notification>,
+    mut cmd_rx: mpsc::UnboundedReceiver<GitHubCommand>,
+    event_tx: mpsc::UnboundedSender<GitHubEvent>,
+) {
+    if event_tx.send(GitHubEvent::Notifications(Ok(notifications))).is_err() {
+        return;
     }
+    while cmd_rx.recv().await.is_some() {}
 }
 
-async fn spotify_background_task(
+async fn player_background_task<P: Player>(
+    player: P,
     config: Config,
     mut cmd_rx: mpsc::UnboundedReceiver<SpotifyCommand>,
-    track_tx: mpsc::UnboundedSender<Option<TrackInfo>>,
+    track_tx: mpsc::UnboundedSender<SpotifyEvent>,
 ) {
-    // Initialize Spotify client (may fail if not configured)
-    let spotify = match SpotifyClient::new(&config).await {
-        Ok(client) => client,
-        Err(_) => return, // No Spotify, exit task
-    };
+    let _ = track_tx.send(SpotifyEvent::Status(SpotifyStatus::Connected));
+
+    let mut volume = VolumeControl::new(&config.spotify).unwrap_or_else(|e| {
+        let _ = track_tx.send(SpotifyEvent::Error(e.to_string()));
+        VolumeControl::default()
+    });
+    let _ = track_tx.send(SpotifyEvent::Volume(volume.label(&player), volume.level()));
 
-    let mut last_refresh = Instant::now() - Duration::from_secs(10);
-    let refresh_interval = Duration::from_secs(1);
+    // Poll quickly while playing and back off when paused or idle to save API quota
+    let active_interval = Duration::from_millis(config.spotify.poll_ms.max(250));
+    let idle_interval = Duration::from_secs(config.spotify.idle_poll_secs).max(active_interval);
+    let mut refresh_interval = active_interval;
+    let mut last_refresh = Instant::now() - idle_interval;
+
+    // Spotify has no endpoint to remove queue items, so they are skipped when
+    // reached. They're kept as positions in the last fetched queue, moving up
+    // as it plays, so other plays of the same track aren't skipped too
+    let mut queue: Vec<QueueItem> = Vec::new();
+    let mut removed: BTreeSet<usize> = BTreeSet::new();
+    let mut playing_uri: Option<String> = None;
+    // Album or playlist the current track plays from, for jumping within it
+    let mut context_uri: Option<String> = None;
+    // Library state of the current track, checked once per track
+    let mut saved: Option<(String, bool)> = None;
+    let mut queue_requested = false;
+    let mut poll_error: Option<String> = None;
+    let mut listens = ListenTracker::default();
+    let scrobbler = Scrobbler::new(&config.scrobble);
 
     loop {
         // Process any pending commands (non-blocking)
         while let Ok(cmd) = cmd_rx.try_recv() {
+            // Playback is likely to change after a user command (list refreshes are periodic)
+            if !matches!(cmd, SpotifyCommand::RefreshQueue | SpotifyCommand::RefreshHistory) {
+                refresh_interval = active_interval;
+            }
             match cmd {
+                SpotifyCommand::RefreshQueue => queue_requested = true,
+                SpotifyCommand::RefreshHistory => {
+                    let history = report(&track_tx, player.recently_played(HISTORY_LIMIT).await);
+                    let _ = track_tx.send(SpotifyEvent::History(history));
+                }
+                SpotifyCommand::JumpTo { index, uri } => {
+                    // Start the item rather than skipping through the rest,
+                    // which would play (and scrobble) each. Items without a
+                    // URI (local files) can't be started
+                    if !uri.is_empty()
+                        && let Some(pos) = queue_position(&queue, &removed, index, &uri)
+                    {
+                        // From within the album or playlist, playback carries on
+                        // through all of it, shuffled if it was, and songs queued
+                        // by hand stay queued
+                        let in_context = match &context_uri {
+                            Some(context) => player.play_in_context(context, &uri).await.is_ok(),
+                            None => false,
+                        };
+                        // Otherwise (or when the item was queued by hand) play it
+                        // and what follows it in the queue as a list
+                        if !in_context {
+                            let uris: Vec<&str> = (pos..queue.len())
+                                .filter(|pos| !removed.contains(pos))
+                                .map(|pos| queue[pos].uri.as_str())
+                                .filter(|uri| !uri.is_empty())
+                                .collect();
+                            report(&track_tx, player.play_uris(&uris).await);
+                        }
+                    }
+                    last_refresh = Instant::now() - idle_interval;
+                    queue_requested = true;
+                }
+                SpotifyCommand::RemoveFromQueue { index, uri } => {
+                    if let Some(pos) = queue_position(&queue, &removed, index, &uri) {
+                        removed.insert(pos);
+                    }
+                    queue_requested = true;
+                }
+                SpotifyCommand::Search(query) => {
+                    let results = player.search(&query).await.map_err(|e| e.to_string());
+                    let _ = track_tx.send(SpotifyEvent::SearchResults(query, results));
+                }
+                SpotifyCommand::PlayUri(uri) => {
+                    report(&track_tx, player.play_uri(&uri).await);
+                    last_refresh = Instant::now() - idle_interval;
+                    queue_requested = true;
+                }
+                SpotifyCommand::ToggleSaved => {
+                    if let Some((uri, is_saved)) = saved.as_mut()
+                        && report(&track_tx, player.set_saved(uri, !*is_saved).await).is_some()
+                    {
+                        *is_saved = !*is_saved;
+                        let notice = if *is_saved { "Saved to your library ♥" } else { "Removed from your library" };
+                        let _ = track_tx.send(SpotifyEvent::Notice(notice.to_string()));
+                    }
+                    last_refresh = Instant::now() - idle_interval;
+                }
+                SpotifyCommand::Seek(position_ms) => {
+                    report(&track_tx, player.seek(position_ms).await);
+                    last_refresh = Instant::now() - idle_interval;
+                }
+                SpotifyCommand::ListDevices => {
+                    let devices = player.list_devices().await.map_err(|e| e.to_string());
+                    let _ = track_tx.send(SpotifyEvent::Devices(devices));
+                }
+                SpotifyCommand::Transfer(device_id) => {
+                    report(&track_tx, player.transfer_playback(&device_id).await);
+                    last_refresh = Instant::now() - idle_interval;
+                }
                 SpotifyCommand::Refresh => {
                     // Force refresh on next iteration
-                    last_refresh = Instant::now() - Duration::from_secs(10);
+                    last_refresh = Instant::now() - idle_interval;
                 }
                 SpotifyCommand::TogglePlayback => {
-                    let _ = spotify.toggle_playback().await;
-                    last_refresh = Instant::now() - Duration::from_secs(10);
+                    report(&track_tx, player.toggle_playback().await);
+                    last_refresh = Instant::now() - idle_interval;
+                }
+                SpotifyCommand::Radio => {
+                    if let Some(radio) = report(&track_tx, player.start_radio().await) {
+                        let _ = track_tx.send(SpotifyEvent::Notice(format!("Radio from {}", radio.seed)));
+                    }
+                    last_refresh = Instant::now() - idle_interval;
+                }
+                SpotifyCommand::Pause => {
+                    report(&track_tx, player.pause().await);
+                    last_refresh = Instant::now() - idle_interval;
                 }
                 SpotifyCommand::Next => {
-                    let _ = spotify.next().await;
-                    last_refresh = Instant::now() - Duration::from_secs(10);
+                    report(&track_tx, player.next().await);
+                    last_refresh = Instant::now() - idle_interval;
                 }
                 SpotifyCommand::Prev => {
-                    let _ = spotify.prev().await;
-                    last_refresh = Instant::now() - Duration::from_secs(10);
+                    report(&track_tx, player.prev().await);
+                    last_refresh = Instant::now() - idle_interval;
                 }
                 SpotifyCommand::SetVolume(vol) => {
-                    let _ = spotify.set_volume(vol).await;
+                    if let Err(e) = volume.set(&player, vol).await {
+                        let _ = track_tx.send(SpotifyEvent::Error(format!("Volume: {}", e)));
+                    }
+                    let _ = track_tx.send(SpotifyEvent::Volume(volume.label(&player), None));
                 }
             }
         }
@@ -430,17 +2475,129 @@ async fn spotify_background_task(
         // Periodic track info refresh
         if last_refresh.elapsed() >= refresh_interval {
             last_refresh = Instant::now();
-            let track_info = spotify.get_current_track().await.ok().flatten();
-            if track_tx.send(track_info).is_err() {
+            // A failing poll is reported once, not every few hundred milliseconds
+            let mut track_info = match player.get_current_track().await {
+                Ok(track_info) => {
+                    poll_error = None;
+                    track_info
+                }
+                Err(e) => {
+                    let error = format!("{:#}", e);
+                    if poll_error.as_ref() != Some(&error) {
+                        let _ = track_tx.send(SpotifyEvent::Error(error.clone()));
+                        poll_error = Some(error);
+                    }
+                    None
+                }
+            };
+            player.persist_token().await;
+            refresh_interval = if track_info.as_ref().is_some_and(|t| t.is_playing) {
+                active_interval
+            } else {
+                idle_interval
+            };
+
+            // Episodes can't be liked, so only tracks get a library check
+            if let Some(track) = track_info.as_mut().filter(|t| !t.is_episode)
+                && let Some(uri) = track.uri.clone()
+            {
+                if saved.as_ref().is_none_or(|(saved_uri, _)| *saved_uri != uri) {
+                    saved = player.is_saved(&uri).await.ok().map(|is_saved| (uri, is_saved));
+                }
+                track.saved = saved.as_ref().map(|(_, is_saved)| *is_saved);
+            }
+
+            context_uri = track_info
+                .as_ref()
+                .and_then(|t| t.context.as_ref())
+                .map(|context| context.uri.clone())
+                .filter(|uri| !uri.is_empty());
+
+            let uri = track_info.as_ref().and_then(|t| t.uri.clone());
+            if uri != playing_uri {
+                playing_uri = uri.clone();
+                if uri.is_some() && queue.first().map(|item| &item.uri) == uri.as_ref() {
+                    // Playback moved on to the head of the queue
+                    queue.remove(0);
+                    let skip = removed.remove(&0);
+                    removed = removed.into_iter().map(|pos| pos - 1).collect();
+                    // A removed item just started playing, move past it. It's
+                    // still reported below, until the next poll shows what's on
+                    if skip {
+                        let _ = player.next().await;
+                        last_refresh = Instant::now() - idle_interval;
+                        queue_requested = true;
+                    }
+                } else {
+                    // Something else started, so the queue is a new one
+                    removed.clear();
+                }
+            }
+
+            for event in listens.update(track_info.as_ref()) {
+                if let Some(scrobbler) = &scrobbler {
+                    scrobbler.handle(&event);
+                }
+                if let ListenEvent::Played(listen) = &event
+                    && config.stats.record
+                {
+                    let _ = stats::record(listen);
+                }
+            }
+
+            if track_tx.send(SpotifyEvent::Track(track_info)).is_err() {
                 break; // Main app closed
             }
         }
 
+        if queue_requested {
+            queue_requested = false;
+            let shown = match player.get_queue().await {
+                Ok(fresh) => {
+                    // Removals only hold while the queue still lines up with them
+                    let uri_at = |queue: &[QueueItem], pos: usize| queue.get(pos).map(|item| item.uri.clone());
+                    removed.retain(|&pos| uri_at(&fresh, pos) == uri_at(&queue, pos));
+                    queue = fresh;
+                    let shown = queue
+                        .iter()
+                        .enumerate()
+                        .filter(|(pos, _)| !removed.contains(pos))
+                        .map(|(_, item)| item.clone())
+                        .collect();
+                    Some(shown)
+                }
+                Err(_) => None,
+            };
+            if track_tx.send(SpotifyEvent::Queue(shown)).is_err() {
+                break;
+            }
+        }
+
         // Small sleep to avoid busy-spinning
         tokio::time::sleep(Duration::from_millis(50)).await;
     }
 }
 
+/// Position in `queue` of the `index`th item shown (the removed ones hidden),
+/// if it's still the item with `uri`
+fn queue_position(queue: &[QueueItem], removed: &BTreeSet<usize>, index: usize, uri: &str) -> Option<usize> {
+    (0..queue.len())
+        .filter(|pos| !removed.contains(pos))
+        .nth(index)
+        .filter(|&pos| queue[pos].uri == uri)
+}
+
+/// Pass a command's result through, telling the app when it failed
+fn report<T>(track_tx: &mpsc::UnboundedSender<SpotifyEvent>, result: Result<T>) -> Option<T> {
+    match result {
+        Ok(value) => Some(value),
+        Err(e) => {
+            let _ = track_tx.send(SpotifyEvent::Error(format!("{:#}", e)));
+            None
+        }
+    }
+}
+
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
     let popup_layout = Layout::vertical([
         Constraint::Percentage((100 - percent_y) / 2),
@@ -459,78 +2616,448 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
 
 pub async fn run() -> Result<()> {
     let config = Config::load()?;
+    // Checked before taking over the terminal, like the layout in `run_with`
+    let sources = Sources::live(&config)?;
+    run_with(config, sources).await
+}
+
+/// Run the dashboard on synthetic data with the default config, touching nothing on disk
+pub async fn run_demo() -> Result<()> {
+    let mut config = Config::default();
+    // The demo track on a loop isn't real listening
+    config.stats.record = false;
+    let sources = Sources::demo(&config)?;
+    run_with(config, sources).await
+}
+
+async fn run_with(config: Config, sources: Sources) -> Result<()> {
     let fps = config.audio.fps;
 
-    // Parse background color for terminal clear
-    let bg_color = parse_hex_to_crossterm(&config.theme.background)
-        .unwrap_or(crossterm::style::Color::Rgb { r: 26, g: 16, b: 0 });
+    // Reject a bad layout before taking over the terminal so the error is readable
+    let layout = GridLayout::from_config(&config.layout)?;
+    let visuals = VisualizerOptions::from_config(&config.audio)?;
+    let output = RawOutput::from_config(&config.audio.output)?;
+    let keymap = Keymap::from_config(&config.keys)?;
+    Preset::named(&config.theme.preset).context("Invalid theme.preset")?;
+
+    // Background color for terminal clear; transparent themes keep the
+    // terminal's own background
+    let bg_color = match Theme::from_config(&config.theme).bg() {
+        Color::Rgb(r, g, b) => crossterm::style::Color::Rgb { r, g, b },
+        _ => crossterm::style::Color::Reset,
+    };
 
-    // Setup terminal with background color
+    // Setup terminal with background color. From here on a panic, an error or
+    // a signal still hands the terminal back in a usable state
+    install_panic_hook();
+    let quit = watch_signals();
     enable_raw_mode()?;
+    let _guard = TerminalGuard;
     let mut stdout = io::stdout();
     execute!(
         stdout,
         EnterAlternateScreen,
         SetBackgroundColor(bg_color),
         TermClear(ClearType::All),
-        EnableMouseCapture
+        EnableMouseCapture,
+        EnableFocusChange
     )?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
+    // Show the splash while subsystems initialize
+    let splash_start = Instant::now();
+    let show_splash = config.ui.splash;
+    let boot_sequence = config.ui.boot_sequence;
+    if show_splash {
+        let theme = Theme::from_config(&config.theme);
+        terminal.draw(|f| draw_splash(f, &theme, Duration::ZERO, boot_sequence))?;
+    }
+
     // Create app
-    let mut app = App::new(config).await?;
+    let Sources { audio, git, player, github, content } = sources;
+    let link = player.spawn(&config);
+    let git = git.spawn(&config);
+    let mut app = App::new(config, layout, audio, git, link, content, visuals);
+    app.output = output;
+    app.keymap = keymap;
+    app.github = github.map(GitHubSource::spawn);
+    let size = terminal.size()?;
+    app.resize(Rect::new(0, 0, size.width, size.height));
 
-    let tick_rate = Duration::from_millis(1000 / fps as u64);
-    let mut last_tick = Instant::now();
+    if show_splash {
+        run_splash(&mut terminal, &app.theme, splash_start, boot_sequence)?;
+    }
+
+    // Frames (audio analysis and drawing) at `fps`, everything else at its own pace
+    let frame = Duration::from_secs(1) / fps.max(1);
+    let mut frames = Cadence::new(frame);
+    let mut data = Cadence::new(DATA_POLL);
 
     // Force full redraw on first frame so ratatui's diff buffer is in sync
     terminal.clear()?;
 
-    loop {
-        // Draw
-        terminal.draw(|f| app.draw(f))?;
-
-        // Handle events
-        let timeout = tick_rate.saturating_sub(last_tick.elapsed());
+    while !quit.load(Ordering::Relaxed) {
+        // Input gets an answer straight away rather than at the next frame
+        let mut redraw = false;
+        let timeout = frames.remaining().min(data.remaining());
         if event::poll(timeout)? {
-            if let Event::Key(key) = event::read()? {
-                if key.kind == KeyEventKind::Press {
-                    if app.handle_key(key.code) {
+            redraw = true;
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let quit = app.handle_key_event(key);
+                    if quit {
                         break;
                     }
                 }
+                Event::Mouse(mouse) => {
+                    let size = terminal.size()?;
+                    app.handle_mouse(mouse, Rect::new(0, 0, size.width, size.height));
+                }
+                Event::Resize(width, height) => app.resize(Rect::new(0, 0, width, height)),
+                Event::FocusGained => app.terminal_focused = true,
+                Event::FocusLost => app.terminal_focused = false,
+                _ => {}
             }
         }
 
-        // Update on tick
-        if last_tick.elapsed() >= tick_rate {
-            last_tick = Instant::now();
-            app.update_audio();
+        if data.due() {
+            app.mark_playback_dirty();
             app.poll_spotify(); // Non-blocking check for track updates
-            app.update_git();
+            app.poll_album_art();
+            app.poll_lyrics();
+            app.update_lists();
+            app.update_sleep();
+            app.poll_git();
+            app.poll_github();
+            app.update_power_save();
+            frames.interval = if app.power_saving {
+                Duration::from_secs(1) / POWER_SAVE_FPS
+            } else {
+                frame
+            };
+        }
+
+        if frames.due() {
+            app.update_audio();
+            redraw = true;
+        }
+
+        if redraw {
+            terminal.draw(|f| app.draw(f))?;
         }
     }
 
-    // Restore terminal
+    // `_guard` restores the terminal
+    Ok(())
+}
+
+/// Leave raw mode, the alternate screen and mouse and focus reporting
+fn restore_terminal() -> io::Result<()> {
     disable_raw_mode()?;
     execute!(
-        terminal.backend_mut(),
+        io::stdout(),
         LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+        DisableMouseCapture,
+        DisableFocusChange,
+        ShowCursor
+    )
+}
+
+/// Restores the terminal when dropped, so returning early with an error
+/// doesn't leave the shell in raw mode on the alternate screen
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        let _ = restore_terminal();
+    }
+}
+
+/// Restore the terminal before a panic's message is printed, so it can be
+/// read. Only for the UI thread: a panicking background task is reported
+/// through its channel while the dashboard keeps running
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() == Some("main") {
+            let _ = restore_terminal();
+        }
+        default_hook(info);
+    }));
+}
+
+/// A flag raised by SIGINT, SIGTERM or SIGHUP for the main loop to quit on.
+/// If it hasn't managed to within `SIGNAL_GRACE`, the terminal is restored
+/// here and the process exits
+fn watch_signals() -> Arc<AtomicBool> {
+    let quit = Arc::new(AtomicBool::new(false));
 
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let flag = quit.clone();
+        tokio::spawn(async move {
+            let (Ok(mut interrupt), Ok(mut terminate), Ok(mut hangup)) = (
+                signal(SignalKind::interrupt()),
+                signal(SignalKind::terminate()),
+                signal(SignalKind::hangup()),
+            ) else {
+                return;
+            };
+            tokio::select! {
+                _ = interrupt.recv() => {}
+                _ = terminate.recv() => {}
+                _ = hangup.recv() => {}
+            }
+            flag.store(true, Ordering::Relaxed);
+
+            tokio::time::sleep(SIGNAL_GRACE).await;
+            let _ = restore_terminal();
+            std::process::exit(1);
+        });
+    }
+
+    quit
+}
+
+/// Something the main loop does every `interval`
+struct Cadence {
+    interval: Duration,
+    last: Instant,
+}
+
+impl Cadence {
+    /// Due straight away, then every `interval`
+    fn new(interval: Duration) -> Self {
+        Self { interval, last: Instant::now() - interval }
+    }
+
+    fn remaining(&self) -> Duration {
+        self.interval.saturating_sub(self.last.elapsed())
+    }
+
+    /// Whether it's time again, starting the next wait if so
+    fn due(&mut self) -> bool {
+        if self.last.elapsed() < self.interval {
+            return false;
+        }
+        self.last = Instant::now();
+        true
+    }
+}
+
+/// Keep the splash up for the rest of its duration, fading out at the end,
+/// after the boot sequence if it's on. Any keypress skips straight to the
+/// dashboard.
+fn run_splash(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    theme: &Theme,
+    start: Instant,
+    boot_sequence: bool,
+) -> Result<()> {
+    let duration = if boot_sequence { BOOT_DURATION + SPLASH_DURATION } else { SPLASH_DURATION };
+    while start.elapsed() < duration {
+        terminal.draw(|f| draw_splash(f, theme, start.elapsed(), boot_sequence))?;
+
+        if event::poll(Duration::from_millis(16))?
+            && let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            break;
+        }
+    }
     Ok(())
 }
 
-fn parse_hex_to_crossterm(hex: &str) -> Option<crossterm::style::Color> {
-    let hex = hex.trim_start_matches('#');
-    if hex.len() != 6 {
-        return None;
+/// The splash `elapsed` into it: boot text first when `boot_sequence` is on,
+/// then the logo sweeping in, holding and fading out
+fn draw_splash(frame: &mut Frame, theme: &Theme, elapsed: Duration, boot_sequence: bool) {
+    fill_background(frame, theme);
+    if boot_sequence && elapsed < BOOT_DURATION {
+        frame.render_widget(BootWidget::new(theme, elapsed), frame.area());
+        return;
+    }
+
+    let (elapsed, reveal) = if boot_sequence {
+        let elapsed = elapsed - BOOT_DURATION;
+        (elapsed, elapsed.as_secs_f32() / SPLASH_REVEAL.as_secs_f32())
+    } else {
+        (elapsed, 1.0)
+    };
+    let remaining = SPLASH_DURATION.saturating_sub(elapsed);
+    let brightness = if remaining < SPLASH_FADE {
+        remaining.as_secs_f32() / SPLASH_FADE.as_secs_f32()
+    } else {
+        1.0
+    };
+    frame.render_widget(SplashWidget::new(theme, brightness).reveal(reveal), frame.area());
+}
+
+/// Size a panel as `percent` of the screen, clamped to its configured row limits
+fn panel_rows(rows: &PanelRows, percent: u16, height: u16) -> u16 {
+    let mut len = (height as u32 * percent as u32 / 100) as u16;
+    if rows.max_rows > 0 {
+        len = len.min(rows.max_rows);
+    }
+    len.max(rows.min_rows)
+}
+
+/// Blank every cell, painting the theme background unless it is transparent
+fn fill_background(frame: &mut Frame, theme: &Theme) {
+    let area = frame.area();
+    let bg = theme.bg();
+    for y in area.y..area.y + area.height {
+        for x in area.x..area.x + area.width {
+            frame.buffer_mut()[(x, y)].set_bg(bg).set_char(' ');
+        }
+    }
+}
+
+/// Fade every drawn color towards the background
+fn dim_frame(frame: &mut Frame, theme: &Theme) {
+    let area = frame.area();
+    let buf = frame.buffer_mut();
+    for y in area.y..area.y + area.height {
+        for x in area.x..area.x + area.width {
+            let cell = &mut buf[(x, y)];
+            let fg = theme.fade(cell.fg, SLEEP_DIM);
+            cell.set_fg(fg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeyBinding;
+    use crossterm::event::KeyModifiers;
+
+    /// The app on demo content with its channels held by the test, so no task
+    /// runs and every command it sends can be checked
+    struct Harness {
+        app: App,
+        player_rx: mpsc::UnboundedReceiver<SpotifyCommand>,
+        git_rx: mpsc::UnboundedReceiver<GitCommand>,
+    }
+
+    impl Harness {
+        fn new(keys: &[(&str, &str)]) -> Self {
+            let mut config = Config::default();
+            for (action, binding) in keys {
+                config.keys.insert(action.to_string(), KeyBinding::One(binding.to_string()));
+            }
+            let Sources { audio, content, .. } = Sources::demo(&config).unwrap();
+            let layout = GridLayout::from_config(&config.layout).unwrap();
+            let visuals = VisualizerOptions::from_config(&config.audio).unwrap();
+            let keymap = Keymap::from_config(&config.keys).unwrap();
+
+            let (player_tx, player_rx) = mpsc::unbounded_channel();
+            let (_event_tx, event_rx) = mpsc::unbounded_channel();
+            let (git_tx, git_rx) = mpsc::unbounded_channel();
+            let (_git_event_tx, git_event_rx) = mpsc::unbounded_channel();
+            let player = PlayerLink { tx: player_tx, rx: event_rx };
+            let git = GitLink { tx: git_tx, rx: git_event_rx };
+
+            let mut app = App::new(config, layout, audio, git, player, content, visuals);
+            app.keymap = keymap;
+            app.resize(Rect::new(0, 0, 120, 40));
+            let mut harness = Self { app, player_rx, git_rx };
+            // The initial track request
+            harness.sent();
+            harness
+        }
+
+        /// Press each key in turn, returning whether the last one quit
+        fn press(&mut self, keys: &[KeyCode]) -> bool {
+            keys.iter().fold(false, |_, &key| self.app.handle_key_event(key.into()))
+        }
+
+        /// Commands sent to the player since the last call
+        fn sent(&mut self) -> Vec<SpotifyCommand> {
+            std::iter::from_fn(|| self.player_rx.try_recv().ok()).collect()
+        }
+    }
+
+    #[test]
+    fn tab_cycles_through_the_panels_on_screen() {
+        let mut h = Harness::new(&[]);
+        assert_eq!(h.app.focused_panel, Panel::Spotify);
+        h.press(&[KeyCode::Tab]);
+        assert_eq!(h.app.focused_panel, Panel::Git);
+        h.press(&[KeyCode::Tab]);
+        assert_eq!(h.app.focused_panel, Panel::Lyrics);
+        h.press(&[KeyCode::BackTab, KeyCode::BackTab]);
+        assert_eq!(h.app.focused_panel, Panel::Spotify);
+    }
+
+    #[test]
+    fn hiding_the_lyrics_moves_focus_off_them() {
+        let mut h = Harness::new(&[]);
+        h.press(&[KeyCode::Tab, KeyCode::Tab]);
+        assert_eq!(h.app.focused_panel, Panel::Lyrics);
+        h.press(&[KeyCode::Char('l')]);
+        assert!(!h.app.show_lyrics);
+        assert_ne!(h.app.focused_panel, Panel::Lyrics);
+        h.press(&[KeyCode::Char('l')]);
+        assert!(h.app.show_lyrics);
+    }
+
+    #[test]
+    fn volume_keys_step_and_clamp() {
+        let mut h = Harness::new(&[]);
+        h.press(&[KeyCode::Char('+')]);
+        assert_eq!(h.app.volume, 55);
+        assert!(matches!(h.sent()[..], [SpotifyCommand::SetVolume(55)]));
+        h.press(&[KeyCode::Char('-'), KeyCode::Char('-')]);
+        assert_eq!(h.app.volume, 45);
+        h.press(&[KeyCode::Char('+'); 20]);
+        assert_eq!(h.app.volume, 100);
+        h.press(&[KeyCode::Char('-'); 30]);
+        assert_eq!(h.app.volume, 0);
+    }
+
+    #[test]
+    fn a_switches_the_art_style() {
+        let mut h = Harness::new(&[]);
+        assert!(h.app.art_style == ArtStyle::Braille);
+        h.press(&[KeyCode::Char('a')]);
+        assert!(h.app.art_style == ArtStyle::Blocks);
+        h.press(&[KeyCode::Char('a')]);
+        assert!(h.app.art_style == ArtStyle::Braille);
+    }
+
+    #[test]
+    fn q_closes_help_before_quitting() {
+        let mut h = Harness::new(&[]);
+        assert!(!h.press(&[KeyCode::Char('?'), KeyCode::Char('q')]));
+        assert!(!h.app.show_help);
+        assert!(h.press(&[KeyCode::Char('q')]));
+    }
+
+    #[test]
+    fn ctrl_c_quits_instead_of_running_c() {
+        let mut h = Harness::new(&[]);
+        let ctrl = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL);
+        assert!(h.app.handle_key_event(ctrl('c')));
+        // Other combinations aren't the plain key either
+        assert!(!h.app.handle_key_event(ctrl('l')));
+        assert!(!h.app.handle_key_event(KeyEvent::new(KeyCode::Char('a'), KeyModifiers::ALT)));
+        assert!(h.app.show_lyrics);
+        assert!(h.app.art_style == ArtStyle::Braille);
+    }
+
+    #[test]
+    fn chords_wait_for_their_second_key() {
+        let mut h = Harness::new(&[("git_refresh", "g r"), ("toggle_github", "G")]);
+        h.press(&[KeyCode::Char('g')]);
+        assert!(h.git_rx.try_recv().is_err());
+        h.press(&[KeyCode::Char('r')]);
+        assert!(matches!(h.git_rx.try_recv(), Ok(GitCommand::Refresh)));
+        // A broken chord runs the key that broke it
+        h.press(&[KeyCode::Char('g'), KeyCode::Char('a')]);
+        assert!(h.git_rx.try_recv().is_err());
+        assert!(h.app.art_style == ArtStyle::Blocks);
     }
-    let r = u8::from_str_radix(&hex[0..2], 16).ok()?;
-    let g = u8::from_str_radix(&hex[2..4], 16).ok()?;
-    let b = u8::from_str_radix(&hex[4..6], 16).ok()?;
-    Some(crossterm::style::Color::Rgb { r, g, b })
 }
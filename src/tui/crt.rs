@@ -0,0 +1,126 @@
+//! Old-monitor effects applied to each finished frame: darker alternate
+//! scanlines, a glow bleeding from bright text, and a faint flicker
+
+use ratatui::{buffer::Buffer, style::Color};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::config::CrtConfig;
+use crate::tui::theme::Theme;
+
+/// How far scanlines darken at full intensity
+const SCANLINE_DARKEN: f32 = 0.35;
+
+/// How far a bright cell tints its blank neighbours at full intensity
+const GLOW_TINT: f32 = 0.1;
+
+/// Luminance (0-1) a character needs to glow
+const GLOW_THRESHOLD: f32 = 0.55;
+
+/// Deepest brightness dip of the flicker at full intensity
+const FLICKER_DEPTH: f32 = 0.08;
+
+const BLACK: Color = Color::Rgb(0, 0, 0);
+
+pub struct CrtEffects {
+    scanlines: bool,
+    glow: bool,
+    flicker: bool,
+    intensity: f32,
+}
+
+impl CrtEffects {
+    /// None when every effect is off
+    pub fn from_config(config: &CrtConfig) -> Option<Self> {
+        (config.scanlines || config.glow || config.flicker).then(|| Self {
+            scanlines: config.scanlines,
+            glow: config.glow,
+            flicker: config.flicker,
+            intensity: config.intensity.clamp(0.0, 1.0),
+        })
+    }
+
+    pub fn apply(&self, buf: &mut Buffer, theme: &Theme) {
+        if self.glow {
+            self.apply_glow(buf, theme);
+        }
+
+        let flicker = if self.flicker { frame_noise() * FLICKER_DEPTH * self.intensity } else { 0.0 };
+        let area = buf.area;
+        for y in area.top()..area.bottom() {
+            // Rows alternate from the top of the screen, not of each panel
+            let scanline = if self.scanlines && (y - area.y) % 2 == 1 {
+                SCANLINE_DARKEN * self.intensity
+            } else {
+                0.0
+            };
+            let darken = scanline + flicker;
+            if darken <= 0.0 {
+                continue;
+            }
+            for x in area.left()..area.right() {
+                let cell = &mut buf[(x, y)];
+                // Reset colors belong to the terminal (transparent backgrounds)
+                if let Color::Rgb(..) = cell.fg {
+                    cell.fg = theme.mix(cell.fg, BLACK, darken);
+                }
+                if let Color::Rgb(..) = cell.bg {
+                    cell.bg = theme.mix(cell.bg, BLACK, darken);
+                }
+            }
+        }
+    }
+
+    /// Tint the background of blank cells next to bright characters towards
+    /// their color
+    fn apply_glow(&self, buf: &mut Buffer, theme: &Theme) {
+        let area = buf.area;
+        let mut bright = Vec::new();
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = &buf[(x, y)];
+                if cell.symbol() != " " && luminance(cell.fg) >= GLOW_THRESHOLD {
+                    bright.push((x, y, cell.fg));
+                }
+            }
+        }
+
+        let tint = GLOW_TINT * self.intensity;
+        for (x, y, color) in bright {
+            let neighbours = [
+                (x.checked_sub(1), Some(y)),
+                (x.checked_add(1), Some(y)),
+                (Some(x), y.checked_sub(1)),
+                (Some(x), y.checked_add(1)),
+            ];
+            for (nx, ny) in neighbours {
+                let (Some(nx), Some(ny)) = (nx, ny) else {
+                    continue;
+                };
+                let Some(cell) = buf.cell_mut((nx, ny)) else {
+                    continue;
+                };
+                if cell.symbol() == " " && matches!(cell.bg, Color::Rgb(..)) {
+                    cell.bg = theme.mix(cell.bg, color, tint);
+                }
+            }
+        }
+    }
+}
+
+/// Perceived brightness of an RGB color (0-1); other colors count as dark
+fn luminance(color: Color) -> f32 {
+    match color {
+        Color::Rgb(r, g, b) => (0.299 * r as f32 + 0.587 * g as f32 + 0.114 * b as f32) / 255.0,
+        _ => 0.0,
+    }
+}
+
+/// 0-1, different from frame to frame; flicker doesn't need a real RNG
+fn frame_noise() -> f32 {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos());
+    let mut x = nanos | 1;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    (x % 1000) as f32 / 1000.0
+}
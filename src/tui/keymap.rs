@@ -0,0 +1,288 @@
+//! Key bindings for the dashboard's actions, from the `[keys]` config
+
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+
+use crate::config::KeyBinding;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Action {
+    Quit,
+    Help,
+    Messages,
+    FocusNext,
+    FocusPrev,
+    PlayPause,
+    Next,
+    Prev,
+    VolumeUp,
+    VolumeDown,
+    ToggleSaved,
+    SeekBack,
+    SeekForward,
+    GitRefresh,
+    ArtStyle,
+    ToggleLyrics,
+    RefetchLyrics,
+    Search,
+    Devices,
+    AudioDevices,
+    SleepTimer,
+    Channels,
+    SpectrumMode,
+    WaveformStyle,
+    FftSize,
+    CopyUrl,
+    OpenUrl,
+    Radio,
+    ToggleQueue,
+    ToggleHistory,
+    ToggleGitHub,
+    /// Hide or show the layout's nth panel (from 1)
+    TogglePanel(usize),
+    SavePanels,
+}
+
+/// Every action with its config name and default keys
+const ACTIONS: [(Action, &str, &[&str]); 41] = [
+    (Action::Quit, "quit", &["q", "esc"]),
+    (Action::Help, "help", &["?"]),
+    (Action::Messages, "messages", &["M"]),
+    (Action::FocusNext, "focus_next", &["tab"]),
+    (Action::FocusPrev, "focus_prev", &["backtab"]),
+    (Action::PlayPause, "play_pause", &["space"]),
+    (Action::Next, "next", &["n"]),
+    (Action::Prev, "prev", &["p"]),
+    (Action::VolumeUp, "volume_up", &["+", "="]),
+    (Action::VolumeDown, "volume_down", &["-"]),
+    (Action::ToggleSaved, "toggle_saved", &["f"]),
+    (Action::SeekBack, "seek_back", &["left"]),
+    (Action::SeekForward, "seek_forward", &["right"]),
+    (Action::GitRefresh, "git_refresh", &["r"]),
+    (Action::ArtStyle, "art_style", &["a"]),
+    (Action::ToggleLyrics, "toggle_lyrics", &["l"]),
+    (Action::RefetchLyrics, "refetch_lyrics", &["L"]),
+    (Action::Search, "search", &["/"]),
+    (Action::Devices, "devices", &["d"]),
+    (Action::AudioDevices, "audio_devices", &["D"]),
+    (Action::SleepTimer, "sleep_timer", &["z"]),
+    (Action::Channels, "channels", &["s"]),
+    (Action::SpectrumMode, "spectrum_mode", &["m"]),
+    (Action::WaveformStyle, "waveform_style", &["w"]),
+    (Action::FftSize, "fft_size", &["F"]),
+    (Action::CopyUrl, "copy_url", &["c"]),
+    (Action::OpenUrl, "open_url", &["o"]),
+    (Action::Radio, "radio", &["R"]),
+    (Action::ToggleQueue, "toggle_queue", &["u"]),
+    (Action::ToggleHistory, "toggle_history", &["h"]),
+    (Action::ToggleGitHub, "toggle_github", &["g"]),
+    (Action::TogglePanel(1), "toggle_panel_1", &["1"]),
+    (Action::TogglePanel(2), "toggle_panel_2", &["2"]),
+    (Action::TogglePanel(3), "toggle_panel_3", &["3"]),
+    (Action::TogglePanel(4), "toggle_panel_4", &["4"]),
+    (Action::TogglePanel(5), "toggle_panel_5", &["5"]),
+    (Action::TogglePanel(6), "toggle_panel_6", &["6"]),
+    (Action::TogglePanel(7), "toggle_panel_7", &["7"]),
+    (Action::TogglePanel(8), "toggle_panel_8", &["8"]),
+    (Action::TogglePanel(9), "toggle_panel_9", &["9"]),
+    (Action::SavePanels, "save_panels", &["S"]),
+];
+
+/// What the keys pressed so far amount to
+pub enum Lookup {
+    Action(Action),
+    /// The start of a chord; wait for the next key
+    Pending,
+    None,
+}
+
+/// What a key press is before the bindings are looked at
+pub enum Press {
+    /// Ctrl+C, which quits outright as it would outside raw mode
+    Interrupt,
+    Key(KeyCode),
+    /// A Ctrl or Alt combination, which no binding can name
+    Unbound,
+}
+
+impl Press {
+    pub fn from_event(key: KeyEvent) -> Self {
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            Press::Interrupt
+        } else if key.modifiers.intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) {
+            Press::Unbound
+        } else {
+            Press::Key(key.code)
+        }
+    }
+}
+
+struct Binding {
+    keys: Vec<KeyCode>,
+    action: Action,
+}
+
+pub struct Keymap {
+    bindings: Vec<Binding>,
+}
+
+impl Keymap {
+    /// The defaults with `[keys]` entries replacing an action's keys (an empty
+    /// list unbinds it). A binding can't be the start of another's chord
+    pub fn from_config(config: &BTreeMap<String, KeyBinding>) -> Result<Self> {
+        for name in config.keys() {
+            if !ACTIONS.iter().any(|(_, action_name, _)| action_name == name) {
+                let names: Vec<&str> = ACTIONS.iter().map(|(_, name, _)| *name).collect();
+                bail!("Unknown action keys.{} (expected one of: {})", name, names.join(", "));
+            }
+        }
+
+        let mut bindings = Vec::new();
+        for (action, name, defaults) in ACTIONS {
+            let sequences: Vec<&str> = match config.get(name) {
+                Some(binding) => binding.sequences(),
+                None => defaults.to_vec(),
+            };
+            for sequence in sequences {
+                let keys = sequence
+                    .split_whitespace()
+                    .map(|key| parse_key(key).ok_or(key))
+                    .collect::<Result<Vec<_>, _>>();
+                match keys {
+                    Ok(keys) if !keys.is_empty() => bindings.push(Binding { keys, action }),
+                    Ok(_) => {}
+                    Err(key) => bail!(
+                        "keys.{}: unknown key '{}' (use a character or a name like space, tab, esc, enter, left, f1)",
+                        name,
+                        key
+                    ),
+                }
+            }
+        }
+
+        for (i, first) in bindings.iter().enumerate() {
+            for second in &bindings[i + 1..] {
+                let shorter = first.keys.len().min(second.keys.len());
+                if first.keys[..shorter] == second.keys[..shorter] {
+                    bail!(
+                        "keys.{} ({}) clashes with keys.{} ({}); rebind one of them",
+                        action_name(first.action),
+                        describe_keys(&first.keys),
+                        action_name(second.action),
+                        describe_keys(&second.keys)
+                    );
+                }
+            }
+        }
+
+        Ok(Self { bindings })
+    }
+
+    pub fn lookup(&self, pressed: &[KeyCode]) -> Lookup {
+        let mut pending = false;
+        for binding in &self.bindings {
+            if binding.keys == pressed {
+                return Lookup::Action(binding.action);
+            }
+            pending |= binding.keys.starts_with(pressed);
+        }
+        if pending { Lookup::Pending } else { Lookup::None }
+    }
+
+    /// Every key sequence bound to `action` for display, e.g. ["q", "Esc"]
+    pub fn keys(&self, action: Action) -> Vec<String> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.action == action)
+            .map(|binding| describe_keys(&binding.keys))
+            .collect()
+    }
+
+    /// The rest of each chord started by `pressed` and the action it runs,
+    /// e.g. [("r", "git_refresh")] after "g"
+    pub fn continuations(&self, pressed: &[KeyCode]) -> Vec<(String, &'static str)> {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.keys.len() > pressed.len() && binding.keys.starts_with(pressed))
+            .map(|binding| (describe_keys(&binding.keys[pressed.len()..]), action_name(binding.action)))
+            .collect()
+    }
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        Self::from_config(&BTreeMap::new()).expect("default keys are valid")
+    }
+}
+
+fn action_name(action: Action) -> &'static str {
+    ACTIONS
+        .iter()
+        .find(|(other, _, _)| *other == action)
+        .map_or("?", |(_, name, _)| name)
+}
+
+/// A single character as itself, anything else by name
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+
+    let code = match name.to_lowercase().as_str() {
+        "space" => KeyCode::Char(' '),
+        "tab" => KeyCode::Tab,
+        "backtab" | "shift-tab" => KeyCode::BackTab,
+        "esc" | "escape" => KeyCode::Esc,
+        "enter" | "return" => KeyCode::Enter,
+        "backspace" => KeyCode::Backspace,
+        "delete" | "del" => KeyCode::Delete,
+        "insert" | "ins" => KeyCode::Insert,
+        "left" => KeyCode::Left,
+        "right" => KeyCode::Right,
+        "up" => KeyCode::Up,
+        "down" => KeyCode::Down,
+        "home" => KeyCode::Home,
+        "end" => KeyCode::End,
+        "pageup" => KeyCode::PageUp,
+        "pagedown" => KeyCode::PageDown,
+        other => {
+            let number = other.strip_prefix('f')?.parse::<u8>().ok()?;
+            if !(1..=12).contains(&number) {
+                return None;
+            }
+            KeyCode::F(number)
+        }
+    };
+    Some(code)
+}
+
+/// Keys as the help overlay shows them, e.g. "g r" or "S-Tab"
+pub fn describe_keys(keys: &[KeyCode]) -> String {
+    let names: Vec<String> = keys
+        .iter()
+        .map(|key| match key {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "S-Tab".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Backspace => "Bksp".to_string(),
+            KeyCode::Delete => "Del".to_string(),
+            KeyCode::Insert => "Ins".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        })
+        .collect();
+    names.join(" ")
+}
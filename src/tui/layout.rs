@@ -0,0 +1,195 @@
+//! Dashboard grid built from the `[layout] rows` config
+
+use anyhow::{bail, Result};
+
+use crate::config::LayoutConfig;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Panel {
+    Spotify,
+    Git,
+    Spectrum,
+    Spectrogram,
+    Waveform,
+    Scope,
+    Lyrics,
+    AlbumArt,
+    Queue,
+    History,
+    GitHub,
+    Heatmap,
+}
+
+impl Panel {
+    /// Config names, in the order shown in error messages
+    pub const NAMES: [&'static str; 12] = [
+        "spotify", "git", "spectrum", "spectrogram", "waveform", "scope", "lyrics", "albumart",
+        "queue", "history", "github", "heatmap",
+    ];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "spotify" => Some(Panel::Spotify),
+            "git" => Some(Panel::Git),
+            "spectrum" => Some(Panel::Spectrum),
+            "spectrogram" => Some(Panel::Spectrogram),
+            "waveform" => Some(Panel::Waveform),
+            "scope" => Some(Panel::Scope),
+            "lyrics" => Some(Panel::Lyrics),
+            "albumart" => Some(Panel::AlbumArt),
+            "queue" => Some(Panel::Queue),
+            "history" => Some(Panel::History),
+            "github" => Some(Panel::GitHub),
+            "heatmap" => Some(Panel::Heatmap),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Panel::Spotify => "spotify",
+            Panel::Git => "git",
+            Panel::Spectrum => "spectrum",
+            Panel::Spectrogram => "spectrogram",
+            Panel::Waveform => "waveform",
+            Panel::Scope => "scope",
+            Panel::Lyrics => "lyrics",
+            Panel::AlbumArt => "albumart",
+            Panel::Queue => "queue",
+            Panel::History => "history",
+            Panel::GitHub => "github",
+            Panel::Heatmap => "heatmap",
+        }
+    }
+
+    /// How much the panel is worth keeping on a small terminal; the lowest
+    /// goes first when the layout doesn't fit
+    pub fn priority(self) -> u8 {
+        match self {
+            Panel::Spotify => 11,
+            Panel::Spectrum => 10,
+            Panel::Lyrics => 9,
+            Panel::Git => 8,
+            Panel::AlbumArt => 7,
+            Panel::Queue => 6,
+            Panel::GitHub => 5,
+            Panel::Waveform => 4,
+            Panel::History => 3,
+            Panel::Spectrogram => 2,
+            Panel::Scope => 1,
+            Panel::Heatmap => 0,
+        }
+    }
+
+    /// The panel shown in this one's place while lyrics are toggled off with `l`
+    pub fn swapped(self) -> Self {
+        match self {
+            Panel::Lyrics => Panel::AlbumArt,
+            Panel::AlbumArt => Panel::Lyrics,
+            other => other,
+        }
+    }
+}
+
+/// Rows of panels, each row split evenly into columns
+#[derive(Clone, Debug)]
+pub struct GridLayout {
+    rows: Vec<Vec<Panel>>,
+    /// Panels taken off the screen, starting with `layout.hidden`
+    hidden: Vec<Panel>,
+}
+
+impl GridLayout {
+    pub fn from_config(config: &LayoutConfig) -> Result<Self> {
+        let mut rows = Vec::new();
+
+        for (i, row) in config.rows.iter().enumerate() {
+            let mut panels = Vec::new();
+            for name in row {
+                match Panel::from_name(name) {
+                    Some(panel) => panels.push(panel),
+                    None => bail!(
+                        "Unknown panel '{}' in layout.rows row {} (expected one of: {})",
+                        name,
+                        i + 1,
+                        Panel::NAMES.join(", ")
+                    ),
+                }
+            }
+            // Tolerate empty rows rather than rendering a blank strip
+            if !panels.is_empty() {
+                rows.push(panels);
+            }
+        }
+
+        if rows.is_empty() {
+            bail!(
+                "layout.rows has no panels (available: {})",
+                Panel::NAMES.join(", ")
+            );
+        }
+
+        let mut hidden = Vec::new();
+        for name in &config.hidden {
+            match Panel::from_name(name) {
+                Some(panel) => hidden.push(panel),
+                None => bail!(
+                    "Unknown panel '{}' in layout.hidden (expected one of: {})",
+                    name,
+                    Panel::NAMES.join(", ")
+                ),
+            }
+        }
+
+        let layout = Self { rows, hidden };
+        if layout.rows().is_empty() {
+            bail!("layout.hidden hides every panel in layout.rows");
+        }
+        Ok(layout)
+    }
+
+    /// The rows on screen: hidden panels left out and the rest closing up
+    pub fn rows(&self) -> Vec<Vec<Panel>> {
+        self.rows
+            .iter()
+            .map(|row| row.iter().copied().filter(|panel| !self.hidden.contains(panel)).collect())
+            .filter(|row: &Vec<Panel>| !row.is_empty())
+            .collect()
+    }
+
+    /// Every panel in the layout once, hidden or not, in screen order; the
+    /// number keys toggle them by position in this list
+    pub fn panels(&self) -> Vec<Panel> {
+        let mut panels = Vec::new();
+        for &panel in self.rows.iter().flatten() {
+            if !panels.contains(&panel) {
+                panels.push(panel);
+            }
+        }
+        panels
+    }
+
+    /// Hide or show the `number`th panel (from 1), returning it and whether
+    /// it is now shown
+    pub fn toggle(&mut self, number: usize) -> Result<(Panel, bool)> {
+        let Some(panel) = number.checked_sub(1).and_then(|i| self.panels().get(i).copied()) else {
+            bail!("No panel {} in the layout", number);
+        };
+        if let Some(i) = self.hidden.iter().position(|&hidden| hidden == panel) {
+            self.hidden.remove(i);
+            return Ok((panel, true));
+        }
+
+        self.hidden.push(panel);
+        if self.rows().is_empty() {
+            self.hidden.pop();
+            bail!("Can't hide the last panel");
+        }
+        Ok((panel, false))
+    }
+
+    /// Config names of the hidden panels, for `layout.hidden`
+    pub fn hidden_names(&self) -> Vec<String> {
+        self.hidden.iter().map(|panel| panel.name().to_string()).collect()
+    }
+}
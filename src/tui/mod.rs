@@ -1,5 +1,9 @@
 mod app;
-mod theme;
+mod crt;
+mod keymap;
+mod layout;
+mod panel_cache;
+pub mod theme;
 pub mod widgets;
 
-pub use app::run;
+pub use app::{run, run_demo};
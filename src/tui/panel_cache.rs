@@ -0,0 +1,72 @@
+//! Last frame's cells for each panel, copied back in while nothing the panel
+//! shows has changed so only new audio frames and fresh data get rendered
+
+use ratatui::{buffer::Buffer, layout::Rect};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crate::tui::layout::Panel;
+
+/// Longest a panel goes without a real render, for what changes with the
+/// clock alone: "5m ago" times, countdowns, loading text
+const MAX_AGE: Duration = Duration::from_secs(1);
+
+struct Entry {
+    area: Rect,
+    cells: Buffer,
+    rendered: Instant,
+}
+
+#[derive(Default)]
+pub struct PanelCache {
+    entries: HashMap<Panel, Entry>,
+}
+
+impl PanelCache {
+    /// Whether `panel` is worth caching at all: the visualizers change with
+    /// every audio frame
+    fn cacheable(panel: Panel) -> bool {
+        !matches!(panel, Panel::Spectrum | Panel::Spectrogram | Panel::Waveform | Panel::Scope)
+    }
+
+    /// Render `panel` afresh next frame
+    pub fn mark(&mut self, panel: Panel) {
+        self.entries.remove(&panel);
+    }
+
+    /// Render everything afresh next frame, e.g. after input or a resize
+    pub fn mark_all(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Copy `panel`'s cells into `buf` if they're still good for `area`,
+    /// returning whether they were
+    pub fn restore(&self, panel: Panel, area: Rect, buf: &mut Buffer) -> bool {
+        let Some(entry) = self.entries.get(&panel) else {
+            return false;
+        };
+        if entry.area != area || entry.rendered.elapsed() >= MAX_AGE {
+            return false;
+        }
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                buf[(x, y)] = entry.cells[(x, y)].clone();
+            }
+        }
+        true
+    }
+
+    /// Keep what was just rendered for `panel` in `area` of `buf`
+    pub fn store(&mut self, panel: Panel, area: Rect, buf: &Buffer) {
+        if !Self::cacheable(panel) {
+            return;
+        }
+        let mut cells = Buffer::empty(area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                cells[(x, y)] = buf[(x, y)].clone();
+            }
+        }
+        self.entries.insert(panel, Entry { area, cells, rendered: Instant::now() });
+    }
+}
@@ -1,25 +1,236 @@
-use ratatui::style::Color;
+use anyhow::{Context, Result};
+use ratatui::{
+    style::{Color, Style},
+    symbols::border,
+    text::Line,
+    widgets::{Block, BorderType, Borders},
+};
 
 use crate::config::ThemeConfig;
 
+/// What the spectrum's dim-to-accent gradient follows
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GradientAxis {
+    /// Dim at the base of each bar, accent at the top
+    #[default]
+    Height,
+    /// One color per bar, deep for the bass and bright for the highs
+    Frequency,
+}
+
+impl GradientAxis {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "height" => Some(Self::Height),
+            "frequency" => Some(Self::Frequency),
+            _ => None,
+        }
+    }
+}
+
+/// How panels and overlays are framed
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BorderStyle {
+    #[default]
+    Plain,
+    Rounded,
+    Thick,
+    Double,
+    /// `+`, `-` and `|` for terminals and fonts without box drawing
+    Ascii,
+    /// No borders or titles on the panels, for small tmux panes
+    Compact,
+}
+
+impl BorderStyle {
+    fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "plain" => Some(Self::Plain),
+            "rounded" => Some(Self::Rounded),
+            "thick" => Some(Self::Thick),
+            "double" => Some(Self::Double),
+            "ascii" => Some(Self::Ascii),
+            "compact" => Some(Self::Compact),
+            _ => None,
+        }
+    }
+}
+
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// A named built-in color scheme, the base that `[theme]` colors override
+pub struct Preset {
+    pub name: &'static str,
+    pub description: &'static str,
+    background: Color,
+    foreground: Color,
+    accent: Color,
+    dim: Color,
+    warning: Color,
+}
+
+/// Every built-in scheme; the first is the default
+pub const PRESETS: [Preset; 6] = [
+    Preset {
+        name: "amber",
+        description: "Amber CRT",
+        background: Color::Rgb(26, 16, 0),
+        foreground: Color::Rgb(255, 176, 0),
+        accent: Color::Rgb(255, 204, 0),
+        dim: Color::Rgb(102, 68, 0),
+        warning: Color::Rgb(255, 68, 0),
+    },
+    Preset {
+        name: "green",
+        description: "Green P1 phosphor",
+        background: Color::Rgb(4, 20, 8),
+        foreground: Color::Rgb(51, 255, 51),
+        accent: Color::Rgb(153, 255, 102),
+        dim: Color::Rgb(20, 96, 32),
+        warning: Color::Rgb(255, 170, 0),
+    },
+    Preset {
+        name: "paper",
+        description: "Black on white paper",
+        background: Color::Rgb(244, 241, 232),
+        foreground: Color::Rgb(43, 43, 43),
+        accent: Color::Rgb(0, 0, 0),
+        dim: Color::Rgb(168, 162, 150),
+        warning: Color::Rgb(192, 57, 43),
+    },
+    Preset {
+        name: "dos",
+        description: "DOS blue",
+        background: Color::Rgb(0, 0, 170),
+        foreground: Color::Rgb(170, 170, 170),
+        accent: Color::Rgb(255, 255, 255),
+        dim: Color::Rgb(85, 85, 255),
+        warning: Color::Rgb(255, 255, 85),
+    },
+    Preset {
+        name: "gruvbox",
+        description: "Gruvbox dark",
+        background: Color::Rgb(40, 40, 40),
+        foreground: Color::Rgb(235, 219, 178),
+        accent: Color::Rgb(250, 189, 47),
+        dim: Color::Rgb(102, 92, 84),
+        warning: Color::Rgb(251, 73, 52),
+    },
+    Preset {
+        name: "nord",
+        description: "Nord",
+        background: Color::Rgb(46, 52, 64),
+        foreground: Color::Rgb(216, 222, 233),
+        accent: Color::Rgb(136, 192, 208),
+        dim: Color::Rgb(76, 86, 106),
+        warning: Color::Rgb(191, 97, 106),
+    },
+];
+
+impl Preset {
+    /// The preset called `name`, or an error listing the ones there are
+    pub fn named(name: &str) -> Result<&'static Preset> {
+        let name = name.trim().to_lowercase();
+        PRESETS.iter().find(|preset| preset.name == name).with_context(|| {
+            let names: Vec<&str> = PRESETS.iter().map(|preset| preset.name).collect();
+            format!("Unknown theme preset '{}' (expected one of: {})", name, names.join(", "))
+        })
+    }
+
+    /// The preset's colors with the rest of the theme left at its defaults
+    pub fn theme(&self) -> Theme {
+        Theme {
+            background: self.background,
+            foreground: self.foreground,
+            accent: self.accent,
+            dim: self.dim,
+            warning: self.warning,
+            transparent: false,
+            spectrum_gradient: GradientAxis::Height,
+            borders: BorderStyle::Plain,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Theme {
     pub background: Color,
     pub foreground: Color,
     pub accent: Color,
     pub dim: Color,
+    pub warning: Color,
+    pub transparent: bool,
+    pub spectrum_gradient: GradientAxis,
+    pub borders: BorderStyle,
 }
 
 impl Theme {
+    /// The preset with any colors set in the config on top; unknown presets
+    /// fall back to the default one
     pub fn from_config(config: &ThemeConfig) -> Self {
+        let preset = Preset::named(&config.preset).unwrap_or(&PRESETS[0]);
+        let color = |hex: &Option<String>, fallback: Color| hex.as_deref().and_then(parse_hex_color).unwrap_or(fallback);
         Self {
-            background: parse_hex_color(&config.background).unwrap_or(Color::Rgb(26, 16, 0)),
-            foreground: parse_hex_color(&config.foreground).unwrap_or(Color::Rgb(255, 176, 0)),
-            accent: parse_hex_color(&config.accent).unwrap_or(Color::Rgb(255, 204, 0)),
-            dim: parse_hex_color(&config.dim).unwrap_or(Color::Rgb(102, 68, 0)),
+            background: color(&config.background, preset.background),
+            foreground: color(&config.foreground, preset.foreground),
+            accent: color(&config.accent, preset.accent),
+            dim: color(&config.dim, preset.dim),
+            warning: color(&config.warning, preset.warning),
+            transparent: config.transparent_background,
+            // Unknown names fall back like unparseable colors do
+            spectrum_gradient: GradientAxis::from_name(&config.spectrum_gradient).unwrap_or_default(),
+            borders: BorderStyle::from_name(&config.borders).unwrap_or_default(),
+        }
+    }
+
+    /// Background color to paint cells with; `Reset` keeps the terminal's own
+    pub fn bg(&self) -> Color {
+        if self.transparent {
+            Color::Reset
+        } else {
+            self.background
         }
     }
 
+    /// A bordered box in the configured style, for overlays and toasts
+    /// (compact mode keeps plain borders there, they float over the panels)
+    pub fn frame(&self) -> Block<'static> {
+        let block = Block::default().borders(Borders::ALL);
+        match self.borders {
+            BorderStyle::Plain | BorderStyle::Compact => block,
+            BorderStyle::Rounded => block.border_type(BorderType::Rounded),
+            BorderStyle::Thick => block.border_type(BorderType::Thick),
+            BorderStyle::Double => block.border_type(BorderType::Double),
+            BorderStyle::Ascii => block.border_set(ASCII_BORDER),
+        }
+    }
+
+    /// A panel's frame with `title` on top, or nothing at all in compact mode
+    pub fn block<'a>(&self, title: impl Into<Line<'a>>, border_style: Style) -> Block<'a> {
+        if self.borderless() {
+            return Block::default();
+        }
+        self.frame()
+            .border_style(border_style)
+            .title(title)
+            .title_style(Style::default().fg(self.foreground))
+    }
+
+    /// Whether panels go without borders and titles; hints usually shown in
+    /// the bottom border are left out too
+    pub fn borderless(&self) -> bool {
+        self.borders == BorderStyle::Compact
+    }
+
     pub fn gradient(&self, intensity: f32) -> Color {
         let intensity = intensity.clamp(0.0, 1.0);
 
@@ -33,6 +244,41 @@ impl Theme {
 
         Color::Rgb(r, g, b)
     }
+
+    /// Blend a color towards white (0.0 = unchanged, 1.0 = white)
+    pub fn brighten(&self, color: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+
+        let (r, g, b) = color_to_rgb(color);
+        let lift = |c: u8| (c as f32 + (255.0 - c as f32) * amount) as u8;
+
+        Color::Rgb(lift(r), lift(g), lift(b))
+    }
+
+    /// Blend a color towards another (0.0 = unchanged, 1.0 = `towards`)
+    pub fn mix(&self, color: Color, towards: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+
+        let (cr, cg, cb) = color_to_rgb(color);
+        let (tr, tg, tb) = color_to_rgb(towards);
+        let blend = |c: u8, t: u8| (c as f32 + (t as f32 - c as f32) * amount) as u8;
+
+        Color::Rgb(blend(cr, tr), blend(cg, tg), blend(cb, tb))
+    }
+
+    /// Blend a color towards the background (0.0 = unchanged, 1.0 = background)
+    pub fn fade(&self, color: Color, amount: f32) -> Color {
+        let amount = amount.clamp(0.0, 1.0);
+
+        let (cr, cg, cb) = color_to_rgb(color);
+        let (br, bg, bb) = color_to_rgb(self.background);
+
+        let r = (cr as f32 + (br as f32 - cr as f32) * amount) as u8;
+        let g = (cg as f32 + (bg as f32 - cg as f32) * amount) as u8;
+        let b = (cb as f32 + (bb as f32 - cb as f32) * amount) as u8;
+
+        Color::Rgb(r, g, b)
+    }
 }
 
 fn parse_hex_color(hex: &str) -> Option<Color> {
@@ -57,11 +303,6 @@ fn color_to_rgb(color: Color) -> (u8, u8, u8) {
 
 impl Default for Theme {
     fn default() -> Self {
-        Self {
-            background: Color::Rgb(26, 16, 0),
-            foreground: Color::Rgb(255, 176, 0),
-            accent: Color::Rgb(255, 204, 0),
-            dim: Color::Rgb(102, 68, 0),
-        }
+        PRESETS[0].theme()
     }
 }
@@ -1,13 +1,16 @@
-use image::{DynamicImage, GenericImageView, imageops::FilterType};
+use image::{DynamicImage, imageops::FilterType};
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::Style,
-    widgets::{Block, Borders, Widget},
+    widgets::{Paragraph, Widget, Wrap},
 };
 use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
+use crate::config::AlbumArtConfig;
+use crate::modules::net;
+use crate::modules::spotify::SpotifyStatus;
 use crate::tui::theme::Theme;
 
 // Block characters by density (darkest to brightest)
@@ -22,7 +25,8 @@ const BLOCK_CHARS: [char; 5] = [' ', '░', '▒', '▓', '█'];
 const BRAILLE_BASE: u32 = 0x2800;
 const BRAILLE_DOTS: [u32; 8] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80];
 
-/// Simple image cache to avoid re-downloading
+/// Simple image cache to avoid re-downloading, shared with fetch tasks
+#[derive(Clone)]
 pub struct ImageCache {
     cache: Arc<Mutex<HashMap<String, DynamicImage>>>,
 }
@@ -34,20 +38,31 @@ impl ImageCache {
         }
     }
 
-    pub fn get_or_fetch(&self, url: &str) -> Option<DynamicImage> {
-        let mut cache = self.cache.lock().ok()?;
+    /// Cached image only, never touches the network
+    pub fn get(&self, url: &str) -> Option<DynamicImage> {
+        self.cache.lock().ok()?.get(url).cloned()
+    }
 
-        if let Some(img) = cache.get(url) {
-            return Some(img.clone());
+    /// Blocking download (or file read) on a cache miss; run it off the render loop
+    pub fn get_or_fetch(&self, url: &str) -> Option<DynamicImage> {
+        if let Some(img) = self.get(url) {
+            return Some(img);
         }
 
-        // Fetch the image (blocking, but should be called sparingly)
-        let response = ureq::get(url).call().ok()?;
-        let mut bytes = Vec::new();
-        response.into_reader().read_to_end(&mut bytes).ok()?;
+        // Download without holding the lock so lookups stay instant. Local players
+        // (MPD, some MPRIS ones) hand out file:// URLs instead
+        let bytes = match url.strip_prefix("file://") {
+            Some(path) => std::fs::read(urlencoding::decode(path).ok()?.as_ref()).ok()?,
+            None => {
+                let response = net::agent().get(url).call().ok()?;
+                let mut bytes = Vec::new();
+                response.into_reader().read_to_end(&mut bytes).ok()?;
+                bytes
+            }
+        };
 
         let img = image::load_from_memory(&bytes).ok()?;
-        cache.insert(url.to_string(), img.clone());
+        self.cache.lock().ok()?.insert(url.to_string(), img.clone());
         Some(img)
     }
 }
@@ -67,14 +82,38 @@ pub enum ArtStyle {
 
 pub struct AlbumArtWidget<'a> {
     image: Option<&'a DynamicImage>,
+    spotify: SpotifyStatus,
+    has_track: bool,
     theme: &'a Theme,
     focused: bool,
     style: ArtStyle,
+    cell_aspect: f32,
+    loading: bool,
 }
 
 impl<'a> AlbumArtWidget<'a> {
-    pub fn new(image: Option<&'a DynamicImage>, theme: &'a Theme, focused: bool, style: ArtStyle) -> Self {
-        Self { image, theme, focused, style }
+    pub fn new(
+        image: Option<&'a DynamicImage>,
+        spotify: SpotifyStatus,
+        has_track: bool,
+        theme: &'a Theme,
+        focused: bool,
+        style: ArtStyle,
+    ) -> Self {
+        Self { image, spotify, has_track, theme, focused, style, cell_aspect: 2.0, loading: false }
+    }
+
+    /// Apply the cell aspect correction from config
+    pub fn config(mut self, config: &AlbumArtConfig) -> Self {
+        // Guard against nonsense values that would collapse the image
+        self.cell_aspect = config.cell_aspect.clamp(0.5, 4.0);
+        self
+    }
+
+    /// Show a loading placeholder while the image is being downloaded
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
     }
 
     fn render_blocks(&self, img: &DynamicImage, area: Rect, buf: &mut Buffer) {
@@ -85,10 +124,13 @@ impl<'a> AlbumArtWidget<'a> {
             return;
         }
 
-        // Maintain 1:1 aspect ratio (terminal chars are ~2:1, so width = height*2 in chars)
-        let square_size = width.min(height * 2);
-        let img_width = square_size;
-        let img_height = square_size / 2;
+        // Maintain 1:1 aspect ratio (cells are ~cell_aspect:1, so width = height * cell_aspect)
+        let img_width = (width as f32).min(height as f32 * self.cell_aspect) as u32;
+        let img_height = ((img_width as f32 / self.cell_aspect) as u32).min(height);
+
+        if img_width == 0 || img_height == 0 {
+            return;
+        }
 
         // Center the image
         let x_offset = (width - img_width) / 2;
@@ -120,7 +162,7 @@ impl<'a> AlbumArtWidget<'a> {
                     buf[(cell_x, cell_y)]
                         .set_char(ch)
                         .set_fg(color)
-                        .set_bg(self.theme.background);
+                        .set_bg(self.theme.bg());
                 }
             }
         }
@@ -135,9 +177,14 @@ impl<'a> AlbumArtWidget<'a> {
         }
 
         // Maintain 1:1 visual aspect ratio
-        // Terminal chars are ~2:1 (height:width), so for square output: char_width = char_height * 2
-        let char_height = height.min(width / 2);
-        let char_width = char_height * 2;
+        // Terminal chars are ~cell_aspect:1 (height:width), so for square output:
+        // char_width = char_height * cell_aspect
+        let char_height = (height as f32).min(width as f32 / self.cell_aspect) as u32;
+        let char_width = ((char_height as f32 * self.cell_aspect) as u32).min(width);
+
+        if char_width == 0 || char_height == 0 {
+            return;
+        }
 
         // Center the image
         let x_offset = (width - char_width) / 2;
@@ -189,7 +236,7 @@ impl<'a> AlbumArtWidget<'a> {
                 let ch = char::from_u32(braille).unwrap_or(' ');
 
                 // Color based on average brightness of the block
-                let avg_brightness = if count > 0 { total_brightness / count } else { 0 };
+                let avg_brightness = total_brightness.checked_div(count).unwrap_or(0);
                 let intensity = avg_brightness as f32 / 255.0;
                 let color = self.theme.gradient(intensity);
 
@@ -200,7 +247,7 @@ impl<'a> AlbumArtWidget<'a> {
                     buf[(cell_x, cell_y)]
                         .set_char(ch)
                         .set_fg(color)
-                        .set_bg(self.theme.background);
+                        .set_bg(self.theme.bg());
                 }
             }
         }
@@ -215,11 +262,7 @@ impl Widget for AlbumArtWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(" Album Art ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let block = self.theme.block(" Album Art ", border_style);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -232,19 +275,22 @@ impl Widget for AlbumArtWidget<'_> {
                 }
             }
             None => {
-                // Show placeholder text
-                let msg = "No album art";
-                let x = inner.x + (inner.width.saturating_sub(msg.len() as u16)) / 2;
-                let y = inner.y + inner.height / 2;
-                if y < inner.y + inner.height {
-                    for (i, ch) in msg.chars().enumerate() {
-                        let px = x + i as u16;
-                        if px < inner.x + inner.width {
-                            buf[(px, y)]
-                                .set_char(ch)
-                                .set_fg(self.theme.dim);
-                        }
-                    }
+                // Show placeholder text, explaining a missing connection over missing art
+                let msg = if self.loading {
+                    "Loading album art..."
+                } else if self.has_track {
+                    "No album art"
+                } else {
+                    self.spotify.idle_hint()
+                };
+                let y_offset = inner.height / 2;
+                if y_offset < inner.height {
+                    let text_area = Rect::new(inner.x, inner.y + y_offset, inner.width, inner.height - y_offset);
+                    Paragraph::new(msg)
+                        .style(Style::default().fg(self.theme.dim))
+                        .alignment(Alignment::Center)
+                        .wrap(Wrap { trim: true })
+                        .render(text_area, buf);
                 }
             }
         }
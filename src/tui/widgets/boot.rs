@@ -0,0 +1,100 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+use std::time::Duration;
+
+use crate::tui::theme::Theme;
+
+/// Memory counted up to, in kilobytes
+const MEMORY_KB: u32 = 65536;
+
+/// Time spent counting memory
+const MEMORY_CHECK: Duration = Duration::from_millis(700);
+
+/// Device checks printed one after another once memory is done
+const CHECKS: [&str; 4] = [
+    "Warming up phosphor coating",
+    "Calibrating electron beam",
+    "Tuning audio capture",
+    "Loading dashboard",
+];
+const CHECK_INTERVAL: Duration = Duration::from_millis(150);
+
+/// The cursor blinks on the last line before the logo takes over
+const CURSOR_WAIT: Duration = Duration::from_millis(500);
+const CURSOR_BLINK: Duration = Duration::from_millis(125);
+
+/// How long the text part of the boot runs before the logo
+pub const BOOT_DURATION: Duration = Duration::from_millis(
+    MEMORY_CHECK.as_millis() as u64 + CHECK_INTERVAL.as_millis() as u64 * CHECKS.len() as u64 + CURSOR_WAIT.as_millis() as u64,
+);
+
+/// Old-terminal power-on text: a memory count, device checks and a blinking
+/// cursor, laid out from the top-left like a BIOS screen
+pub struct BootWidget<'a> {
+    theme: &'a Theme,
+    /// Time since the boot started
+    elapsed: Duration,
+}
+
+impl<'a> BootWidget<'a> {
+    pub fn new(theme: &'a Theme, elapsed: Duration) -> Self {
+        Self { theme, elapsed }
+    }
+}
+
+impl Widget for BootWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let text = Style::default().fg(self.theme.foreground);
+        let dim = Style::default().fg(self.theme.dim);
+        let ok = Style::default().fg(self.theme.accent);
+
+        let mut lines = vec![
+            Line::styled(format!("PHOSPHOR v{}", env!("CARGO_PKG_VERSION")), ok),
+            Line::styled("Retro terminal dashboard", dim),
+            Line::default(),
+        ];
+
+        let progress = (self.elapsed.as_secs_f32() / MEMORY_CHECK.as_secs_f32()).min(1.0);
+        // Whole 64K blocks, like the old counters
+        let counted = (MEMORY_KB as f32 * progress) as u32 / 64 * 64;
+        let memory = Span::styled(format!("Memory test: {}K", counted), text);
+        if progress < 1.0 {
+            lines.push(Line::from(vec![memory, Span::styled("█", text)]));
+        } else {
+            lines.push(Line::from(vec![memory, Span::styled(" OK", ok)]));
+            lines.push(Line::default());
+
+            let after_memory = self.elapsed.saturating_sub(MEMORY_CHECK);
+            let shown = ((after_memory.as_millis() / CHECK_INTERVAL.as_millis()) as usize).min(CHECKS.len());
+            for check in &CHECKS[..shown] {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{:.<36}", format!("{} ", check)), text),
+                    Span::styled(" OK", ok),
+                ]));
+            }
+
+            // The cursor waits on the next line, blinking once every check is in
+            let done = shown == CHECKS.len();
+            if done {
+                lines.push(Line::default());
+            }
+            let cursor_on = !done || (self.elapsed.as_millis() / CURSOR_BLINK.as_millis()).is_multiple_of(2);
+            if cursor_on {
+                lines.push(Line::styled("█", text));
+            }
+        }
+
+        let inner = Rect {
+            x: area.x + 2.min(area.width),
+            y: area.y + 1.min(area.height),
+            width: area.width.saturating_sub(2),
+            height: area.height.saturating_sub(1),
+        };
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
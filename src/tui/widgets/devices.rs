@@ -0,0 +1,98 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::modules::spotify::DeviceInfo;
+use crate::tui::theme::Theme;
+
+/// Popup listing Spotify Connect devices to move playback to (or audio
+/// capture sources, with a different title)
+pub struct DevicesWidget<'a> {
+    devices: &'a [DeviceInfo],
+    selected: usize,
+    /// Shown instead of the list (loading, errors, no devices)
+    message: Option<&'a str>,
+    theme: &'a Theme,
+    title: &'a str,
+    hint: &'a str,
+}
+
+impl<'a> DevicesWidget<'a> {
+    pub fn new(
+        devices: &'a [DeviceInfo],
+        selected: usize,
+        message: Option<&'a str>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self {
+            devices,
+            selected,
+            message,
+            theme,
+            title: " Devices ",
+            hint: " Enter play here · ↑↓ select · Esc close ",
+        }
+    }
+
+    pub fn title(mut self, title: &'a str) -> Self {
+        self.title = title;
+        self
+    }
+
+    /// Key hints along the bottom border
+    pub fn hint(mut self, hint: &'a str) -> Self {
+        self.hint = hint;
+        self
+    }
+}
+
+impl Widget for DevicesWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self.theme.frame()
+            .border_style(Style::default().fg(self.theme.accent))
+            .style(Style::default().bg(self.theme.bg()))
+            .title(self.title)
+            .title_style(Style::default().fg(self.theme.foreground))
+            .title_bottom(self.hint);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if let Some(message) = self.message {
+            Paragraph::new(message)
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .render(inner, buf);
+            return;
+        }
+
+        let lines: Vec<Line> = self
+            .devices
+            .iter()
+            .enumerate()
+            .map(|(i, device)| {
+                let is_selected = i == self.selected;
+                let marker = if is_selected { "▸ " } else { "  " };
+                let name_style = if is_selected {
+                    Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                let active = if device.is_active { " ▶" } else { "" };
+
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(self.theme.accent)),
+                    Span::styled(device.name.clone(), name_style),
+                    Span::styled(format!(" ({})", device.kind), Style::default().fg(self.theme.dim)),
+                    Span::styled(active, Style::default().fg(self.theme.accent)),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -1,29 +1,71 @@
+use std::path::PathBuf;
+
 use ratatui::{
     buffer::Buffer,
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{
+        Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, StatefulWidget,
+        Widget, Wrap,
+    },
 };
 
-use crate::modules::git::{CommitInfo, RepoStatus};
+use crate::config::GitConfig;
+use crate::modules::git::{CommitInfo, RepoDetails, RepoStatus};
+use crate::modules::spotify::format_ago;
+use crate::tui::keymap::{Action, Keymap};
 use crate::tui::theme::Theme;
 
 pub struct GitWidget<'a> {
     repos: &'a [RepoStatus],
     commits: &'a [CommitInfo],
+    config: &'a GitConfig,
     theme: &'a Theme,
     focused: bool,
+    loading: bool,
+    selected: Option<usize>,
+    /// Repo drilled into, with its details once read
+    expanded: Option<(&'a PathBuf, Option<&'a RepoDetails>)>,
 }
 
 impl<'a> GitWidget<'a> {
     pub fn new(
         repos: &'a [RepoStatus],
         commits: &'a [CommitInfo],
+        config: &'a GitConfig,
         theme: &'a Theme,
         focused: bool,
     ) -> Self {
-        Self { repos, commits, theme, focused }
+        Self {
+            repos,
+            commits,
+            config,
+            theme,
+            focused,
+            loading: false,
+            selected: None,
+            expanded: None,
+        }
+    }
+
+    /// The first read of the repos hasn't finished yet
+    pub fn loading(mut self, loading: bool) -> Self {
+        self.loading = loading;
+        self
+    }
+
+    /// Highlight the repo at this index, or past the repos the commit at
+    /// the index less their count (while focused)
+    pub fn selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Show one repo's branches, changes and commits instead of the overview
+    pub fn expanded(mut self, path: Option<&'a PathBuf>, details: Option<&'a RepoDetails>) -> Self {
+        self.expanded = path.map(|path| (path, details));
+        self
     }
 }
 
@@ -35,19 +77,39 @@ impl Widget for GitWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title("  Git ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let mut block = self.theme.block("  Git ", border_style);
+        let hint = if self.expanded.is_some() {
+            Some(" Esc back ")
+        } else if self.selected.is_some_and(|selected| selected < self.repos.len()) {
+            Some(" Enter details ")
+        } else {
+            None
+        };
+        if let Some(hint) = hint.filter(|_| !self.theme.borderless()) {
+            block = block.title_bottom(Line::styled(hint, Style::default().fg(self.theme.dim)).right_aligned());
+        }
 
         let inner = block.inner(area);
         block.render(area, buf);
 
+        if let Some((path, details)) = self.expanded {
+            self.render_expanded(path, details, inner, buf);
+            return;
+        }
+
         if self.repos.is_empty() && self.commits.is_empty() {
-            let text = Paragraph::new("No repositories configured")
+            // Distinguish an empty config from paths that failed to open
+            let hint = if self.config.repos.is_empty() {
+                "No repositories configured - add paths to `git.repos` via `phosphor config edit`"
+            } else if self.loading {
+                "Reading repositories..."
+            } else {
+                "No readable repositories - check the `git.repos` paths in your config"
+            };
+            let text = Paragraph::new(hint)
                 .style(Style::default().fg(self.theme.dim))
-                .alignment(Alignment::Center);
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true });
             text.render(inner, buf);
             return;
         }
@@ -66,7 +128,7 @@ impl Widget for GitWidget<'_> {
 
 impl GitWidget<'_> {
     fn render_repos(&self, area: Rect, buf: &mut Buffer) {
-        if self.repos.is_empty() {
+        if self.repos.is_empty() || area.height == 0 {
             return;
         }
 
@@ -79,78 +141,125 @@ impl GitWidget<'_> {
         Paragraph::new(header).render(Rect::new(area.x, y, area.width, 1), buf);
         y += 1;
 
-        for repo in self.repos.iter().take((area.height - 1) as usize) {
-            let branch_icon = if repo.is_clean { "" } else { "" };
-            let status_icon = if repo.is_clean { "✓" } else { "●" };
-            let status_color = if repo.is_clean {
-                self.theme.dim
-            } else {
-                self.theme.accent
-            };
+        for (i, repo) in self.repos.iter().enumerate().take((area.height - 1) as usize) {
+            let line = self.repo_line(repo, self.selected == Some(i));
+            Paragraph::new(line).render(Rect::new(area.x, y, area.width, 1), buf);
+            y += 1;
+        }
+    }
 
-            let mut spans = vec![
-                Span::styled(
-                    format!("{} ", branch_icon),
-                    Style::default().fg(self.theme.foreground),
-                ),
-                Span::styled(
-                    format!("{} ", repo.name),
-                    Style::default().fg(self.theme.foreground),
-                ),
-                Span::styled(
-                    format!(" {} ", repo.branch),
-                    Style::default().fg(self.theme.dim),
-                ),
-                Span::styled(
-                    status_icon,
-                    Style::default().fg(status_color),
-                ),
-            ];
-
-            // Add ahead/behind indicators
-            if repo.ahead > 0 {
+    /// Name, branch, status icon, ahead/behind and change counts
+    fn repo_line(&self, repo: &RepoStatus, selected: bool) -> Line<'_> {
+        let icons = &self.config.icons;
+        let (status_icon, status_style) = if repo.is_clean {
+            (&icons.clean, Style::default().fg(self.theme.dim))
+        } else if repo.exceeds_dirty_threshold(self.config.dirty_warn_threshold) {
+            (
+                &icons.warn,
+                Style::default()
+                    .fg(self.theme.warning)
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (&icons.dirty, Style::default().fg(self.theme.accent))
+        };
+
+        let (marker, name_style) = if selected {
+            ("▸", Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD))
+        } else {
+            (icons.branch.as_str(), Style::default().fg(self.theme.foreground))
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                format!("{} ", marker),
+                Style::default().fg(self.theme.foreground),
+            ),
+            Span::styled(
+                format!("{} ", repo.name),
+                name_style,
+            ),
+            Span::styled(
+                format!(" {} ", repo.branch_label()),
+                Style::default().fg(self.theme.dim),
+            ),
+            Span::styled(
+                status_icon.as_str(),
+                status_style,
+            ),
+        ];
+
+        if repo.conflicts > 0 {
+            spans.push(Span::styled(
+                format!(" {}{}", icons.conflict, repo.conflicts),
+                Style::default().fg(self.theme.warning).add_modifier(Modifier::BOLD),
+            ));
+        }
+
+        // Add ahead/behind indicators
+        if repo.ahead > 0 {
+            spans.push(Span::styled(
+                format!(" {}{}", icons.ahead, repo.ahead),
+                Style::default().fg(self.theme.accent),
+            ));
+        }
+        if repo.behind > 0 {
+            spans.push(Span::styled(
+                format!(" {}{}", icons.behind, repo.behind),
+                Style::default().fg(self.theme.dim),
+            ));
+        }
+
+        // Add change counts if dirty
+        if !repo.is_clean {
+            if repo.modified > 0 {
                 spans.push(Span::styled(
-                    format!(" ↑{}", repo.ahead),
+                    format!(" ~{}", repo.modified),
                     Style::default().fg(self.theme.accent),
                 ));
             }
-            if repo.behind > 0 {
+            if repo.staged > 0 {
                 spans.push(Span::styled(
-                    format!(" ↓{}", repo.behind),
+                    format!(" +{}", repo.staged),
+                    Style::default().fg(self.theme.foreground),
+                ));
+            }
+            if repo.untracked > 0 {
+                spans.push(Span::styled(
+                    format!(" ?{}", repo.untracked),
                     Style::default().fg(self.theme.dim),
                 ));
             }
-
-            // Add change counts if dirty
-            if !repo.is_clean {
-                if repo.modified > 0 {
-                    spans.push(Span::styled(
-                        format!(" ~{}", repo.modified),
-                        Style::default().fg(self.theme.accent),
-                    ));
-                }
-                if repo.staged > 0 {
-                    spans.push(Span::styled(
-                        format!(" +{}", repo.staged),
-                        Style::default().fg(self.theme.foreground),
-                    ));
-                }
-                if repo.untracked > 0 {
-                    spans.push(Span::styled(
-                        format!(" ?{}", repo.untracked),
-                        Style::default().fg(self.theme.dim),
-                    ));
-                }
+            if repo.insertions + repo.deletions > 0 {
+                spans.push(Span::styled(
+                    format!(" +{}", repo.insertions),
+                    Style::default().fg(self.theme.foreground),
+                ));
+                spans.push(Span::styled(
+                    format!(" −{}", repo.deletions),
+                    Style::default().fg(self.theme.dim),
+                ));
             }
+        }
 
-            let line = Line::from(spans);
-            Paragraph::new(line).render(Rect::new(area.x, y, area.width, 1), buf);
-            y += 1;
+        if repo.stashes > 0 {
+            spans.push(Span::styled(
+                format!(" {}{}", icons.stash, repo.stashes),
+                Style::default().fg(self.theme.dim),
+            ));
+        }
+        if repo.stale_submodules > 0 {
+            spans.push(Span::styled(
+                format!(" {}{}", icons.submodule, repo.stale_submodules),
+                Style::default().fg(self.theme.warning),
+            ));
         }
+
+        Line::from(spans)
     }
 
     fn render_commits(&self, area: Rect, buf: &mut Buffer) {
-        if self.commits.is_empty() {
+        if self.commits.is_empty() || area.height == 0 {
             return;
         }
 
@@ -163,59 +272,246 @@ impl GitWidget<'_> {
         Paragraph::new(header).render(Rect::new(area.x, y, area.width, 1), buf);
         y += 1;
 
-        for commit in self.commits.iter().take((area.height - 1) as usize) {
-            let hash_short = if commit.hash.len() >= 7 {
-                &commit.hash[..7]
+        // Keep the selected commit on screen, with a scrollbar when they don't all fit
+        let rows = (area.height - 1) as usize;
+        let selected = self
+            .selected
+            .and_then(|selected| selected.checked_sub(self.repos.len()));
+        let start = selected.map_or(0, |selected| (selected + 1).saturating_sub(rows));
+        let scrolls = self.commits.len() > rows;
+        let width = if scrolls { area.width.saturating_sub(1) } else { area.width };
+
+        for (i, commit) in self.commits.iter().enumerate().skip(start).take(rows) {
+            let line = self.commit_line(commit, width, true, selected == Some(i));
+            Paragraph::new(line).render(Rect::new(area.x, y, width, 1), buf);
+            y += 1;
+        }
+
+        if scrolls {
+            let mut state = ScrollbarState::new(self.commits.len() - rows).position(start);
+            Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_symbol(Some("│"))
+                .track_style(Style::default().fg(self.theme.dim))
+                .thumb_style(Style::default().fg(self.theme.accent))
+                .render(Rect::new(area.x, area.y + 1, area.width, area.height - 1), buf, &mut state);
+        }
+    }
+
+    /// Short hash and subject, plus the repo it's from in the overview
+    fn commit_line(&self, commit: &CommitInfo, width: u16, show_repo: bool, selected: bool) -> Line<'_> {
+        let hash_short = if commit.hash.len() >= 7 {
+            &commit.hash[..7]
+        } else {
+            &commit.hash
+        };
+
+        let age = format_ago(commit.timestamp());
+
+        // Truncate message to fit
+        let reserved = if show_repo { 30 } else { 10 } + age.len() + 1;
+        let max_msg_len = (width as usize).saturating_sub(reserved);
+        let message = if commit.message.len() > max_msg_len {
+            format!("{}…", &commit.message[..max_msg_len.saturating_sub(1)])
+        } else {
+            commit.message.clone()
+        };
+
+        let (marker, message_style) = if selected {
+            ("▸", Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD))
+        } else {
+            (" ", Style::default().fg(self.theme.foreground))
+        };
+
+        let mut spans = vec![
+            Span::styled(
+                marker,
+                Style::default().fg(self.theme.accent),
+            ),
+            Span::styled(
+                format!("{} ", hash_short),
+                Style::default().fg(self.theme.dim),
+            ),
+            Span::styled(
+                message,
+                message_style,
+            ),
+        ];
+        if show_repo {
+            spans.push(Span::styled(
+                format!(" ({})", commit.repo_name),
+                Style::default().fg(self.theme.dim),
+            ));
+        }
+        spans.push(Span::styled(
+            format!(" {}", age),
+            Style::default().fg(self.theme.dim),
+        ));
+        Line::from(spans)
+    }
+
+    fn render_expanded(
+        &self,
+        path: &PathBuf,
+        details: Option<&RepoDetails>,
+        area: Rect,
+        buf: &mut Buffer,
+    ) {
+        let Some(repo) = self.repos.iter().find(|repo| &repo.path == path) else {
+            return;
+        };
+        if area.height == 0 {
+            return;
+        }
+        let line = self.repo_line(repo, false);
+        Paragraph::new(line).render(Rect::new(area.x, area.y, area.width, 1), buf);
+        let area = Rect::new(area.x, area.y + 1, area.width, area.height - 1);
+
+        let Some(details) = details else {
+            Paragraph::new(format!("Reading {}...", repo.name))
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        };
+
+        // Changes get the room they need (at least one line for "clean"),
+        // branches a few lines, commits the rest
+        let chunks = Layout::vertical([
+            Constraint::Length(details.branches.len().clamp(1, 3) as u16 + 1),
+            Constraint::Length(details.files.len().clamp(1, 8) as u16 + 1),
+            Constraint::Min(2),
+        ])
+        .split(area);
+
+        let header = |title: String| {
+            Line::from(Span::styled(
+                title,
+                Style::default().fg(self.theme.foreground).add_modifier(Modifier::BOLD),
+            ))
+        };
+
+        // Branches, the current one first and marked
+        let mut lines = vec![header(format!("Branches ({})", details.branches.len()))];
+        for (i, branch) in details.branches.iter().enumerate() {
+            let (marker, style) = if i == 0 && *branch == repo.branch {
+                ("* ", Style::default().fg(self.theme.accent))
             } else {
-                &commit.hash
+                ("  ", Style::default().fg(self.theme.foreground))
             };
+            lines.push(Line::from(Span::styled(format!("{}{}", marker, branch), style)));
+        }
+        Paragraph::new(lines).render(chunks[0], buf);
 
-            // Truncate message to fit
-            let max_msg_len = (area.width as usize).saturating_sub(30);
-            let message = if commit.message.len() > max_msg_len {
-                format!("{}…", &commit.message[..max_msg_len.saturating_sub(1)])
+        let mut lines = vec![header(format!("Changes ({})", details.files.len()))];
+        if details.files.is_empty() {
+            lines.push(Line::styled("  Working tree clean", Style::default().fg(self.theme.dim)));
+        }
+        let shown = chunks[1].height.saturating_sub(1) as usize;
+        for (i, file) in details.files.iter().enumerate().take(shown) {
+            // The last row says how many more there are when they don't fit
+            if i + 1 == shown && details.files.len() > shown {
+                let more = format!("  … {} more", details.files.len() - i);
+                lines.push(Line::styled(more, Style::default().fg(self.theme.dim)));
+                break;
+            }
+            // Staged changes in the accent, like `git status` colors the index
+            let code_style = if file.staged {
+                Style::default().fg(self.theme.accent)
             } else {
-                commit.message.clone()
+                Style::default().fg(self.theme.dim)
             };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} ", file.code), code_style),
+                Span::styled(file.path.as_str(), Style::default().fg(self.theme.foreground)),
+            ]));
+        }
+        Paragraph::new(lines).render(chunks[1], buf);
 
-            let line = Line::from(vec![
-                Span::styled(
-                    " ",
-                    Style::default().fg(self.theme.foreground),
-                ),
-                Span::styled(
-                    format!("{} ", hash_short),
-                    Style::default().fg(self.theme.dim),
-                ),
-                Span::styled(
-                    message,
-                    Style::default().fg(self.theme.foreground),
-                ),
-                Span::styled(
-                    format!(" ({})", commit.repo_name),
-                    Style::default().fg(self.theme.dim),
-                ),
-            ]);
-            Paragraph::new(line).render(Rect::new(area.x, y, area.width, 1), buf);
-            y += 1;
+        let mut lines = vec![header("Recent Commits".to_string())];
+        for commit in details.commits.iter().take(chunks[2].height.saturating_sub(1) as usize) {
+            lines.push(self.commit_line(commit, chunks[2].width, false, false));
         }
+        Paragraph::new(lines).render(chunks[2], buf);
     }
 }
 
+/// Keys shown on a help line: those bound to the actions, or fixed ones
+enum HelpKeys {
+    Actions(&'static [Action]),
+    /// The first and last of a run, e.g. "1-9"
+    Range(Action, Action),
+    Fixed(&'static str),
+}
+
+const HELP: [(HelpKeys, &str); 31] = [
+    (HelpKeys::Actions(&[Action::Quit]), "Quit"),
+    (HelpKeys::Actions(&[Action::PlayPause]), "Play/Pause"),
+    (HelpKeys::Actions(&[Action::Next]), "Next track"),
+    (HelpKeys::Actions(&[Action::Prev]), "Previous track"),
+    (HelpKeys::Actions(&[Action::VolumeUp, Action::VolumeDown]), "Volume up/down"),
+    (HelpKeys::Actions(&[Action::ToggleSaved]), "Like / unlike track"),
+    (HelpKeys::Actions(&[Action::SeekBack, Action::SeekForward]), "Seek -/+5s"),
+    (HelpKeys::Actions(&[Action::FocusNext, Action::FocusPrev]), "Cycle focus"),
+    (HelpKeys::Actions(&[Action::GitRefresh]), "Refresh git status"),
+    (HelpKeys::Fixed("Enter / Esc"), "Git: open / close repo details"),
+    (HelpKeys::Actions(&[Action::ToggleLyrics]), "Toggle lyrics"),
+    (HelpKeys::Actions(&[Action::RefetchLyrics]), "Re-fetch lyrics (lyrics focused)"),
+    (HelpKeys::Actions(&[Action::Search]), "Search and play"),
+    (HelpKeys::Actions(&[Action::Devices]), "Choose playback device"),
+    (HelpKeys::Actions(&[Action::AudioDevices]), "Choose audio capture device"),
+    (HelpKeys::Actions(&[Action::CopyUrl, Action::OpenUrl]), "Copy / open track link"),
+    (HelpKeys::Actions(&[Action::Radio]), "Radio from this track"),
+    (HelpKeys::Actions(&[Action::SleepTimer]), "Sleep timer (cycles, then off)"),
+    (HelpKeys::Actions(&[Action::ToggleQueue]), "Toggle queue"),
+    (HelpKeys::Actions(&[Action::ToggleHistory]), "Toggle history (Enter replays)"),
+    (HelpKeys::Fixed("Enter / x"), "Queue: play / remove"),
+    (HelpKeys::Actions(&[Action::ToggleGitHub]), "Toggle GitHub (x marks read)"),
+    (HelpKeys::Actions(&[Action::ArtStyle]), "Toggle art style"),
+    (HelpKeys::Actions(&[Action::Channels]), "Visualizer channels: mono / split / stacked"),
+    (HelpKeys::Actions(&[Action::SpectrumMode]), "Spectrum: bars / mirror / wings"),
+    (HelpKeys::Actions(&[Action::WaveformStyle]), "Waveform: bars / braille"),
+    (HelpKeys::Actions(&[Action::FftSize]), "FFT size: 512 to 8192"),
+    (HelpKeys::Range(Action::TogglePanel(1), Action::TogglePanel(9)), "Hide / show panels"),
+    (HelpKeys::Actions(&[Action::SavePanels]), "Save hidden panels to config"),
+    (HelpKeys::Actions(&[Action::Help]), "Toggle help"),
+    (HelpKeys::Actions(&[Action::Messages]), "Recent messages and errors"),
+];
+
 pub struct HelpWidget<'a> {
     theme: &'a Theme,
+    keymap: &'a Keymap,
 }
 
 impl<'a> HelpWidget<'a> {
-    pub fn new(theme: &'a Theme) -> Self {
-        Self { theme }
+    pub fn new(theme: &'a Theme, keymap: &'a Keymap) -> Self {
+        Self { theme, keymap }
+    }
+
+    /// "q / Esc" for one action's bindings, "+ / -" for the first binding of
+    /// each of a pair; None when nothing is bound
+    fn keys(&self, keys: &HelpKeys) -> Option<String> {
+        let names: Vec<String> = match keys {
+            HelpKeys::Fixed(keys) => return Some(keys.to_string()),
+            HelpKeys::Range(first, last) => {
+                let first = self.keymap.keys(*first).into_iter().next()?;
+                let last = self.keymap.keys(*last).into_iter().next()?;
+                return Some(format!("{}-{}", first, last));
+            }
+            HelpKeys::Actions([action]) => self.keymap.keys(*action),
+            HelpKeys::Actions(actions) => actions
+                .iter()
+                .filter_map(|action| self.keymap.keys(*action).into_iter().next())
+                .collect(),
+        };
+        (!names.is_empty()).then(|| names.join(" / "))
     }
 }
 
 impl Widget for HelpWidget<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let block = Block::default()
-            .borders(Borders::ALL)
+        let block = self.theme.frame()
             .border_style(Style::default().fg(self.theme.accent))
             .title(" Help ")
             .title_style(Style::default().fg(self.theme.foreground));
@@ -223,48 +519,16 @@ impl Widget for HelpWidget<'_> {
         let inner = block.inner(area);
         block.render(area, buf);
 
-        let help_text = vec![
-            Line::from(vec![
-                Span::styled("q / Esc", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Quit", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("Space", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Play/Pause", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("n", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Next track", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("p", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Previous track", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("+ / -", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Volume up/down", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("Tab", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Cycle focus", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("r", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Refresh git status", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("l", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Toggle lyrics", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("a", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Toggle art style", Style::default().fg(self.theme.foreground)),
-            ]),
-            Line::from(vec![
-                Span::styled("?", Style::default().fg(self.theme.accent)),
-                Span::styled(" - Toggle help", Style::default().fg(self.theme.foreground)),
-            ]),
-        ];
+        let help_text: Vec<Line> = HELP
+            .iter()
+            .filter_map(|(keys, description)| {
+                let keys = self.keys(keys)?;
+                Some(Line::from(vec![
+                    Span::styled(keys, Style::default().fg(self.theme.accent)),
+                    Span::styled(format!(" - {}", description), Style::default().fg(self.theme.foreground)),
+                ]))
+            })
+            .collect();
 
         let paragraph = Paragraph::new(help_text);
         paragraph.render(inner, buf);
@@ -0,0 +1,122 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+use crate::modules::github::Notification;
+use crate::modules::spotify::format_ago;
+use crate::tui::theme::Theme;
+
+pub struct NotificationsWidget<'a> {
+    notifications: &'a [Notification],
+    selected: usize,
+    /// Shown instead of the list (loading, no token, request failed)
+    message: Option<&'a str>,
+    theme: &'a Theme,
+    focused: bool,
+}
+
+impl<'a> NotificationsWidget<'a> {
+    pub fn new(
+        notifications: &'a [Notification],
+        selected: usize,
+        message: Option<&'a str>,
+        theme: &'a Theme,
+        focused: bool,
+    ) -> Self {
+        Self { notifications, selected, message, theme, focused }
+    }
+
+    fn render_centered(&self, text: &str, area: Rect, buf: &mut Buffer) {
+        let y_offset = area.height / 2;
+        if y_offset < area.height {
+            let text_area = Rect::new(area.x, area.y + y_offset, area.width, area.height - y_offset);
+            Paragraph::new(text)
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .render(text_area, buf);
+        }
+    }
+}
+
+impl Widget for NotificationsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(self.theme.accent)
+        } else {
+            Style::default().fg(self.theme.dim)
+        };
+
+        let title = match self.message {
+            Some(_) => " GitHub ".to_string(),
+            None => format!(" GitHub ({}) ", self.notifications.len()),
+        };
+        let mut block = self.theme.block(title, border_style);
+        if self.focused && self.message.is_none() && !self.notifications.is_empty() && !self.theme.borderless() {
+            block = block.title_bottom(
+                Line::styled(" x mark read ", Style::default().fg(self.theme.dim)).right_aligned(),
+            );
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if let Some(message) = self.message {
+            self.render_centered(message, inner, buf);
+            return;
+        }
+        if self.notifications.is_empty() {
+            self.render_centered("No unread notifications", inner, buf);
+            return;
+        }
+
+        let height = inner.height as usize;
+        if height == 0 {
+            return;
+        }
+
+        // Keep the selection on screen
+        let selected = self.selected.min(self.notifications.len() - 1);
+        let start = (selected + 1).saturating_sub(height);
+
+        let lines: Vec<Line> = self
+            .notifications
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(i, notification)| {
+                let is_selected = self.focused && i == selected;
+                let marker = if is_selected { "▸ " } else { "  " };
+                let title_style = if is_selected {
+                    Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                // CI failures are the ones that need acting on
+                let label_style = if notification.reason == "ci_activity" {
+                    Style::default().fg(self.theme.warning)
+                } else {
+                    Style::default().fg(self.theme.accent)
+                };
+
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(self.theme.accent)),
+                    Span::styled(format!("{:>8} ", notification.label()), label_style),
+                    Span::styled(notification.title.clone(), title_style),
+                    Span::styled(format!(" - {}", notification.repo), Style::default().fg(self.theme.dim)),
+                    Span::styled(
+                        format!(" ({})", format_ago(notification.updated_at)),
+                        Style::default().fg(self.theme.dim),
+                    ),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
@@ -0,0 +1,129 @@
+use chrono::{Datelike, Days};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Color, Style},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+use crate::modules::git::Activity;
+use crate::tui::theme::Theme;
+
+const MONTHS: [&str; 12] = [
+    "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+];
+
+/// Weekday labels down the left edge, Sunday first like the grid
+const WEEKDAYS: [&str; 7] = ["", "Mon", "", "Wed", "", "Fri", ""];
+
+/// Width of the weekday labels
+const GUTTER: u16 = 4;
+
+/// Each week is a column of one cell plus a space
+const WEEK_WIDTH: u16 = 2;
+
+/// Shades from quiet to busy days, like GitHub's four
+const LEVELS: f32 = 4.0;
+
+/// GitHub-style grid of commits per day: weeks left to right, days top to bottom
+pub struct HeatmapWidget<'a> {
+    /// None until the first git refresh
+    activity: Option<&'a Activity>,
+    theme: &'a Theme,
+    focused: bool,
+}
+
+impl<'a> HeatmapWidget<'a> {
+    pub fn new(activity: Option<&'a Activity>, theme: &'a Theme, focused: bool) -> Self {
+        Self { activity, theme, focused }
+    }
+
+    /// Empty days stay faint; the rest climb the amber gradient in steps
+    /// relative to the busiest day
+    fn color(&self, count: u32, max: u32) -> Color {
+        if count == 0 {
+            return self.theme.fade(self.theme.dim, 0.5);
+        }
+        let level = (count as f32 / max as f32 * LEVELS).ceil() / LEVELS;
+        self.theme.gradient(0.25 + 0.75 * level)
+    }
+}
+
+impl Widget for HeatmapWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(self.theme.accent)
+        } else {
+            Style::default().fg(self.theme.dim)
+        };
+
+        let title = match self.activity {
+            Some(activity) => format!(" Contributions ({}) ", activity.total()),
+            None => " Contributions ".to_string(),
+        };
+        let block = self.theme.block(title, border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let Some(activity) = self.activity else {
+            let y_offset = inner.height / 2;
+            if y_offset < inner.height {
+                let text_area = Rect::new(inner.x, inner.y + y_offset, inner.width, inner.height - y_offset);
+                Paragraph::new("Reading repositories...")
+                    .style(Style::default().fg(self.theme.dim))
+                    .alignment(Alignment::Center)
+                    .wrap(Wrap { trim: true })
+                    .render(text_area, buf);
+            }
+            return;
+        };
+        if inner.width <= GUTTER || inner.height < 2 {
+            return;
+        }
+
+        // The most recent weeks that fit
+        let weeks = activity.days.len().div_ceil(7);
+        let shown = weeks.min(((inner.width - GUTTER) / WEEK_WIDTH) as usize);
+        let first_week = weeks - shown;
+        let max = activity.days.iter().copied().max().unwrap_or(0).max(1);
+
+        let label_style = Style::default().fg(self.theme.dim);
+        let grid_y = inner.y + 1;
+        for (day, label) in WEEKDAYS.iter().enumerate() {
+            let y = grid_y + day as u16;
+            if y >= inner.bottom() {
+                break;
+            }
+            buf.set_string(inner.x, y, label, label_style);
+        }
+
+        // Month names over the week their first day falls in
+        let mut label_end = inner.x;
+        for week in 0..shown {
+            let x = inner.x + GUTTER + week as u16 * WEEK_WIDTH;
+            let days = ((first_week + week) * 7 + 6) as u64;
+            let Some(saturday) = activity.start.checked_add_days(Days::new(days)) else {
+                continue;
+            };
+            // The 1st is in this week when the week ends within the month's first 7 days
+            let starts_month = saturday.day() <= 7;
+            if (starts_month || week == 0) && x >= label_end && x + 3 <= inner.right() {
+                let month = MONTHS[saturday.month0() as usize];
+                buf.set_string(x, inner.y, month, label_style);
+                label_end = x + 4;
+            }
+
+            for day in 0..7 {
+                let y = grid_y + day as u16;
+                let Some(&count) = activity.days.get((first_week + week) * 7 + day) else {
+                    break;
+                };
+                if y >= inner.bottom() {
+                    break;
+                }
+                buf.set_string(x, y, "■", Style::default().fg(self.color(count, max)));
+            }
+        }
+    }
+}
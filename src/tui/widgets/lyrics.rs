@@ -3,16 +3,28 @@ use ratatui::{
     layout::{Alignment, Rect},
     style::{Modifier, Style},
     text::Line,
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget, Wrap},
 };
+use unicode_bidi::BidiInfo;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 
+use crate::config::LyricsConfig;
 use crate::modules::lyrics::{LyricsStatus, SyncedLyrics};
+use crate::modules::spotify::SpotifyStatus;
 use crate::tui::theme::Theme;
 
 pub struct LyricsWidget<'a> {
     lyrics: Option<&'a SyncedLyrics>,
     status: &'a LyricsStatus,
     progress_ms: u64,
+    spotify: SpotifyStatus,
+    has_track: bool,
+    context_lines: usize,
+    fade: bool,
+    reorder_rtl: bool,
+    /// Line scrolled to by hand, centered and marked instead of the current one
+    cursor: Option<usize>,
     theme: &'a Theme,
     focused: bool,
 }
@@ -22,6 +34,8 @@ impl<'a> LyricsWidget<'a> {
         lyrics: Option<&'a SyncedLyrics>,
         status: &'a LyricsStatus,
         progress_ms: u64,
+        spotify: SpotifyStatus,
+        has_track: bool,
         theme: &'a Theme,
         focused: bool,
     ) -> Self {
@@ -29,10 +43,29 @@ impl<'a> LyricsWidget<'a> {
             lyrics,
             status,
             progress_ms,
+            spotify,
+            has_track,
+            context_lines: 0,
+            fade: false,
+            reorder_rtl: true,
+            cursor: None,
             theme,
             focused,
         }
     }
+
+    /// Apply the highlight window and fade settings from config
+    pub fn config(mut self, config: &LyricsConfig) -> Self {
+        self.context_lines = config.context_lines;
+        self.fade = config.fade;
+        self.reorder_rtl = config.reorder_rtl;
+        self
+    }
+
+    pub fn cursor(mut self, cursor: Option<usize>) -> Self {
+        self.cursor = cursor;
+        self
+    }
 }
 
 impl Widget for LyricsWidget<'_> {
@@ -43,15 +76,22 @@ impl Widget for LyricsWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(" ♪ Lyrics ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let mut block = self.theme.block(" ♪ Lyrics ", border_style);
+        if self.cursor.is_some() && !self.theme.borderless() {
+            block = block.title_bottom(
+                Line::styled(" Enter seek · Esc follow ", Style::default().fg(self.theme.dim)).right_aligned(),
+            );
+        }
 
         let inner = block.inner(area);
         block.render(area, buf);
 
+        // Without a track the lyrics status is meaningless, explain why instead
+        if !self.has_track {
+            self.render_centered(self.spotify.idle_hint(), inner, buf);
+            return;
+        }
+
         match self.status {
             LyricsStatus::Loading => {
                 self.render_centered("Loading lyrics...", inner, buf);
@@ -76,12 +116,13 @@ impl LyricsWidget<'_> {
     fn render_centered(&self, text: &str, area: Rect, buf: &mut Buffer) {
         let paragraph = Paragraph::new(text)
             .style(Style::default().fg(self.theme.dim))
-            .alignment(Alignment::Center);
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
 
-        // Center vertically
+        // Center vertically, leaving room for long hints to wrap
         let y_offset = area.height / 2;
         if y_offset < area.height {
-            let centered_area = Rect::new(area.x, area.y + y_offset, area.width, 1);
+            let centered_area = Rect::new(area.x, area.y + y_offset, area.width, area.height - y_offset);
             paragraph.render(centered_area, buf);
         }
     }
@@ -95,8 +136,10 @@ impl LyricsWidget<'_> {
         let current_idx = lyrics.current_line_index(self.progress_ms);
         let center_offset = height / 2;
 
-        // Calculate start index to center current line
-        let start_idx = current_idx
+        // Calculate start index to center the current (or scrolled-to) line
+        let start_idx = self
+            .cursor
+            .or(current_idx)
             .map(|idx| idx.saturating_sub(center_offset))
             .unwrap_or(0);
 
@@ -108,42 +151,92 @@ impl LyricsWidget<'_> {
             let line = &lyrics.lines[line_idx];
             let y = area.y + row as u16;
 
-            // Determine style based on position relative to current
-            let style = match current_idx {
-                Some(curr) if line_idx == curr => {
-                    // Current line: bright accent, bold
-                    Style::default()
-                        .fg(self.theme.accent)
-                        .add_modifier(Modifier::BOLD)
-                }
-                Some(curr) if line_idx < curr => {
-                    // Past line: dim
-                    Style::default().fg(self.theme.dim)
-                }
-                _ => {
-                    // Future line or no current: normal foreground
-                    Style::default().fg(self.theme.foreground)
-                }
-            };
+            let mut style = self.line_style(line_idx, current_idx);
+            if self.cursor == Some(line_idx) {
+                style = style.add_modifier(Modifier::REVERSED);
+            }
 
-            // Truncate if needed
-            let text = truncate(&line.text, area.width as usize);
+            // Truncate if needed, right-aligning right-to-left lines
+            let (text, rtl) = display_line(&line.text, area.width as usize, self.reorder_rtl);
             let line_widget = Line::from(text);
+            let alignment = if rtl { Alignment::Right } else { Alignment::Center };
 
             let paragraph = Paragraph::new(line_widget)
                 .style(style)
-                .alignment(Alignment::Center);
+                .alignment(alignment);
 
             paragraph.render(Rect::new(area.x, y, area.width, 1), buf);
         }
     }
+
+    /// Style a line by its distance from the current one
+    fn line_style(&self, line_idx: usize, current_idx: Option<usize>) -> Style {
+        // Before the first lyric, treat every line as upcoming
+        let distance = match current_idx {
+            Some(curr) => line_idx.abs_diff(curr),
+            None => line_idx + 1,
+        };
+
+        if current_idx.is_some() && distance == 0 {
+            // Current line: bright accent, bold
+            return Style::default()
+                .fg(self.theme.accent)
+                .add_modifier(Modifier::BOLD);
+        }
+
+        if distance > self.context_lines {
+            // Outside the highlight window: dim
+            return Style::default().fg(self.theme.dim);
+        }
+
+        if self.fade {
+            // Nearest neighbours brightest, ramping down towards the window edge
+            let intensity = 1.0 - distance as f32 / (self.context_lines + 1) as f32;
+            Style::default().fg(self.theme.gradient(intensity))
+        } else {
+            Style::default().fg(self.theme.foreground)
+        }
+    }
 }
 
+/// Truncate a line and, if it contains right-to-left text, reorder it for display.
+/// Returns the text and whether the line's base direction is right-to-left.
+fn display_line(text: &str, max_width: usize, reorder: bool) -> (String, bool) {
+    let truncated = truncate(text, max_width);
+
+    let bidi = BidiInfo::new(&truncated, None);
+    if !bidi.has_rtl() {
+        return (truncated, false);
+    }
+    let Some(para) = bidi.paragraphs.first() else {
+        return (truncated, false);
+    };
+
+    let rtl = para.level.is_rtl();
+    if !reorder {
+        return (truncated.clone(), rtl);
+    }
+    // Terminals lay out cells left-to-right, so hand them visual order
+    let visual = bidi.reorder_line(para, para.range.clone()).into_owned();
+    (visual, rtl)
+}
+
+/// Truncate to a display width without splitting grapheme clusters
 fn truncate(text: &str, max_width: usize) -> String {
-    if text.chars().count() <= max_width {
-        text.to_string()
-    } else {
-        let truncated: String = text.chars().take(max_width.saturating_sub(3)).collect();
-        format!("{}...", truncated)
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(3);
+    let mut truncated = String::new();
+    let mut width = 0;
+    for grapheme in text.graphemes(true) {
+        let grapheme_width = grapheme.width();
+        if width + grapheme_width > budget {
+            break;
+        }
+        width += grapheme_width;
+        truncated.push_str(grapheme);
     }
+    format!("{}...", truncated)
 }
@@ -1,5 +1,16 @@
 pub mod album_art;
+pub mod boot;
+pub mod devices;
 pub mod git;
+pub mod github;
+pub mod heatmap;
+pub mod history;
 pub mod lyrics;
+pub mod queue;
+pub mod screensaver;
+pub mod search;
+pub mod splash;
 pub mod spotify;
+pub mod status_bar;
+pub mod toast;
 pub mod visualizer;
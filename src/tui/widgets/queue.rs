@@ -0,0 +1,111 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget, Wrap},
+};
+
+use crate::modules::spotify::{format_time, needs_hours, QueueItem, SpotifyStatus};
+use crate::tui::theme::Theme;
+
+pub struct QueueWidget<'a> {
+    /// None when the player backend has no queue (MPRIS)
+    queue: Option<&'a [QueueItem]>,
+    selected: usize,
+    spotify: SpotifyStatus,
+    theme: &'a Theme,
+    focused: bool,
+}
+
+impl<'a> QueueWidget<'a> {
+    pub fn new(
+        queue: Option<&'a [QueueItem]>,
+        selected: usize,
+        spotify: SpotifyStatus,
+        theme: &'a Theme,
+        focused: bool,
+    ) -> Self {
+        Self { queue, selected, spotify, theme, focused }
+    }
+
+    fn render_centered(&self, text: &str, area: Rect, buf: &mut Buffer) {
+        let y_offset = area.height / 2;
+        if y_offset < area.height {
+            let text_area = Rect::new(area.x, area.y + y_offset, area.width, area.height - y_offset);
+            Paragraph::new(text)
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: true })
+                .render(text_area, buf);
+        }
+    }
+}
+
+impl Widget for QueueWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(self.theme.accent)
+        } else {
+            Style::default().fg(self.theme.dim)
+        };
+
+        let block = self.theme.block(" Up Next ", border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.spotify != SpotifyStatus::Connected {
+            self.render_centered(self.spotify.idle_hint(), inner, buf);
+            return;
+        }
+
+        let queue = match self.queue {
+            Some(queue) if !queue.is_empty() => queue,
+            Some(_) => {
+                self.render_centered("Queue is empty", inner, buf);
+                return;
+            }
+            None => {
+                self.render_centered("Queue not available for this player", inner, buf);
+                return;
+            }
+        };
+
+        let height = inner.height as usize;
+        if height == 0 {
+            return;
+        }
+
+        // Keep the selection on screen
+        let selected = self.selected.min(queue.len() - 1);
+        let start = (selected + 1).saturating_sub(height);
+
+        let lines: Vec<Line> = queue
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(i, item)| {
+                let is_selected = self.focused && i == selected;
+                let marker = if is_selected { "▸ " } else { "  " };
+                let title_style = if is_selected {
+                    Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+                let length = format_time(item.duration, needs_hours(item.duration));
+
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(self.theme.accent)),
+                    Span::styled(format!("{:>2}. ", i + 1), Style::default().fg(self.theme.dim)),
+                    Span::styled(item.name.clone(), title_style),
+                    Span::styled(format!(" - {}", item.artist), Style::default().fg(self.theme.dim)),
+                    Span::styled(format!(" ({})", length), Style::default().fg(self.theme.dim)),
+                ])
+            })
+            .collect();
+
+        Paragraph::new(lines).render(inner, buf);
+    }
+}
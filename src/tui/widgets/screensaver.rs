@@ -0,0 +1,96 @@
+use chrono::{DateTime, Local, Timelike};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::modules::spotify::TrackInfo;
+use crate::tui::theme::Theme;
+
+/// Digits 0-9 in the splash logo's block style, each cell drawn two wide
+const DIGITS: [[&str; 5]; 10] = [
+    ["███", "█ █", "█ █", "█ █", "███"],
+    [" █ ", "██ ", " █ ", " █ ", "███"],
+    ["███", "  █", "███", "█  ", "███"],
+    ["███", "  █", "███", "  █", "███"],
+    ["█ █", "█ █", "███", "  █", "  █"],
+    ["███", "█  ", "███", "  █", "███"],
+    ["███", "█  ", "███", "█ █", "███"],
+    ["███", "  █", "  █", "  █", "  █"],
+    ["███", "█ █", "███", "█ █", "███"],
+    ["███", "█ █", "███", "  █", "███"],
+];
+
+const COLON: [&str; 5] = [" ", "█", " ", "█", " "];
+
+/// Rows taken by the clock and the track line under it
+pub const CLOCK_ROWS: u16 = 7;
+
+/// Big block-digit clock with the playing track underneath, for the idle screen
+pub struct ScreensaverWidget<'a> {
+    theme: &'a Theme,
+    time: DateTime<Local>,
+    track: Option<&'a TrackInfo>,
+}
+
+impl<'a> ScreensaverWidget<'a> {
+    pub fn new(theme: &'a Theme, time: DateTime<Local>, track: Option<&'a TrackInfo>) -> Self {
+        Self { theme, time, track }
+    }
+}
+
+impl Widget for ScreensaverWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let digits = [self.time.hour() / 10, self.time.hour() % 10, self.time.minute() / 10, self.time.minute() % 10];
+        let glyphs = [
+            &DIGITS[digits[0] as usize],
+            &DIGITS[digits[1] as usize],
+            &COLON,
+            &DIGITS[digits[2] as usize],
+            &DIGITS[digits[3] as usize],
+        ];
+
+        // Two columns per cell and one cell between glyphs
+        let glyph_width = |glyph: &[&str; 5]| glyph[0].chars().count() as u16 * 2;
+        let width = glyphs.iter().map(|glyph| glyph_width(glyph) + 2).sum::<u16>() - 2;
+        if area.width < width || area.height < CLOCK_ROWS {
+            // Too small for the big digits
+            Paragraph::new(self.time.format("%H:%M").to_string())
+                .style(Style::default().fg(self.theme.accent))
+                .alignment(Alignment::Center)
+                .render(Rect { height: 1, ..area }, buf);
+            return;
+        }
+
+        let mut x = area.x + (area.width - width) / 2;
+        for glyph in glyphs {
+            for (row, line) in glyph.iter().enumerate() {
+                // Brighter towards the bottom, like the splash logo
+                let color = self.theme.gradient(0.4 + 0.6 * row as f32 / 4.0);
+                for (col, ch) in line.chars().enumerate() {
+                    if ch != ' ' {
+                        let cell_x = x + col as u16 * 2;
+                        let y = area.y + row as u16;
+                        buf[(cell_x, y)].set_char(ch).set_fg(color);
+                        buf[(cell_x + 1, y)].set_char(ch).set_fg(color);
+                    }
+                }
+            }
+            x += glyph_width(glyph) + 2;
+        }
+
+        if let Some(track) = self.track {
+            let line = if track.artist.is_empty() {
+                track.name.clone()
+            } else {
+                format!("{} - {}", track.name, track.artist)
+            };
+            Paragraph::new(line)
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .render(Rect::new(area.x, area.y + 6, area.width, 1), buf);
+        }
+    }
+}
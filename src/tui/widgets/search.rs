@@ -0,0 +1,114 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::modules::spotify::{SearchItem, SearchKind};
+use crate::tui::theme::Theme;
+
+/// Search overlay: a query line above the results list
+pub struct SearchWidget<'a> {
+    query: &'a str,
+    results: &'a [SearchItem],
+    selected: usize,
+    /// Shown instead of the results (searching, errors, hints)
+    message: Option<&'a str>,
+    theme: &'a Theme,
+}
+
+impl<'a> SearchWidget<'a> {
+    pub fn new(
+        query: &'a str,
+        results: &'a [SearchItem],
+        selected: usize,
+        message: Option<&'a str>,
+        theme: &'a Theme,
+    ) -> Self {
+        Self { query, results, selected, message, theme }
+    }
+}
+
+fn kind_label(kind: SearchKind) -> &'static str {
+    match kind {
+        SearchKind::Track => "track",
+        SearchKind::Album => "album",
+        SearchKind::Playlist => "list ",
+    }
+}
+
+impl Widget for SearchWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self.theme.frame()
+            .border_style(Style::default().fg(self.theme.accent))
+            .style(Style::default().bg(self.theme.bg()))
+            .title(" Search Spotify ")
+            .title_style(Style::default().fg(self.theme.foreground))
+            .title_bottom(" Enter search/play · ↑↓ select · Esc close ");
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 3 {
+            return;
+        }
+
+        let prompt = Line::from(vec![
+            Span::styled("/ ", Style::default().fg(self.theme.accent)),
+            Span::styled(self.query, Style::default().fg(self.theme.foreground)),
+            Span::styled("█", Style::default().fg(self.theme.accent)),
+        ]);
+        Paragraph::new(prompt).render(Rect::new(inner.x, inner.y, inner.width, 1), buf);
+
+        let list_area = Rect::new(inner.x, inner.y + 2, inner.width, inner.height - 2);
+
+        if let Some(message) = self.message {
+            Paragraph::new(message)
+                .style(Style::default().fg(self.theme.dim))
+                .alignment(Alignment::Center)
+                .render(list_area, buf);
+            return;
+        }
+
+        let height = list_area.height as usize;
+        let selected = self.selected.min(self.results.len().saturating_sub(1));
+        let start = (selected + 1).saturating_sub(height);
+
+        let lines: Vec<Line> = self
+            .results
+            .iter()
+            .enumerate()
+            .skip(start)
+            .take(height)
+            .map(|(i, item)| {
+                let is_selected = i == selected;
+                let marker = if is_selected { "▸ " } else { "  " };
+                let name_style = if is_selected {
+                    Style::default().fg(self.theme.accent).add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(self.theme.foreground)
+                };
+
+                let mut spans = vec![
+                    Span::styled(marker, Style::default().fg(self.theme.accent)),
+                    Span::styled(
+                        format!("{} ", kind_label(item.kind)),
+                        Style::default().fg(self.theme.dim),
+                    ),
+                    Span::styled(item.name.clone(), name_style),
+                ];
+                if !item.detail.is_empty() {
+                    spans.push(Span::styled(
+                        format!(" - {}", item.detail),
+                        Style::default().fg(self.theme.dim),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect();
+
+        Paragraph::new(lines).render(list_area, buf);
+    }
+}
@@ -0,0 +1,93 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    widgets::{Paragraph, Widget},
+};
+
+use crate::tui::theme::Theme;
+
+const LOGO: [&str; 5] = [
+    "███  █  █  ██   ███ ███  █  █  ██  ███ ",
+    "█  █ █  █ █  █ █    █  █ █  █ █  █ █  █",
+    "███  ████ █  █  ██  ███  ████ █  █ ███ ",
+    "█    █  █ █  █    █ █    █  █ █  █ █ █ ",
+    "█    █  █  ██  ███  █    █  █  ██  █  █",
+];
+
+const TAGLINE: &str = "retro terminal dashboard";
+
+/// Boot logo drawn in the theme gradient, fading towards the background
+pub struct SplashWidget<'a> {
+    theme: &'a Theme,
+    brightness: f32,
+    /// Share of the logo's columns drawn so far, left to right
+    reveal: f32,
+}
+
+impl<'a> SplashWidget<'a> {
+    /// `brightness` runs from 1.0 (fully lit) down to 0.0 (faded out)
+    pub fn new(theme: &'a Theme, brightness: f32) -> Self {
+        Self {
+            theme,
+            brightness: brightness.clamp(0.0, 1.0),
+            reveal: 1.0,
+        }
+    }
+
+    /// Draw only the first `reveal` (0.0-1.0) of the logo, as if the beam were
+    /// still sweeping across it; the tagline waits for the whole logo
+    pub fn reveal(mut self, reveal: f32) -> Self {
+        self.reveal = reveal.clamp(0.0, 1.0);
+        self
+    }
+}
+
+impl Widget for SplashWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let logo_width = LOGO[0].chars().count() as u16;
+        let logo_height = LOGO.len() as u16;
+
+        // Logo, tagline and a blank spacer row between them
+        let total_height = logo_height + 2;
+        if area.width < logo_width || area.height < total_height {
+            // Too small for the logo, fall back to the name
+            let y = area.y + area.height / 2;
+            Paragraph::new("PHOSPHOR")
+                .style(Style::default().fg(self.theme.fade(self.theme.accent, 1.0 - self.brightness)))
+                .alignment(Alignment::Center)
+                .render(Rect::new(area.x, y, area.width, 1), buf);
+            return;
+        }
+
+        let x = area.x + (area.width - logo_width) / 2;
+        let drawn = (logo_width as f32 * self.reveal).ceil() as usize;
+        let y = area.y + (area.height - total_height) / 2;
+
+        for (row, line) in LOGO.iter().enumerate() {
+            // Brighten towards the bottom of the logo like a warming CRT
+            let intensity = 0.4 + 0.6 * row as f32 / (LOGO.len() - 1) as f32;
+            let color = self
+                .theme
+                .fade(self.theme.gradient(intensity), 1.0 - self.brightness);
+
+            for (col, ch) in line.chars().enumerate().take(drawn) {
+                if ch != ' ' {
+                    buf[(x + col as u16, y + row as u16)]
+                        .set_char(ch)
+                        .set_fg(color);
+                }
+            }
+        }
+
+        if self.reveal < 1.0 {
+            return;
+        }
+
+        let tagline_color = self.theme.fade(self.theme.dim, 1.0 - self.brightness);
+        Paragraph::new(TAGLINE)
+            .style(Style::default().fg(tagline_color))
+            .alignment(Alignment::Center)
+            .render(Rect::new(area.x, y + logo_height + 1, area.width, 1), buf);
+    }
+}
@@ -3,21 +3,59 @@ use ratatui::{
     layout::{Alignment, Constraint, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Paragraph, Widget, Wrap},
 };
 
-use crate::modules::spotify::TrackInfo;
+use std::time::Duration;
+
+use crate::modules::spotify::{format_time, needs_hours, SpotifyStatus, TrackInfo};
 use crate::tui::theme::Theme;
 
+/// Breathing room kept around the time labels and bar
+const PROGRESS_MARGIN: usize = 4;
+
+/// Lines the full view takes; shorter panels get the two-line compact view
+const FULL_LINES: u16 = 6;
+
 pub struct SpotifyWidget<'a> {
     track: Option<&'a TrackInfo>,
+    status: SpotifyStatus,
     theme: &'a Theme,
     focused: bool,
+    /// Beat intensity (0-1) to pulse the title with, None when disabled
+    pulse: Option<f32>,
+    /// Time left on the sleep timer
+    sleep: Option<Duration>,
+    /// Where the volume keys go, e.g. "pulse"
+    volume_backend: Option<&'a str>,
+    /// Tempo of the captured audio
+    bpm: Option<f32>,
 }
 
 impl<'a> SpotifyWidget<'a> {
-    pub fn new(track: Option<&'a TrackInfo>, theme: &'a Theme, focused: bool) -> Self {
-        Self { track, theme, focused }
+    pub fn new(
+        track: Option<&'a TrackInfo>,
+        status: SpotifyStatus,
+        theme: &'a Theme,
+        focused: bool,
+        pulse: Option<f32>,
+    ) -> Self {
+        Self { track, status, theme, focused, pulse, sleep: None, volume_backend: None, bpm: None }
+    }
+
+    pub fn sleep(mut self, remaining: Option<Duration>) -> Self {
+        self.sleep = remaining;
+        self
+    }
+
+    pub fn volume_backend(mut self, backend: Option<&'a str>) -> Self {
+        self.volume_backend = backend;
+        self
+    }
+
+    pub fn bpm(mut self, bpm: Option<f32>) -> Self {
+        self.bpm = bpm;
+        self
     }
 }
 
@@ -29,16 +67,33 @@ impl Widget for SpotifyWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title(" ♫ Now Playing ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let mut block = self.theme.block(" ♫ Now Playing ", border_style);
+        if let Some(remaining) = self.sleep.filter(|_| !self.theme.borderless()) {
+            // Round up so the countdown reads 00:01 until the very end
+            let ms = remaining.as_millis().div_ceil(1000) as u64 * 1000;
+            let countdown = format!(" ☾ {} ", format_time(ms, needs_hours(ms)));
+            block = block.title(
+                Line::styled(countdown, Style::default().fg(self.theme.accent)).right_aligned(),
+            );
+        }
+
+        if let Some(bpm) = self.bpm.filter(|_| !self.theme.borderless()) {
+            // The note flashes on the beat along with the title
+            let note = match self.pulse {
+                Some(beat) => self.theme.gradient(0.4 + 0.6 * beat),
+                None => self.theme.accent,
+            };
+            block = block.title_bottom(Line::from(vec![
+                Span::styled(" ♩ ", Style::default().fg(note)),
+                Span::styled(format!("{:.0} BPM ", bpm), Style::default().fg(self.theme.dim)),
+            ]));
+        }
 
         let inner = block.inner(area);
         block.render(area, buf);
 
         match self.track {
+            Some(track) if inner.height < FULL_LINES => self.render_compact(track, inner, buf),
             Some(track) => self.render_track(track, inner, buf),
             None => self.render_empty(inner, buf),
         }
@@ -46,20 +101,44 @@ impl Widget for SpotifyWidget<'_> {
 }
 
 impl SpotifyWidget<'_> {
+    /// Position in `track` under a click at `column`, or None when `row` isn't
+    /// the progress bar of the widget drawn in `area`
+    pub fn seek_target(track: &TrackInfo, theme: &Theme, area: Rect, column: u16, row: u16) -> Option<u64> {
+        let inner = theme.block("", Style::default()).inner(area);
+        // The bar is the fifth line of `render_track`, the second of `render_compact`
+        let bar_row = if inner.height < FULL_LINES { 1 } else { 4 };
+        if track.duration == 0 || inner.height <= bar_row || row != inner.y + bar_row {
+            return None;
+        }
+        let (progress_str, duration_str, width) = progress_labels(track.progress, track.duration, inner.width);
+        if width == 0 {
+            return None;
+        }
+        // Centered like the rendered line
+        let line_width = progress_str.len() + width + duration_str.len() + 2;
+        let start = inner.x as usize + (inner.width as usize).saturating_sub(line_width) / 2 + progress_str.len() + 1;
+        let offset = (column as usize).checked_sub(start).filter(|&offset| offset < width)?;
+        Some(((offset as f64 + 0.5) / width as f64 * track.duration as f64) as u64)
+    }
+
     fn render_track(&self, track: &TrackInfo, area: Rect, buf: &mut Buffer) {
         let chunks = Layout::vertical([
             Constraint::Length(1), // Track name
             Constraint::Length(1), // Artist
             Constraint::Length(1), // Album
-            Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Playing from (or spacer)
             Constraint::Length(1), // Progress bar
             Constraint::Length(1), // Controls hint
         ])
         .split(area);
 
-        // Track name
+        // Track name, pulsing with the beat while playing
         let status_icon = if track.is_playing { "▶" } else { "⏸" };
-        let track_line = Line::from(vec![
+        let title_color = match self.pulse {
+            Some(beat) if track.is_playing => self.theme.gradient(0.75 + 0.25 * beat),
+            _ => self.theme.foreground,
+        };
+        let mut track_spans = vec![
             Span::styled(
                 format!("{} ", status_icon),
                 Style::default().fg(self.theme.accent),
@@ -67,11 +146,17 @@ impl SpotifyWidget<'_> {
             Span::styled(
                 &track.name,
                 Style::default()
-                    .fg(self.theme.foreground)
+                    .fg(title_color)
                     .add_modifier(Modifier::BOLD),
             ),
-        ]);
-        Paragraph::new(track_line).render(chunks[0], buf);
+        ];
+        // Liked Songs marker, once the library has been checked
+        match track.saved {
+            Some(true) => track_spans.push(Span::styled(" ♥", Style::default().fg(self.theme.accent))),
+            Some(false) => track_spans.push(Span::styled(" ♡", Style::default().fg(self.theme.dim))),
+            None => {}
+        }
+        Paragraph::new(Line::from(track_spans)).render(chunks[0], buf);
 
         // Artist
         let artist_line = Line::from(vec![
@@ -87,22 +172,64 @@ impl SpotifyWidget<'_> {
         ]);
         Paragraph::new(album_line).render(chunks[2], buf);
 
-        // Progress bar
-        if let Some(progress) = track.progress {
-            self.render_progress(progress, track.duration, chunks[4], buf);
+        // Playlist/album/artist the track comes from
+        if let Some(context) = &track.context {
+            let context_line = Line::from(vec![
+                Span::styled("  from ", Style::default().fg(self.theme.dim)),
+                Span::styled(context.label(), Style::default().fg(self.theme.accent)),
+            ]);
+            Paragraph::new(context_line).render(chunks[3], buf);
         }
 
+        // Progress bar
+        self.render_progress(track.progress, track.duration, chunks[4], buf);
+
         // Controls hint - retro icons, centered
-        let controls = Line::from(vec![
+        let mut controls_spans = vec![
             Span::styled("⏮ p  ⏸ space  ⏭ n  vol +/-", Style::default().fg(self.theme.dim)),
-        ]);
+        ];
+        if let Some(backend) = self.volume_backend {
+            controls_spans.push(Span::styled(
+                format!(" ({})", backend),
+                Style::default().fg(self.theme.dim),
+            ));
+        }
+        let controls = Line::from(controls_spans);
         Paragraph::new(controls)
             .alignment(Alignment::Center)
             .render(chunks[5], buf);
     }
 
-    fn render_progress(&self, progress: u64, duration: u64, area: Rect, buf: &mut Buffer) {
-        let width = area.width.saturating_sub(16) as usize;
+    /// Title and artist on one line, the progress bar on the next, for short
+    /// terminals
+    fn render_compact(&self, track: &TrackInfo, area: Rect, buf: &mut Buffer) {
+        let [title_area, progress_area] = Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(area);
+
+        let status_icon = if track.is_playing { "▶" } else { "⏸" };
+        let title_color = match self.pulse {
+            Some(beat) if track.is_playing => self.theme.gradient(0.75 + 0.25 * beat),
+            _ => self.theme.foreground,
+        };
+        let mut spans = vec![
+            Span::styled(format!("{} ", status_icon), Style::default().fg(self.theme.accent)),
+            Span::styled(&track.name, Style::default().fg(title_color).add_modifier(Modifier::BOLD)),
+        ];
+        if let Some(true) = track.saved {
+            spans.push(Span::styled(" ♥", Style::default().fg(self.theme.accent)));
+        }
+        if !track.artist.is_empty() {
+            spans.push(Span::styled(" - ", Style::default().fg(self.theme.dim)));
+            spans.push(Span::styled(&track.artist, Style::default().fg(self.theme.foreground)));
+        }
+        Paragraph::new(Line::from(spans)).render(title_area, buf);
+
+        self.render_progress(track.progress, track.duration, progress_area, buf);
+    }
+
+    fn render_progress(&self, progress: Option<u64>, duration: u64, area: Rect, buf: &mut Buffer) {
+        let (progress_str, duration_str, width) = progress_labels(progress, duration, area.width);
+        let progress = progress.unwrap_or(0);
+
         let pct = if duration > 0 {
             (progress as f64 / duration as f64).min(1.0)
         } else {
@@ -111,32 +238,48 @@ impl SpotifyWidget<'_> {
         let filled = (pct * width as f64) as usize;
         let empty = width.saturating_sub(filled);
 
-        let progress_str = format!(
-            "{:02}:{:02}",
-            progress / 60000,
-            (progress / 1000) % 60
-        );
-        let duration_str = format!(
-            "{:02}:{:02}",
-            duration / 60000,
-            (duration / 1000) % 60
-        );
-
-        let bar = Line::from(vec![
-            Span::styled(format!("{} ", progress_str), Style::default().fg(self.theme.dim)),
-            Span::styled("█".repeat(filled), Style::default().fg(self.theme.accent)),
-            Span::styled("░".repeat(empty), Style::default().fg(self.theme.dim)),
-            Span::styled(format!(" {}", duration_str), Style::default().fg(self.theme.dim)),
-        ]);
+        let bar = if width == 0 {
+            // Too narrow for a bar, keep just the times
+            Line::from(Span::styled(
+                format!("{} / {}", progress_str, duration_str),
+                Style::default().fg(self.theme.dim),
+            ))
+        } else {
+            Line::from(vec![
+                Span::styled(format!("{} ", progress_str), Style::default().fg(self.theme.dim)),
+                Span::styled("█".repeat(filled), Style::default().fg(self.theme.accent)),
+                Span::styled("░".repeat(empty), Style::default().fg(self.theme.dim)),
+                Span::styled(format!(" {}", duration_str), Style::default().fg(self.theme.dim)),
+            ])
+        };
         Paragraph::new(bar)
             .alignment(Alignment::Center)
             .render(area, buf);
     }
 
     fn render_empty(&self, area: Rect, buf: &mut Buffer) {
-        let text = Paragraph::new("Nothing playing")
+        let text = Paragraph::new(self.status.idle_hint())
             .style(Style::default().fg(self.theme.dim))
-            .alignment(Alignment::Center);
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true });
         text.render(area, buf);
     }
 }
+
+/// Time labels either side of the progress bar, and the bar's width on a line
+/// `line_width` wide
+fn progress_labels(progress: Option<u64>, duration: u64, line_width: u16) -> (String, String, usize) {
+    let with_hours = needs_hours(duration);
+    // Spotify transiently omits the position; show an empty bar rather than nothing
+    let progress_str = match progress {
+        Some(ms) => format_time(ms, with_hours),
+        None if with_hours => "-:--:--".to_string(),
+        None => "--:--".to_string(),
+    };
+    let duration_str = format_time(duration, with_hours);
+
+    // Reserve the actual label widths plus the spaces either side of the bar
+    let reserved = progress_str.len() + duration_str.len() + 2 + PROGRESS_MARGIN;
+    let width = (line_width as usize).saturating_sub(reserved);
+    (progress_str, duration_str, width)
+}
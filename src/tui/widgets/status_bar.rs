@@ -0,0 +1,87 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::tui::theme::Theme;
+
+/// One line along the bottom: clock, playback device, capture backend and
+/// volume on the left, key hints on the right
+pub struct StatusBarWidget<'a> {
+    theme: &'a Theme,
+    /// Spotify Connect device or MPRIS player
+    device: Option<&'a str>,
+    /// Audio capture backend, e.g. "pulse"
+    audio_backend: &'a str,
+    volume: u8,
+    /// Where the volume keys go, None until the player reports it
+    volume_backend: Option<&'a str>,
+    /// (keys, what they do) pairs, e.g. the rest of a chord being typed
+    hints: Vec<(String, String)>,
+}
+
+impl<'a> StatusBarWidget<'a> {
+    pub fn new(theme: &'a Theme, audio_backend: &'a str) -> Self {
+        Self { theme, device: None, audio_backend, volume: 0, volume_backend: None, hints: Vec::new() }
+    }
+
+    pub fn device(mut self, device: Option<&'a str>) -> Self {
+        self.device = device;
+        self
+    }
+
+    pub fn volume(mut self, volume: u8, backend: Option<&'a str>) -> Self {
+        self.volume = volume;
+        self.volume_backend = backend;
+        self
+    }
+
+    pub fn hints(mut self, hints: Vec<(String, String)>) -> Self {
+        self.hints = hints;
+        self
+    }
+}
+
+impl Widget for StatusBarWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dim = Style::default().fg(self.theme.dim);
+        let text = Style::default().fg(self.theme.foreground);
+        let separator = || Span::styled(" │ ", dim);
+
+        let mut left = vec![
+            Span::raw(" "),
+            Span::styled(chrono::Local::now().format("%H:%M").to_string(), Style::default().fg(self.theme.accent)),
+        ];
+        if let Some(device) = self.device.filter(|device| !device.is_empty()) {
+            left.extend([separator(), Span::styled("⏵ ", dim), Span::styled(device, text)]);
+        }
+        left.extend([separator(), Span::styled("♪ ", dim), Span::styled(self.audio_backend, text)]);
+        if let Some(backend) = self.volume_backend {
+            left.extend([
+                separator(),
+                Span::styled("vol ", dim),
+                Span::styled(format!("{}%", self.volume), text),
+                Span::styled(format!(" ({})", backend), dim),
+            ]);
+        }
+
+        let mut right = Vec::new();
+        for (keys, label) in &self.hints {
+            right.extend([
+                Span::styled(keys.clone(), Style::default().fg(self.theme.accent)),
+                Span::styled(format!(" {}  ", label), dim),
+            ]);
+        }
+        let right = Line::from(right);
+
+        // Hints win over the left side when the line is too narrow for both
+        let right_width = (right.width() as u16).min(area.width);
+        let left_area = Rect { width: area.width - right_width, ..area };
+        Paragraph::new(Line::from(left)).render(left_area, buf);
+        let right_area = Rect { x: area.right() - right_width, width: right_width, ..area };
+        Paragraph::new(right).render(right_area, buf);
+    }
+}
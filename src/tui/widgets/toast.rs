@@ -0,0 +1,183 @@
+use chrono::{DateTime, Local};
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Rect},
+    style::Style,
+    text::{Line, Span},
+    widgets::{Clear, Paragraph, Widget, Wrap},
+};
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use crate::tui::theme::Theme;
+
+/// How long a toast stays up; errors get longer to be read
+const INFO_DURATION: Duration = Duration::from_secs(3);
+const ERROR_DURATION: Duration = Duration::from_secs(6);
+
+/// Toasts stacked in the corner at once, newest at the bottom
+const MAX_SHOWN: usize = 3;
+
+/// Messages kept for the scrollback
+const LOG_SIZE: usize = 100;
+
+/// Widest a toast gets, borders included
+const MAX_WIDTH: u16 = 60;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Level {
+    Info,
+    Error,
+}
+
+pub struct Toast {
+    message: String,
+    level: Level,
+    posted: Instant,
+    /// Wall-clock time for the scrollback
+    time: DateTime<Local>,
+}
+
+impl Toast {
+    fn active(&self) -> bool {
+        let duration = match self.level {
+            Level::Info => INFO_DURATION,
+            Level::Error => ERROR_DURATION,
+        };
+        self.posted.elapsed() < duration
+    }
+}
+
+/// Recent messages, the newest of which show as toasts until they expire
+#[derive(Default)]
+pub struct Toasts {
+    log: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, level: Level, message: String) {
+        // The same message again only brings it back up
+        if let Some(last) = self.log.back_mut().filter(|last| last.message == message && last.level == level) {
+            last.posted = Instant::now();
+            last.time = Local::now();
+            return;
+        }
+        if self.log.len() == LOG_SIZE {
+            self.log.pop_front();
+        }
+        self.log.push_back(Toast { message, level, posted: Instant::now(), time: Local::now() });
+    }
+
+    /// Toasts still up, oldest first
+    pub fn active(&self) -> Vec<&Toast> {
+        let mut active: Vec<&Toast> = self.log.iter().rev().take(MAX_SHOWN).filter(|toast| toast.active()).collect();
+        active.reverse();
+        active
+    }
+
+    pub fn len(&self) -> usize {
+        self.log.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.log.is_empty()
+    }
+}
+
+/// The active toasts stacked up from the bottom-right corner of the area
+pub struct ToastsWidget<'a> {
+    toasts: Vec<&'a Toast>,
+    theme: &'a Theme,
+}
+
+impl<'a> ToastsWidget<'a> {
+    pub fn new(toasts: &'a Toasts, theme: &'a Theme) -> Self {
+        Self { toasts: toasts.active(), theme }
+    }
+}
+
+impl Widget for ToastsWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Leave the panels' bottom and right borders showing
+        let mut bottom = area.bottom().saturating_sub(1);
+        for toast in self.toasts.iter().rev() {
+            let width = (toast.message.chars().count() as u16 + 4).min(MAX_WIDTH).min(area.width.saturating_sub(1));
+            if width < 5 || bottom < area.y + 3 {
+                break;
+            }
+            let rect = Rect::new(area.right() - 1 - width, bottom - 3, width, 3);
+            bottom -= 3;
+
+            let color = match toast.level {
+                Level::Info => self.theme.accent,
+                Level::Error => self.theme.warning,
+            };
+            Clear.render(rect, buf);
+            let block = self.theme.frame()
+                .border_style(Style::default().fg(color))
+                .style(Style::default().bg(self.theme.bg()));
+            Paragraph::new(format!(" {}", toast.message))
+                .style(Style::default().fg(self.theme.foreground))
+                .block(block)
+                .render(rect, buf);
+        }
+    }
+}
+
+/// Every message kept, newest first, for the `M` overlay
+pub struct MessagesWidget<'a> {
+    toasts: &'a Toasts,
+    /// Messages scrolled past at the top
+    scroll: usize,
+    theme: &'a Theme,
+}
+
+impl<'a> MessagesWidget<'a> {
+    pub fn new(toasts: &'a Toasts, scroll: usize, theme: &'a Theme) -> Self {
+        Self { toasts, scroll, theme }
+    }
+}
+
+impl Widget for MessagesWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let block = self.theme.frame()
+            .border_style(Style::default().fg(self.theme.accent))
+            .title(format!(" Messages ({}) ", self.toasts.len()))
+            .title_style(Style::default().fg(self.theme.foreground))
+            .title_bottom(Line::styled(" ↑↓ scroll · Esc close ", Style::default().fg(self.theme.dim)).right_aligned())
+            .style(Style::default().bg(self.theme.bg()));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if self.toasts.is_empty() {
+            let y_offset = inner.height / 2;
+            if y_offset < inner.height {
+                let text_area = Rect::new(inner.x, inner.y + y_offset, inner.width, inner.height - y_offset);
+                Paragraph::new("Nothing yet")
+                    .style(Style::default().fg(self.theme.dim))
+                    .alignment(Alignment::Center)
+                    .render(text_area, buf);
+            }
+            return;
+        }
+
+        let lines: Vec<Line> = self
+            .toasts
+            .log
+            .iter()
+            .rev()
+            .skip(self.scroll)
+            .map(|toast| {
+                let color = match toast.level {
+                    Level::Info => self.theme.foreground,
+                    Level::Error => self.theme.warning,
+                };
+                Line::from(vec![
+                    Span::styled(format!(" {} ", toast.time.format("%H:%M:%S")), Style::default().fg(self.theme.dim)),
+                    Span::styled(toast.message.clone(), Style::default().fg(color)),
+                ])
+            })
+            .collect();
+        Paragraph::new(lines).wrap(Wrap { trim: false }).render(inner, buf);
+    }
+}
@@ -1,24 +1,416 @@
 use ratatui::{
     buffer::Buffer,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::Style,
-    widgets::{Block, Borders, Widget},
+    text::Line,
+    widgets::Widget,
 };
 
-use crate::modules::audio::AudioData;
-use crate::tui::theme::Theme;
+use anyhow::{bail, Context, Result};
+
+use crate::config::AudioConfig;
+use crate::modules::audio::{AudioData, DEFAULT_SAMPLE_RATE, SpectrumHistory, SpectrumPeaks};
+use crate::tui::theme::{GradientAxis, Theme};
 
 const BAR_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
 
+/// Braille dot bits by (row, column) within a 2x4 cell
+const BRAILLE_BASE: u32 = 0x2800;
+const BRAILLE_DOTS: [[u32; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+/// Dots plotted at 2x4 per cell, remembering how many landed in each cell
+struct BrailleGrid {
+    width: usize,
+    height: usize,
+    cells: Vec<(u32, u16)>,
+}
+
+impl BrailleGrid {
+    fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![(0, 0); width * height],
+        }
+    }
+
+    /// Set the dot at (x, y) in dot coordinates; out of range is ignored
+    fn plot(&mut self, x: usize, y: usize) {
+        let (cx, cy) = (x / 2, y / 4);
+        if cx >= self.width || cy >= self.height {
+            return;
+        }
+        let cell = &mut self.cells[cy * self.width + cx];
+        cell.0 |= BRAILLE_DOTS[y % 4][x % 2];
+        cell.1 = cell.1.saturating_add(1);
+    }
+
+    /// Draw lit cells at `area`'s origin, colored by how many dots hit them
+    fn render(&self, area: Rect, buf: &mut Buffer, theme: &Theme) {
+        for (i, &(dots, hits)) in self.cells.iter().enumerate() {
+            if dots == 0 {
+                continue;
+            }
+            let (cx, cy) = (i % self.width, i / self.width);
+            let ch = char::from_u32(BRAILLE_BASE | dots).unwrap_or(' ');
+            let intensity = 0.4 + 0.6 * (hits as f32 / 8.0).min(1.0);
+            buf[(area.x + cx as u16, area.y + cy as u16)]
+                .set_char(ch)
+                .set_fg(theme.gradient(intensity));
+        }
+    }
+}
+
+/// How the visualizers show the two channels
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChannelMode {
+    /// The mono mix
+    Mono,
+    /// Left channel on the left half, right on the right; spectrum bass meets in the middle
+    Split,
+    /// Left channel on top, right below; spectrum bars grow away from the center line
+    Stacked,
+}
+
+impl ChannelMode {
+    /// Config names, in cycling order
+    pub const NAMES: [&'static str; 3] = ["mono", "split", "stacked"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "mono" => Some(ChannelMode::Mono),
+            "split" => Some(ChannelMode::Split),
+            "stacked" => Some(ChannelMode::Stacked),
+            _ => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ChannelMode::Mono => ChannelMode::Split,
+            ChannelMode::Split => ChannelMode::Stacked,
+            ChannelMode::Stacked => ChannelMode::Mono,
+        }
+    }
+
+    fn title_suffix(self) -> &'static str {
+        match self {
+            ChannelMode::Mono => "",
+            ChannelMode::Split => "L│R ",
+            ChannelMode::Stacked => "L/R ",
+        }
+    }
+
+    /// Areas for the left and right channel, or None in mono
+    fn split(self, area: Rect) -> Option<[Rect; 2]> {
+        match self {
+            ChannelMode::Mono => None,
+            ChannelMode::Split => Some(Layout::horizontal([Constraint::Ratio(1, 2); 2]).areas(area)),
+            ChannelMode::Stacked => Some(Layout::vertical([Constraint::Ratio(1, 2); 2]).areas(area)),
+        }
+    }
+}
+
+/// How the waveform trace is drawn
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum WaveformStyle {
+    /// A vertical line per column, from the lowest to the highest sample in it
+    #[default]
+    Bars,
+    /// Braille dots, 2x4 per cell, for a finer trace
+    Braille,
+}
+
+impl WaveformStyle {
+    /// Config names, in cycling order
+    pub const NAMES: [&'static str; 2] = ["bars", "braille"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "bars" => Some(WaveformStyle::Bars),
+            "braille" => Some(WaveformStyle::Braille),
+            _ => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            WaveformStyle::Bars => WaveformStyle::Braille,
+            WaveformStyle::Braille => WaveformStyle::Bars,
+        }
+    }
+}
+
+/// How the spectrum bars are laid out within each channel
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum SpectrumMode {
+    /// Bars growing up from the bottom
+    #[default]
+    Bars,
+    /// Bars growing up and down from a center line
+    Mirror,
+    /// Two halves facing away from each other, bass in the middle
+    Wings,
+}
+
+impl SpectrumMode {
+    /// Config names, in cycling order
+    pub const NAMES: [&'static str; 3] = ["bars", "mirror", "wings"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "bars" => Some(SpectrumMode::Bars),
+            "mirror" => Some(SpectrumMode::Mirror),
+            "wings" => Some(SpectrumMode::Wings),
+            _ => None,
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            SpectrumMode::Bars => SpectrumMode::Mirror,
+            SpectrumMode::Mirror => SpectrumMode::Wings,
+            SpectrumMode::Wings => SpectrumMode::Bars,
+        }
+    }
+
+    /// Where a channel drawn in `area` with the given flips puts its bars, as
+    /// (area, flip_x, flip_y). Mirror and wings override the channel's own
+    /// vertical and horizontal flip respectively
+    fn parts(self, area: Rect, flip_x: bool, flip_y: bool) -> Vec<(Rect, bool, bool)> {
+        match self {
+            SpectrumMode::Bars => vec![(area, flip_x, flip_y)],
+            SpectrumMode::Mirror => {
+                let [top, bottom] = Layout::vertical([Constraint::Ratio(1, 2); 2]).areas(area);
+                vec![(top, flip_x, false), (bottom, flip_x, true)]
+            }
+            SpectrumMode::Wings => {
+                let [left, right] = Layout::horizontal([Constraint::Ratio(1, 2); 2]).areas(area);
+                vec![(left, true, flip_y), (right, false, flip_y)]
+            }
+        }
+    }
+}
+
+/// Frequency range and bar geometry from `[audio.spectrum]`
+#[derive(Clone, Copy, Debug)]
+pub struct SpectrumShape {
+    pub min_freq: f32,
+    pub max_freq: f32,
+    /// Requested bar count, 0 to fill the width
+    pub bars: usize,
+    pub bar_width: u16,
+    pub bar_gap: u16,
+    /// Hz labels along the bottom
+    pub labels: bool,
+}
+
+impl Default for SpectrumShape {
+    fn default() -> Self {
+        Self {
+            min_freq: 30.0,
+            max_freq: 16_000.0,
+            bars: 0,
+            bar_width: 1,
+            bar_gap: 0,
+            labels: false,
+        }
+    }
+}
+
+impl SpectrumShape {
+    /// Bars that fit in `width` columns
+    fn bar_count(&self, width: u16) -> usize {
+        let fit = (width + self.bar_gap) / (self.bar_width + self.bar_gap);
+        match self.bars {
+            0 => fit as usize,
+            bars => bars.min(fit as usize),
+        }
+    }
+
+    /// Columns before the first of `count` bars, centering them in `width`
+    fn bar_offset(&self, count: usize, width: usize) -> usize {
+        let step = (self.bar_width + self.bar_gap) as usize;
+        let used = (count * step).saturating_sub(self.bar_gap as usize);
+        width.saturating_sub(used) / 2
+    }
+
+    /// Ends of the frequency range as positions on `scale`, capped at the
+    /// highest frequency the capture rate can carry
+    fn range(&self, scale: FrequencyScale, sample_rate: u32) -> (f32, f32) {
+        let nyquist = sample_rate as f32 / 2.0;
+        (
+            scale.position(self.min_freq.min(nyquist)),
+            scale.position(self.max_freq.min(nyquist)),
+        )
+    }
+}
+
+/// Spectrum and waveform settings from `[audio]`
+#[derive(Clone, Copy, Debug)]
+pub struct VisualizerOptions {
+    pub channels: ChannelMode,
+    pub mode: SpectrumMode,
+    pub waveform: WaveformStyle,
+    pub scale: FrequencyScale,
+    pub shape: SpectrumShape,
+}
+
+impl VisualizerOptions {
+    pub fn from_config(config: &AudioConfig) -> Result<Self> {
+        let channels = ChannelMode::from_name(&config.stereo).with_context(|| {
+            format!(
+                "Unknown audio.stereo '{}' (expected one of: {})",
+                config.stereo,
+                ChannelMode::NAMES.join(", ")
+            )
+        })?;
+        let scale = FrequencyScale::from_name(&config.scale).with_context(|| {
+            format!(
+                "Unknown audio.scale '{}' (expected one of: {})",
+                config.scale,
+                FrequencyScale::NAMES.join(", ")
+            )
+        })?;
+
+        let waveform = WaveformStyle::from_name(&config.waveform).with_context(|| {
+            format!(
+                "Unknown audio.waveform '{}' (expected one of: {})",
+                config.waveform,
+                WaveformStyle::NAMES.join(", ")
+            )
+        })?;
+
+        let spectrum = &config.spectrum;
+        let mode = SpectrumMode::from_name(&spectrum.mode).with_context(|| {
+            format!(
+                "Unknown audio.spectrum.mode '{}' (expected one of: {})",
+                spectrum.mode,
+                SpectrumMode::NAMES.join(", ")
+            )
+        })?;
+        if !(spectrum.min_freq > 0.0 && spectrum.min_freq < spectrum.max_freq) {
+            bail!(
+                "audio.spectrum needs 0 < min_freq < max_freq (got {} and {})",
+                spectrum.min_freq,
+                spectrum.max_freq
+            );
+        }
+        if spectrum.bar_width == 0 {
+            bail!("audio.spectrum.bar_width must be at least 1");
+        }
+        if spectrum.gravity < 0.0 {
+            bail!("audio.spectrum.gravity can't be negative (0 turns the peaks off)");
+        }
+        if config.agc.attack < 0.0 || config.agc.release < 0.0 {
+            bail!("audio.agc.attack and release are seconds and can't be negative");
+        }
+        let smoothing = &config.smoothing;
+        for (name, value) in [
+            ("attack", smoothing.attack),
+            ("decay", smoothing.decay),
+            ("waveform", smoothing.waveform),
+        ] {
+            // 0 would freeze the visualizer on its first frame
+            if !(value > 0.0 && value <= 1.0) {
+                bail!("audio.smoothing.{} must be above 0 and at most 1 (got {})", name, value);
+            }
+        }
+        let shape = SpectrumShape {
+            min_freq: spectrum.min_freq,
+            max_freq: spectrum.max_freq,
+            bars: spectrum.bars,
+            bar_width: spectrum.bar_width,
+            bar_gap: spectrum.bar_gap,
+            labels: spectrum.labels,
+        };
+
+        Ok(Self { channels, mode, waveform, scale, shape })
+    }
+
+    /// Heights (0-1) of `count` mono bars as the spectrum panel would draw
+    /// them, for `audio.output`
+    pub fn levels(&self, data: &AudioData, count: usize, auto_gain: bool) -> Vec<f32> {
+        let mut levels = bands(&data.spectrum, count, self.scale, &self.shape, data.sample_rate);
+        let max_val = if auto_gain {
+            1.0
+        } else {
+            levels.iter().cloned().fold(0.0f32, f32::max).max(0.0001)
+        };
+        for level in &mut levels {
+            *level = (*level / max_val).sqrt().min(1.0);
+        }
+        levels
+    }
+
+    /// How much a spectrum bin at `hz` counts once drawn: its bar's weighting,
+    /// or 0 outside the shown range. Auto gain follows the loudest bin by this
+    pub fn weight(&self, hz: f32) -> f32 {
+        if (self.shape.min_freq..=self.shape.max_freq).contains(&hz) {
+            self.scale.weight(hz)
+        } else {
+            0.0
+        }
+    }
+}
+
 pub struct SpectrumWidget<'a> {
     data: &'a AudioData,
     theme: &'a Theme,
     focused: bool,
+    channels: ChannelMode,
+    mode: SpectrumMode,
+    scale: FrequencyScale,
+    shape: SpectrumShape,
+    peaks: Option<&'a SpectrumPeaks>,
+    auto_gain: bool,
 }
 
 impl<'a> SpectrumWidget<'a> {
     pub fn new(data: &'a AudioData, theme: &'a Theme, focused: bool) -> Self {
-        Self { data, theme, focused }
+        Self {
+            data,
+            theme,
+            focused,
+            channels: ChannelMode::Mono,
+            mode: SpectrumMode::Bars,
+            scale: FrequencyScale::Linear,
+            shape: SpectrumShape::default(),
+            peaks: None,
+            auto_gain: false,
+        }
+    }
+
+    pub fn channels(mut self, mode: ChannelMode) -> Self {
+        self.channels = mode;
+        self
+    }
+
+    pub fn mode(mut self, mode: SpectrumMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn scale(mut self, scale: FrequencyScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn shape(mut self, shape: SpectrumShape) -> Self {
+        self.shape = shape;
+        self
+    }
+
+    pub fn peaks(mut self, peaks: Option<&'a SpectrumPeaks>) -> Self {
+        self.peaks = peaks;
+        self
+    }
+
+    /// The spectra were already scaled to 1.0 by `AutoGain`; otherwise each
+    /// frame is scaled to its own loudest bar
+    pub fn auto_gain(mut self, auto_gain: bool) -> Self {
+        self.auto_gain = auto_gain;
+        self
     }
 }
 
@@ -30,11 +422,7 @@ impl Widget for SpectrumWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title("  Spectrum ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let block = self.theme.block(format!("  Spectrum {}", self.channels.title_suffix()), border_style);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -43,61 +431,320 @@ impl Widget for SpectrumWidget<'_> {
     }
 }
 
-impl SpectrumWidget<'_> {
-    fn render_spectrum(&self, area: Rect, buf: &mut Buffer) {
-        let width = area.width as usize;
-        let height = area.height as usize;
+/// Band weighting leaves this frequency as-is, lifting those above it
+const WEIGHT_REFERENCE: f32 = 1_000.0;
 
-        if width == 0 || height == 0 || self.data.spectrum.is_empty() {
-            return;
+/// How FFT bins are spread across the spectrum bars
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FrequencyScale {
+    /// Equal Hz per bar
+    Linear,
+    /// Equal octaves per bar
+    Log,
+    /// Perceptual mel scale: close to linear in the bass, logarithmic above
+    Mel,
+}
+
+impl FrequencyScale {
+    /// Config names
+    pub const NAMES: [&'static str; 3] = ["linear", "log", "mel"];
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "linear" => Some(FrequencyScale::Linear),
+            "log" => Some(FrequencyScale::Log),
+            "mel" => Some(FrequencyScale::Mel),
+            _ => None,
+        }
+    }
+
+    /// Position of a frequency along the scale
+    fn position(self, hz: f32) -> f32 {
+        match self {
+            FrequencyScale::Linear => hz,
+            FrequencyScale::Log => hz.ln(),
+            FrequencyScale::Mel => 2595.0 * (1.0 + hz / 700.0).log10(),
         }
+    }
+
+    fn frequency(self, position: f32) -> f32 {
+        match self {
+            FrequencyScale::Linear => position,
+            FrequencyScale::Log => position.exp(),
+            FrequencyScale::Mel => 700.0 * (10f32.powf(position / 2595.0) - 1.0),
+        }
+    }
+
+    /// Factor a bar at `hz` is drawn with. Music loses roughly 3 dB per octave;
+    /// the octave-based scales tilt it back so the highs move too
+    fn weight(self, hz: f32) -> f32 {
+        match self {
+            FrequencyScale::Linear => 1.0,
+            _ => (hz / WEIGHT_REFERENCE).sqrt(),
+        }
+    }
+}
+
+/// Frequencies that get a label when the spectrum has room for them
+const LABEL_FREQUENCIES: [f32; 10] = [
+    20.0, 50.0, 100.0, 200.0, 500.0, 1_000.0, 2_000.0, 5_000.0, 10_000.0, 20_000.0,
+];
+
+/// Group the FFT bins into `count` bars spread over `min_freq..max_freq`; the
+/// bins are `sample_rate / 2 / spectrum.len()` Hz apart
+fn bands(
+    spectrum: &[f32],
+    count: usize,
+    scale: FrequencyScale,
+    shape: &SpectrumShape,
+    sample_rate: u32,
+) -> Vec<f32> {
+    if spectrum.is_empty() || count == 0 {
+        return vec![0.0; count];
+    }
+
+    let last_bin = spectrum.len() - 1;
+    let bin_hz = sample_rate as f32 / 2.0 / spectrum.len() as f32;
+    let (low, high) = shape.range(scale, sample_rate);
+    let edge = |i: usize| scale.frequency(low + (high - low) * i as f32 / count as f32);
+
+    (0..count)
+        .map(|i| {
+            let (from, to) = (edge(i), edge(i + 1));
+            let first = ((from / bin_hz).ceil() as usize).min(last_bin);
+            let end = ((to / bin_hz).ceil() as usize).min(spectrum.len());
+
+            let value = if end > first {
+                // Average the bins for this bar
+                spectrum[first..end].iter().sum::<f32>() / (end - first) as f32
+            } else {
+                // Bars narrower than a bin interpolate at their center
+                let position = ((from + to) / 2.0 / bin_hz).min(last_bin as f32);
+                let bin = position as usize;
+                let next = (bin + 1).min(last_bin);
+                let t = position.fract();
+                spectrum[bin] * (1.0 - t) + spectrum[next] * t
+            };
 
-        // Focus on lower frequencies (more musical content there)
-        let useful_bins = self.data.spectrum.len().min(width * 2);
-        let bins_per_bar = (useful_bins / width).max(1);
+            value * scale.weight((from * to).sqrt())
+        })
+        .collect()
+}
+
+/// Bar levels of one channel, with the peak markers above them
+struct Bars {
+    levels: Vec<f32>,
+    peaks: Option<Vec<f32>>,
+}
 
-        // Find max for normalization
-        let max_val = self.data.spectrum[..useful_bins]
+impl Bars {
+    /// Largest bar, for scaling frames without auto gain
+    fn max(&self) -> f32 {
+        self.levels
             .iter()
             .cloned()
             .fold(0.0f32, f32::max)
-            .max(0.0001); // Avoid division by zero
+            .max(0.0001) // Avoid division by zero
+    }
+}
 
-        for x in 0..width {
-            let start = x * bins_per_bar;
-            let end = ((x + 1) * bins_per_bar).min(self.data.spectrum.len());
+impl SpectrumWidget<'_> {
+    fn render_spectrum(&self, area: Rect, buf: &mut Buffer) {
+        if area.width == 0 || area.height == 0 || self.data.spectrum.is_empty() {
+            return;
+        }
 
-            if start >= self.data.spectrum.len() {
-                break;
+        // Labels take the bottom row once there's room left for the bars
+        let (area, labels) = if self.shape.labels && area.height >= 3 {
+            let [bars, labels] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(area);
+            (bars, Some(labels))
+        } else {
+            (area, None)
+        };
+
+        let rate = self.data.sample_rate;
+        let bars = |spectrum: &[f32], peaks: Option<&[f32]>, area: Rect| {
+            let count = self.shape.bar_count(area.width);
+            Bars {
+                levels: bands(spectrum, count, self.scale, &self.shape, rate),
+                peaks: peaks.map(|peaks| bands(peaks, count, self.scale, &self.shape, rate)),
             }
+        };
 
-            // Average the bins for this bar
-            let avg: f32 = self.data.spectrum[start..end].iter().sum::<f32>()
-                / (end - start) as f32;
+        // Each channel with its orientation: split turns the left channel's bass
+        // to the middle, stacked hangs the right channel from the center line
+        let [left, right] = &self.data.stereo;
+        let channels = match self.channels.split(area) {
+            None => vec![(area, self.data.spectrum.as_slice(), self.peaks.map(SpectrumPeaks::mono), false, false)],
+            Some([left_area, right_area]) => {
+                let split = self.channels == ChannelMode::Split;
+                vec![
+                    (left_area, left.spectrum.as_slice(), self.peaks.map(|p| p.channel(0)), split, false),
+                    (right_area, right.spectrum.as_slice(), self.peaks.map(|p| p.channel(1)), false, !split),
+                ]
+            }
+        };
+
+        let parts: Vec<(Bars, Rect, bool, bool)> = channels
+            .into_iter()
+            .flat_map(|(area, spectrum, peaks, flip_x, flip_y)| {
+                self.mode
+                    .parts(area, flip_x, flip_y)
+                    .into_iter()
+                    .map(move |(area, flip_x, flip_y)| (bars(spectrum, peaks, area), area, flip_x, flip_y))
+            })
+            .collect();
+
+        // One scale for every channel and half so their levels can be compared
+        let max_val = if self.auto_gain {
+            1.0
+        } else {
+            parts.iter().map(|(bars, ..)| bars.max()).fold(0.0, f32::max)
+        };
+        for (bars, area, flip_x, flip_y) in &parts {
+            self.render_bars(bars, max_val, *area, buf, *flip_x, *flip_y);
+        }
+
+        if let Some(labels) = labels {
+            // Labels run the same way as the bars above them
+            let halves = match self.channels {
+                ChannelMode::Split => {
+                    let [left, right] = Layout::horizontal([Constraint::Ratio(1, 2); 2]).areas(labels);
+                    vec![(left, true), (right, false)]
+                }
+                _ => vec![(labels, false)],
+            };
+            for (area, flip_x) in halves {
+                let segments = match self.mode {
+                    SpectrumMode::Wings => self.mode.parts(area, flip_x, false),
+                    _ => vec![(area, flip_x, false)],
+                };
+                for (area, flip_x, _) in segments {
+                    self.render_labels(area, buf, flip_x);
+                }
+            }
+        }
+    }
+
+    /// Hz labels in a one-row `area`, each centered under the bar holding its
+    /// frequency; labels that would run into the previous one are skipped
+    fn render_labels(&self, area: Rect, buf: &mut Buffer, flip_x: bool) {
+        let width = area.width as usize;
+        let count = self.shape.bar_count(area.width);
+        if count == 0 {
+            return;
+        }
+
+        let (low, high) = self.shape.range(self.scale, self.data.sample_rate);
+        let step = (self.shape.bar_width + self.shape.bar_gap) as f32;
+        let offset = self.shape.bar_offset(count, width) as f32;
+        let mut labels: Vec<(usize, String)> = LABEL_FREQUENCIES
+            .iter()
+            .filter(|&&hz| hz >= self.shape.min_freq && hz <= self.shape.max_freq)
+            .filter_map(|&hz| {
+                let bar = (self.scale.position(hz) - low) / (high - low) * count as f32;
+                if !(0.0..count as f32).contains(&bar) {
+                    return None;
+                }
+                let column = (offset + bar.floor() * step + self.shape.bar_width as f32 / 2.0) as usize;
+                let column = if flip_x { width.saturating_sub(column + 1) } else { column };
+                let text = if hz >= 1_000.0 {
+                    format!("{}k", hz / 1_000.0)
+                } else {
+                    format!("{}", hz)
+                };
+                let start = column.saturating_sub(text.len() / 2).min(width.saturating_sub(text.len()));
+                Some((start, text))
+            })
+            .collect();
+        labels.sort_by_key(|(start, _)| *start);
+
+        let mut free = 0;
+        for (start, text) in labels {
+            if start < free {
+                continue;
+            }
+            buf.set_string(area.x + start as u16, area.y, &text, Style::default().fg(self.theme.dim));
+            free = start + text.len() + 1;
+        }
+    }
+
+    /// Bars laid out left to right, centered, growing up; `flip_x` puts bass
+    /// on the right and `flip_y` hangs the bars from the top
+    fn render_bars(
+        &self,
+        bars: &Bars,
+        max_val: f32,
+        area: Rect,
+        buf: &mut Buffer,
+        flip_x: bool,
+        flip_y: bool,
+    ) {
+        let width = area.width as usize;
+        let height = area.height as usize;
+
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let step = (self.shape.bar_width + self.shape.bar_gap) as usize;
+        let offset = self.shape.bar_offset(bars.levels.len(), width);
+        let last = bars.levels.len().saturating_sub(1).max(1) as f32;
+
+        for (i, &avg) in bars.levels.iter().enumerate() {
+            let left = offset + i * step;
+            let columns = left..(left + self.shape.bar_width as usize).min(width);
 
             // Normalize to max and apply some boost for visibility
             let normalized = (avg / max_val).sqrt(); // sqrt gives nicer curve
             let bar_height = (normalized * height as f32).min(height as f32) as usize;
 
-            // Draw the bar from bottom up
+            // Peak marker in the cell its level falls in, unless the bar covers it
+            let peak = bars.peaks.as_ref().map(|peaks| {
+                let level = (peaks[i] / max_val).sqrt() * height as f32;
+                (level as usize).min(height - 1)
+            });
+            if let Some(y) = peak.filter(|&y| y >= bar_height && y > 0) {
+                let cell_y = area.y + if flip_y { y } else { height - 1 - y } as u16;
+                for x in columns.clone() {
+                    let cell_x = area.x + if flip_x { width - 1 - x } else { x } as u16;
+                    buf[(cell_x, cell_y)]
+                        .set_char(if flip_y { '▔' } else { '▁' })
+                        .set_fg(self.theme.accent);
+                }
+            }
+
+            // Draw the bar from its base outwards
             for y in 0..height {
-                let cell_y = area.y + (height - 1 - y) as u16;
-                let cell_x = area.x + x as u16;
+                let cell_y = area.y + if flip_y { y } else { height - 1 - y } as u16;
 
-                if y < bar_height {
-                    let intensity = y as f32 / height as f32;
-                    let color = self.theme.gradient(intensity);
-                    buf[(cell_x, cell_y)]
-                        .set_char('█')
-                        .set_fg(color);
+                let ch = if y < bar_height {
+                    '█'
                 } else if y == bar_height && bar_height > 0 {
                     // Partial block at top
                     let frac = (normalized * height as f32) - bar_height as f32 + 1.0;
                     let char_idx = ((frac * 8.0) as usize).min(7);
-                    let intensity = y as f32 / height as f32;
-                    let color = self.theme.gradient(intensity);
+                    // Only half and eighth blocks exist at the top of a cell
+                    match (flip_y, char_idx) {
+                        (false, i) => BAR_CHARS[i],
+                        (true, 0..=2) => '▔',
+                        (true, 3..=6) => '▀',
+                        (true, _) => '█',
+                    }
+                } else {
+                    continue;
+                };
+                let intensity = match self.theme.spectrum_gradient {
+                    GradientAxis::Height => y as f32 / height as f32,
+                    // Kept off the dim end so the bass still stands out from the background
+                    GradientAxis::Frequency => 0.35 + 0.65 * i as f32 / last,
+                };
+                let color = self.theme.gradient(intensity);
+
+                for x in columns.clone() {
+                    let cell_x = area.x + if flip_x { width - 1 - x } else { x } as u16;
                     buf[(cell_x, cell_y)]
-                        .set_char(BAR_CHARS[char_idx])
+                        .set_char(ch)
                         .set_fg(color);
                 }
             }
@@ -105,15 +752,216 @@ impl SpectrumWidget<'_> {
     }
 }
 
+/// Recent spectra as a heatmap: time runs left to right (newest at the right
+/// edge), frequency bottom to top, two bands per row using half blocks
+pub struct SpectrogramWidget<'a> {
+    history: &'a SpectrumHistory,
+    theme: &'a Theme,
+    focused: bool,
+    scale: FrequencyScale,
+    shape: SpectrumShape,
+    sample_rate: u32,
+}
+
+impl<'a> SpectrogramWidget<'a> {
+    pub fn new(history: &'a SpectrumHistory, theme: &'a Theme, focused: bool) -> Self {
+        Self {
+            history,
+            theme,
+            focused,
+            scale: FrequencyScale::Linear,
+            shape: SpectrumShape::default(),
+            sample_rate: DEFAULT_SAMPLE_RATE,
+        }
+    }
+
+    pub fn scale(mut self, scale: FrequencyScale) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Rate the history was captured at
+    pub fn sample_rate(mut self, sample_rate: u32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Only the frequency range is used; bar geometry doesn't apply
+    pub fn shape(mut self, shape: SpectrumShape) -> Self {
+        self.shape = shape;
+        self
+    }
+}
+
+impl Widget for SpectrogramWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(self.theme.accent)
+        } else {
+            Style::default().fg(self.theme.dim)
+        };
+
+        let block = self.theme.block("  Spectrogram ", border_style);
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        self.render_heatmap(inner, buf);
+    }
+}
+
+impl SpectrogramWidget<'_> {
+    fn render_heatmap(&self, area: Rect, buf: &mut Buffer) {
+        let width = area.width as usize;
+        let height = area.height as usize;
+
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        let columns: Vec<Vec<f32>> = self
+            .history
+            .latest(width)
+            .map(|spectrum| bands(spectrum, height * 2, self.scale, &self.shape, self.sample_rate))
+            .collect();
+
+        // One scale for the whole window so a loud moment stands out from the rest
+        let max_val = columns
+            .iter()
+            .flatten()
+            .cloned()
+            .fold(0.0f32, f32::max)
+            .max(0.0001);
+        let color = |value: f32| {
+            let intensity = (value / max_val).sqrt();
+            self.theme.fade(self.theme.gradient(intensity), 1.0 - intensity)
+        };
+
+        // Right-align so the newest frame sits at the edge while history fills up
+        let offset = width - columns.len();
+        for (i, column) in columns.iter().enumerate() {
+            let cell_x = area.x + (offset + i) as u16;
+            for row in 0..height {
+                // Lowest bands at the bottom row
+                let cell_y = area.y + (height - 1 - row) as u16;
+                let (lower, upper) = (column[row * 2], column[row * 2 + 1]);
+                buf[(cell_x, cell_y)]
+                    .set_char('▀')
+                    .set_fg(color(upper))
+                    .set_bg(color(lower));
+            }
+        }
+    }
+}
+
+/// Goniometer: every left/right sample pair as a braille dot, rotated 45° so
+/// mono sits on the vertical axis and out-of-phase audio on the horizontal.
+/// The title shows the phase correlation (+1 mono, 0 wide, -1 out of phase)
+pub struct ScopeWidget<'a> {
+    data: &'a AudioData,
+    theme: &'a Theme,
+    focused: bool,
+}
+
+impl<'a> ScopeWidget<'a> {
+    pub fn new(data: &'a AudioData, theme: &'a Theme, focused: bool) -> Self {
+        Self { data, theme, focused }
+    }
+
+    fn correlation(&self) -> Option<f32> {
+        let [left, right] = &self.data.stereo;
+        let (mut lr, mut ll, mut rr) = (0.0f32, 0.0f32, 0.0f32);
+        for (&l, &r) in left.waveform.iter().zip(&right.waveform) {
+            lr += l * r;
+            ll += l * l;
+            rr += r * r;
+        }
+        let energy = (ll * rr).sqrt();
+        (energy > 1e-9).then(|| lr / energy)
+    }
+}
+
+impl Widget for ScopeWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let border_style = if self.focused {
+            Style::default().fg(self.theme.accent)
+        } else {
+            Style::default().fg(self.theme.dim)
+        };
+
+        let mut block = self.theme.block("  Scope ", border_style);
+        if let Some(correlation) = self.correlation().filter(|_| !self.theme.borderless()) {
+            block = block.title(
+                Line::styled(format!(" {:+.2} ", correlation), Style::default().fg(self.theme.dim))
+                    .right_aligned(),
+            );
+        }
+
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        self.render_scope(inner, buf);
+    }
+}
+
+impl ScopeWidget<'_> {
+    fn render_scope(&self, area: Rect, buf: &mut Buffer) {
+        // A square of dots: cells are about twice as tall as wide, dots are 2x4 per cell
+        let side = (area.width as usize * 2).min(area.height as usize * 4);
+        if side < 4 {
+            return;
+        }
+        let plot = Rect {
+            x: area.x + (area.width - side.div_ceil(2) as u16) / 2,
+            y: area.y + (area.height - side.div_ceil(4) as u16) / 2,
+            width: side.div_ceil(2) as u16,
+            height: side.div_ceil(4) as u16,
+        };
+
+        let [left, right] = &self.data.stereo;
+        let peak = waveform_peak(&left.waveform).max(waveform_peak(&right.waveform));
+
+        let mut grid = BrailleGrid::new(plot.width as usize, plot.height as usize);
+        let half = (side - 1) as f32 / 2.0;
+        for (&l, &r) in left.waveform.iter().zip(&right.waveform) {
+            // Mid up, side across; the corners of the square are full scale
+            let mid = (l + r) / (2.0 * peak);
+            let side = (l - r) / (2.0 * peak);
+            let x = (half + side * half).round().clamp(0.0, 2.0 * half) as usize;
+            let y = (half - mid * half).round().clamp(0.0, 2.0 * half) as usize;
+            grid.plot(x, y);
+        }
+        grid.render(plot, buf, self.theme);
+    }
+}
+
 pub struct WaveformWidget<'a> {
     data: &'a AudioData,
     theme: &'a Theme,
     focused: bool,
+    channels: ChannelMode,
+    style: WaveformStyle,
 }
 
 impl<'a> WaveformWidget<'a> {
     pub fn new(data: &'a AudioData, theme: &'a Theme, focused: bool) -> Self {
-        Self { data, theme, focused }
+        Self {
+            data,
+            theme,
+            focused,
+            channels: ChannelMode::Mono,
+            style: WaveformStyle::Bars,
+        }
+    }
+
+    pub fn channels(mut self, mode: ChannelMode) -> Self {
+        self.channels = mode;
+        self
+    }
+
+    pub fn style(mut self, style: WaveformStyle) -> Self {
+        self.style = style;
+        self
     }
 }
 
@@ -125,11 +973,7 @@ impl Widget for WaveformWidget<'_> {
             Style::default().fg(self.theme.dim)
         };
 
-        let block = Block::default()
-            .borders(Borders::ALL)
-            .border_style(border_style)
-            .title("  Waveform ")
-            .title_style(Style::default().fg(self.theme.foreground));
+        let block = self.theme.block(format!("  Waveform {}", self.channels.title_suffix()), border_style);
 
         let inner = block.inner(area);
         block.render(area, buf);
@@ -138,34 +982,60 @@ impl Widget for WaveformWidget<'_> {
     }
 }
 
+/// Normalize to peak amplitude so waveform fills the full height
+/// Floor of 0.2 prevents quiet audio from being overamplified
+fn waveform_peak(waveform: &[f32]) -> f32 {
+    waveform.iter()
+        .fold(0.0f32, |acc, &s| acc.max(s.abs()))
+        .max(0.0005)
+}
+
 impl WaveformWidget<'_> {
     fn render_waveform(&self, area: Rect, buf: &mut Buffer) {
+        if self.data.waveform.is_empty() {
+            return;
+        }
+
+        let Some([left_area, right_area]) = self.channels.split(area) else {
+            let peak = waveform_peak(&self.data.waveform);
+            self.render_trace(&self.data.waveform, peak, area, buf);
+            return;
+        };
+
+        // Both channels share one scale so their levels can be compared
+        let [left, right] = &self.data.stereo;
+        let peak = waveform_peak(&left.waveform).max(waveform_peak(&right.waveform));
+        self.render_trace(&left.waveform, peak, left_area, buf);
+        self.render_trace(&right.waveform, peak, right_area, buf);
+    }
+
+    fn render_trace(&self, waveform: &[f32], peak: f32, area: Rect, buf: &mut Buffer) {
         let width = area.width as usize;
         let height = area.height as usize;
 
-        if width == 0 || height == 0 || self.data.waveform.is_empty() {
+        if width == 0 || height == 0 {
             return;
         }
 
-        let samples_per_point = self.data.waveform.len() / width;
-        let mid_y = height / 2;
+        if self.style == WaveformStyle::Braille {
+            self.render_braille(waveform, peak, area, buf);
+            self.render_center_line(area, buf);
+            return;
+        }
 
-        // Normalize to peak amplitude so waveform fills the full height
-        // Floor of 0.2 prevents quiet audio from being overamplified
-        let peak = self.data.waveform.iter()
-            .fold(0.0f32, |acc, &s| acc.max(s.abs()))
-            .max(0.0005);
+        let samples_per_point = (waveform.len() / width).max(1);
+        let mid_y = height / 2;
 
         for x in 0..width {
             let start = x * samples_per_point;
-            let end = ((x + 1) * samples_per_point).min(self.data.waveform.len());
+            let end = ((x + 1) * samples_per_point).min(waveform.len());
 
-            if start >= self.data.waveform.len() {
+            if start >= waveform.len() {
                 break;
             }
 
             // Get min and max in this slice for better visualization
-            let slice = &self.data.waveform[start..end];
+            let slice = &waveform[start..end];
             let min_val = slice.iter().cloned().fold(f32::INFINITY, f32::min);
             let max_val = slice.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
 
@@ -195,8 +1065,35 @@ impl WaveformWidget<'_> {
             }
         }
 
-        // Draw center line
-        for x in 0..width {
+        self.render_center_line(area, buf);
+    }
+
+    /// The same trace at braille resolution: each dot column spans the lowest
+    /// to the highest sample under it, so steep edges stay connected
+    fn render_braille(&self, waveform: &[f32], peak: f32, area: Rect, buf: &mut Buffer) {
+        let (dots_x, dots_y) = (area.width as usize * 2, area.height as usize * 4);
+        let mut grid = BrailleGrid::new(area.width as usize, area.height as usize);
+        let samples_per_dot = (waveform.len() / dots_x).max(1);
+        let to_dot = |sample: f32| {
+            let value = (sample / peak).clamp(-1.0, 1.0);
+            (((1.0 - value) * 0.5 * dots_y as f32) as usize).min(dots_y - 1)
+        };
+
+        for (x, slice) in waveform.chunks(samples_per_dot).take(dots_x).enumerate() {
+            let min_val = slice.iter().cloned().fold(f32::INFINITY, f32::min);
+            let max_val = slice.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+            for y in to_dot(max_val)..=to_dot(min_val) {
+                grid.plot(x, y);
+            }
+        }
+
+        grid.render(area, buf, self.theme);
+    }
+
+    /// Dim line through the middle wherever the trace left the cell empty
+    fn render_center_line(&self, area: Rect, buf: &mut Buffer) {
+        let mid_y = area.height as usize / 2;
+        for x in 0..area.width as usize {
             let cell_x = area.x + x as u16;
             let cell_y = area.y + mid_y as u16;
 